@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS set_updated_at ON todos;
+
+ALTER TABLE todos
+  DROP COLUMN updated_at,
+  DROP COLUMN created_at;
@@ -0,0 +1,5 @@
+ALTER TABLE todos
+  ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+SELECT diesel_manage_updated_at('todos');
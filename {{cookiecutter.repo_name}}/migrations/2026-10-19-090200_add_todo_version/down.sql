@@ -0,0 +1,4 @@
+DROP TRIGGER IF EXISTS increment_version ON todos;
+DROP FUNCTION IF EXISTS todos_increment_version();
+
+ALTER TABLE todos DROP COLUMN version;
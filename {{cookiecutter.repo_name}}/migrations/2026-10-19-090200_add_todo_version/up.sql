@@ -0,0 +1,15 @@
+ALTER TABLE todos ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+
+-- Bumps `version` whenever a todo row is modified, so optimistic concurrency
+-- checks (`WHERE version = $expected`) cover every mutation.
+CREATE OR REPLACE FUNCTION todos_increment_version() RETURNS trigger AS $$
+BEGIN
+    IF (NEW IS DISTINCT FROM OLD) THEN
+        NEW.version := OLD.version + 1;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER increment_version BEFORE UPDATE ON todos
+    FOR EACH ROW EXECUTE PROCEDURE todos_increment_version();
@@ -0,0 +1,13 @@
+CREATE TABLE idempotency_keys (
+  key VARCHAR PRIMARY KEY,
+  request_fingerprint VARCHAR NOT NULL,
+  response_status SMALLINT,
+  response_content_type VARCHAR,
+  response_body BYTEA,
+  -- Headers replayed along with the stored response, such as ETag and Location
+  response_headers JSONB,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idempotency_keys_expires_at_idx ON idempotency_keys (expires_at);
@@ -0,0 +1,3 @@
+ALTER TABLE todos
+  DROP COLUMN completed_by,
+  DROP COLUMN completed_at;
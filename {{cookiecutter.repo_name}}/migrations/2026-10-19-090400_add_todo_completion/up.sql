@@ -0,0 +1,5 @@
+ALTER TABLE todos
+  ADD COLUMN completed_at TIMESTAMPTZ,
+  ADD COLUMN completed_by VARCHAR;
+
+UPDATE todos SET completed_at = updated_at WHERE completed;
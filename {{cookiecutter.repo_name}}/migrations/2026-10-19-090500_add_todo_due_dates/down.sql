@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS todos_due_at_idx;
+
+ALTER TABLE todos
+  DROP COLUMN remind_at,
+  DROP COLUMN due_at;
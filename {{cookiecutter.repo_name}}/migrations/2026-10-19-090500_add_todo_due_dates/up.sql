@@ -0,0 +1,5 @@
+ALTER TABLE todos
+  ADD COLUMN due_at TIMESTAMPTZ,
+  ADD COLUMN remind_at TIMESTAMPTZ;
+
+CREATE INDEX todos_due_at_idx ON todos (due_at) WHERE due_at IS NOT NULL;
@@ -0,0 +1,19 @@
+CREATE TABLE jobs (
+  id BIGSERIAL PRIMARY KEY,
+  kind VARCHAR NOT NULL,
+  payload JSONB NOT NULL DEFAULT '{}',
+  status VARCHAR NOT NULL DEFAULT 'pending',
+  attempts INTEGER NOT NULL DEFAULT 0,
+  max_attempts INTEGER NOT NULL DEFAULT 5,
+  run_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  locked_at TIMESTAMPTZ,
+  last_error TEXT,
+  dedup_key VARCHAR UNIQUE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CONSTRAINT jobs_status_check CHECK (status IN ('pending', 'running', 'completed', 'failed'))
+);
+
+CREATE INDEX jobs_status_run_at_idx ON jobs (status, run_at);
+
+SELECT diesel_manage_updated_at('jobs');
@@ -0,0 +1,8 @@
+-- Reminders that have been sent, keyed by the reminder time so that moving
+-- `todos.remind_at` schedules a new reminder.
+CREATE TABLE todo_reminders (
+  todo_id INTEGER NOT NULL REFERENCES todos (id) ON DELETE CASCADE,
+  remind_at TIMESTAMPTZ NOT NULL,
+  sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (todo_id, remind_at)
+);
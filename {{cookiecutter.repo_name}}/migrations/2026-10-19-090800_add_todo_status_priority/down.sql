@@ -0,0 +1,8 @@
+DROP TRIGGER IF EXISTS sync_completion ON todos;
+DROP FUNCTION IF EXISTS todos_sync_completion();
+
+DROP INDEX IF EXISTS todos_status_priority_idx;
+ALTER TABLE todos DROP COLUMN status, DROP COLUMN priority;
+
+DROP TYPE todo_status;
+DROP TYPE todo_priority;
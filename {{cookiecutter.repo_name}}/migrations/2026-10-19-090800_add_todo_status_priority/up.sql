@@ -0,0 +1,33 @@
+CREATE TYPE todo_status AS ENUM ('backlog', 'todo', 'in_progress', 'blocked', 'done');
+CREATE TYPE todo_priority AS ENUM ('low', 'medium', 'high', 'urgent');
+
+ALTER TABLE todos
+    ADD COLUMN status todo_status NOT NULL DEFAULT 'todo',
+    ADD COLUMN priority todo_priority NOT NULL DEFAULT 'medium';
+
+UPDATE todos SET status = 'done' WHERE completed;
+
+CREATE INDEX todos_status_priority_idx ON todos (status, priority) WHERE deleted_at IS NULL;
+
+-- Keeps `completed`, `completed_at` and `completed_by` in line with `status`,
+-- which is the source of truth for completion from now on.
+CREATE OR REPLACE FUNCTION todos_sync_completion() RETURNS trigger AS $$
+BEGIN
+    IF (TG_OP = 'UPDATE') THEN
+        IF (NEW.status IS NOT DISTINCT FROM OLD.status) THEN
+            RETURN NEW;
+        END IF;
+    END IF;
+    NEW.completed := NEW.status = 'done';
+    IF (NEW.status = 'done') THEN
+        NEW.completed_at := COALESCE(NEW.completed_at, NOW());
+    ELSE
+        NEW.completed_at := NULL;
+        NEW.completed_by := NULL;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER sync_completion BEFORE INSERT OR UPDATE ON todos
+    FOR EACH ROW EXECUTE PROCEDURE todos_sync_completion();
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS todos_list_id_idx;
+ALTER TABLE todos DROP COLUMN list_id;
+
+DROP TABLE todo_lists;
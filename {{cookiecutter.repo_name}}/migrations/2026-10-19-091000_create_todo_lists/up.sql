@@ -0,0 +1,24 @@
+CREATE TABLE todo_lists (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR NOT NULL,
+  description TEXT NOT NULL DEFAULT '',
+  owner VARCHAR,
+  archived BOOLEAN NOT NULL DEFAULT FALSE,
+  archived_at TIMESTAMPTZ,
+  deleted_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+SELECT diesel_manage_updated_at('todo_lists');
+
+-- Deleted lists are kept along with their soft-deleted todos until they are purged, so the
+-- todos keep their list and stay hidden with it
+CREATE INDEX todo_lists_deleted_at_idx ON todo_lists (deleted_at) WHERE deleted_at IS NOT NULL;
+
+-- Deleting a list deletes its todos, the service refuses to do so for lists
+-- that still have todos unless asked to cascade. Purging a deleted list removes
+-- its todos through the foreign key.
+ALTER TABLE todos ADD COLUMN list_id INTEGER REFERENCES todo_lists (id) ON DELETE CASCADE;
+
+CREATE INDEX todos_list_id_idx ON todos (list_id);
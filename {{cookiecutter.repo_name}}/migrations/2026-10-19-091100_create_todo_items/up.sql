@@ -0,0 +1,13 @@
+CREATE TABLE todo_items (
+  id SERIAL PRIMARY KEY,
+  todo_id INTEGER NOT NULL REFERENCES todos (id) ON DELETE CASCADE,
+  title VARCHAR NOT NULL,
+  position INTEGER NOT NULL,
+  done BOOLEAN NOT NULL DEFAULT FALSE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  -- Deferred so that items can be shifted and reordered within a transaction
+  CONSTRAINT todo_items_todo_id_position_key UNIQUE (todo_id, position) DEFERRABLE INITIALLY DEFERRED
+);
+
+SELECT diesel_manage_updated_at('todo_items');
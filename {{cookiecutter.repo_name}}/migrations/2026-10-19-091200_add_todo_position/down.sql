@@ -0,0 +1,14 @@
+CREATE OR REPLACE FUNCTION todos_increment_version() RETURNS trigger AS $$
+BEGIN
+    IF (NEW IS DISTINCT FROM OLD) THEN
+        NEW.version := OLD.version + 1;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS assign_position ON todos;
+DROP FUNCTION IF EXISTS todos_assign_position();
+DROP FUNCTION IF EXISTS todos_lock_positions(INTEGER);
+DROP INDEX IF EXISTS todos_list_position_idx;
+ALTER TABLE todos DROP COLUMN position;
@@ -0,0 +1,52 @@
+-- Positions are spaced 1024 apart (`TODO_POSITION_GAP`) so a todo can be moved
+-- between two others by only updating its own row. They order the todos of a
+-- list (or those outside of any list).
+ALTER TABLE todos ADD COLUMN position BIGINT;
+
+UPDATE todos SET position = id::BIGINT * 1024;
+
+ALTER TABLE todos ALTER COLUMN position SET NOT NULL;
+
+CREATE INDEX todos_list_position_idx ON todos (list_id, position);
+
+-- Serializes position assignment within a list until the end of the transaction,
+-- so concurrent appends and moves do not pick the same position.
+CREATE OR REPLACE FUNCTION todos_lock_positions(_list_id INTEGER) RETURNS VOID AS $$
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('todos_position'), hashtext(COALESCE(_list_id::TEXT, '')));
+END;
+$$ LANGUAGE plpgsql;
+
+-- New todos, and todos moved to another list, are appended after the last one of their list
+CREATE OR REPLACE FUNCTION todos_assign_position() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'INSERT' AND NEW.position IS NOT NULL THEN
+        RETURN NEW;
+    END IF;
+    IF TG_OP = 'UPDATE' AND (NEW.list_id IS NOT DISTINCT FROM OLD.list_id OR NEW.position IS DISTINCT FROM OLD.position) THEN
+        RETURN NEW;
+    END IF;
+    PERFORM todos_lock_positions(NEW.list_id);
+    SELECT COALESCE(MAX(position), 0) + 1024 INTO NEW.position FROM todos
+        WHERE list_id IS NOT DISTINCT FROM NEW.list_id;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER assign_position BEFORE INSERT OR UPDATE OF list_id ON todos
+    FOR EACH ROW EXECUTE PROCEDURE todos_assign_position();
+
+-- Renumbering positions only changes `position` and is bookkeeping, so it keeps the
+-- version. Moving a todo bumps the version itself.
+CREATE OR REPLACE FUNCTION todos_increment_version() RETURNS trigger AS $$
+BEGIN
+    IF (NEW IS DISTINCT FROM OLD) THEN
+        IF NEW.position IS DISTINCT FROM OLD.position
+            AND to_jsonb(NEW) - 'position' = to_jsonb(OLD) - 'position' THEN
+            RETURN NEW;
+        END IF;
+        NEW.version := OLD.version + 1;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
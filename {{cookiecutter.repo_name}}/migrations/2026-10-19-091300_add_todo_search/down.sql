@@ -0,0 +1,15 @@
+CREATE OR REPLACE FUNCTION todos_increment_version() RETURNS trigger AS $$
+BEGIN
+    IF (NEW IS DISTINCT FROM OLD) THEN
+        IF NEW.position IS DISTINCT FROM OLD.position
+            AND to_jsonb(NEW) - 'position' = to_jsonb(OLD) - 'position' THEN
+            RETURN NEW;
+        END IF;
+        NEW.version := OLD.version + 1;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP INDEX IF EXISTS todos_search_vector_idx;
+ALTER TABLE todos DROP COLUMN search_vector;
@@ -0,0 +1,22 @@
+-- Titles weigh more than descriptions when ranking search results
+ALTER TABLE todos ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+    setweight(to_tsvector('english', coalesce(description, '')), 'B')
+) STORED;
+
+CREATE INDEX todos_search_vector_idx ON todos USING GIN (search_vector);
+
+-- The generated `search_vector` is not computed yet in BEFORE triggers, so it is left out
+-- when checking whether only the position changed.
+CREATE OR REPLACE FUNCTION todos_increment_version() RETURNS trigger AS $$
+BEGIN
+    IF (NEW IS DISTINCT FROM OLD) THEN
+        IF NEW.position IS DISTINCT FROM OLD.position
+            AND to_jsonb(NEW) - ARRAY['position', 'search_vector'] = to_jsonb(OLD) - ARRAY['position', 'search_vector'] THEN
+            RETURN NEW;
+        END IF;
+        NEW.version := OLD.version + 1;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,12 @@
+CREATE TABLE comments (
+  id SERIAL PRIMARY KEY,
+  todo_id INTEGER NOT NULL REFERENCES todos (id) ON DELETE CASCADE,
+  author VARCHAR,
+  body TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+SELECT diesel_manage_updated_at('comments');
+
+CREATE INDEX comments_todo_id_created_at_idx ON comments (todo_id, created_at);
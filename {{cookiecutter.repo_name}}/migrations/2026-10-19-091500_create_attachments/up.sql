@@ -0,0 +1,14 @@
+CREATE TABLE attachments (
+  id SERIAL PRIMARY KEY,
+  todo_id INTEGER NOT NULL REFERENCES todos (id) ON DELETE CASCADE,
+  file_name VARCHAR NOT NULL,
+  content_type VARCHAR NOT NULL,
+  size BIGINT NOT NULL,
+  -- SHA-256 of the content, hex encoded
+  checksum VARCHAR NOT NULL,
+  -- Key of the content in the configured blob store
+  storage_key VARCHAR NOT NULL UNIQUE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX attachments_todo_id_idx ON attachments (todo_id);
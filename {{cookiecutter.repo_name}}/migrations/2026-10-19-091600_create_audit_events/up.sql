@@ -0,0 +1,14 @@
+-- No foreign key to todos: the history of a todo outlives the todo once it is purged
+CREATE TABLE audit_events (
+  id BIGSERIAL PRIMARY KEY,
+  todo_id INTEGER NOT NULL,
+  action VARCHAR NOT NULL,
+  actor VARCHAR,
+  request_id VARCHAR,
+  changes JSONB NOT NULL DEFAULT '{}',
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX audit_events_todo_id_idx ON audit_events (todo_id, id);
+CREATE INDEX audit_events_created_at_idx ON audit_events (created_at);
+CREATE INDEX audit_events_actor_idx ON audit_events (actor);
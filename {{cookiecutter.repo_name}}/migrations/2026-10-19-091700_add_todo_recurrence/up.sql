@@ -0,0 +1,3 @@
+-- The recurrence as given by the client, see `Recurrence`. Completing a recurring todo
+-- creates its next occurrence, which carries the recurrence on.
+ALTER TABLE todos ADD COLUMN recurrence JSONB;
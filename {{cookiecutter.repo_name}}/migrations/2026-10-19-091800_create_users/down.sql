@@ -0,0 +1,2 @@
+ALTER TABLE todos DROP COLUMN owner_id;
+DROP TABLE users;
@@ -0,0 +1,16 @@
+-- Emails are stored lower-cased, so the unique index is case insensitive in practice
+CREATE TABLE users (
+  id SERIAL PRIMARY KEY,
+  email VARCHAR NOT NULL UNIQUE,
+  name VARCHAR,
+  password_hash VARCHAR NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+SELECT diesel_manage_updated_at('users');
+
+-- Todos created before accounts existed, or anonymously, have no owner
+ALTER TABLE todos ADD COLUMN owner_id INTEGER REFERENCES users (id) ON DELETE SET NULL;
+
+CREATE INDEX todos_owner_id_idx ON todos (owner_id);
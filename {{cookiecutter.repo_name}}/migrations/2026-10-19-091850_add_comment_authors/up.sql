@@ -0,0 +1,2 @@
+-- Only the author of a comment may change it, `author` keeps the name shown for it
+ALTER TABLE comments ADD COLUMN author_id INTEGER REFERENCES users (id) ON DELETE SET NULL;
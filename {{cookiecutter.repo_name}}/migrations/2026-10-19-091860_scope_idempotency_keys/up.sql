@@ -0,0 +1,6 @@
+-- Keys are only unique per user, so one user can neither replay nor block another user's requests.
+-- Anonymous requests share the empty principal.
+DELETE FROM idempotency_keys;
+ALTER TABLE idempotency_keys ADD COLUMN principal VARCHAR NOT NULL;
+ALTER TABLE idempotency_keys DROP CONSTRAINT idempotency_keys_pkey;
+ALTER TABLE idempotency_keys ADD PRIMARY KEY (principal, key);
@@ -0,0 +1,2 @@
+-- Roles are stored by name, see `Role`
+ALTER TABLE users ADD COLUMN role VARCHAR NOT NULL DEFAULT 'member';
@@ -0,0 +1,15 @@
+-- Only a hash of each key is stored, the prefix identifies the key it was issued as
+CREATE TABLE api_keys (
+  id SERIAL PRIMARY KEY,
+  user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+  name VARCHAR NOT NULL,
+  prefix VARCHAR NOT NULL UNIQUE,
+  key_hash VARCHAR NOT NULL,
+  scopes TEXT[] NOT NULL,
+  expires_at TIMESTAMPTZ,
+  last_used_at TIMESTAMPTZ,
+  revoked_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX api_keys_user_id_idx ON api_keys (user_id);
@@ -0,0 +1,3 @@
+ALTER TABLE users DROP COLUMN external_id;
+DELETE FROM users WHERE password_hash IS NULL;
+ALTER TABLE users ALTER COLUMN password_hash SET NOT NULL;
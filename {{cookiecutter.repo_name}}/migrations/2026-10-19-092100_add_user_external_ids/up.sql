@@ -0,0 +1,4 @@
+-- Users signing in through an external identity provider have no password
+ALTER TABLE users ALTER COLUMN password_hash DROP NOT NULL;
+-- The `sub` claim of the provider's tokens
+ALTER TABLE users ADD COLUMN external_id VARCHAR UNIQUE;
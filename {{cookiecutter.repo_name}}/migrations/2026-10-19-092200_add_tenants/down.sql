@@ -0,0 +1,74 @@
+DROP TABLE tenants;
+
+DO $$
+DECLARE
+    tenant_table TEXT;
+BEGIN
+    FOREACH tenant_table IN ARRAY ARRAY[
+        'todos', 'todo_lists', 'tags', 'users', 'api_keys', 'idempotency_keys',
+        'todo_items', 'comments', 'attachments', 'audit_events'
+    ] LOOP
+        EXECUTE format('ALTER TABLE %I DISABLE ROW LEVEL SECURITY', tenant_table);
+        EXECUTE format('DROP POLICY tenant_isolation ON %I', tenant_table);
+    END LOOP;
+END;
+$$;
+
+DROP TRIGGER inherit_tenant ON audit_events;
+DROP TRIGGER inherit_tenant ON attachments;
+DROP TRIGGER inherit_tenant ON comments;
+DROP TRIGGER inherit_tenant ON todo_items;
+DROP FUNCTION inherit_todo_tenant();
+
+-- Only the data of the default tenant fits the global unique constraints again
+DELETE FROM idempotency_keys WHERE tenant_id <> 'default';
+DELETE FROM todos WHERE tenant_id <> 'default';
+DELETE FROM todo_lists WHERE tenant_id <> 'default';
+DELETE FROM tags WHERE tenant_id <> 'default';
+DELETE FROM users WHERE tenant_id <> 'default';
+DELETE FROM audit_events WHERE tenant_id <> 'default';
+
+ALTER TABLE idempotency_keys DROP CONSTRAINT idempotency_keys_pkey;
+ALTER TABLE idempotency_keys ADD PRIMARY KEY (principal, key);
+
+CREATE OR REPLACE FUNCTION todos_assign_position() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'INSERT' AND NEW.position IS NOT NULL THEN
+        RETURN NEW;
+    END IF;
+    IF TG_OP = 'UPDATE' AND (NEW.list_id IS NOT DISTINCT FROM OLD.list_id OR NEW.position IS DISTINCT FROM OLD.position) THEN
+        RETURN NEW;
+    END IF;
+    PERFORM todos_lock_positions(NEW.list_id);
+    SELECT COALESCE(MAX(position), 0) + 1024 INTO NEW.position FROM todos
+        WHERE list_id IS NOT DISTINCT FROM NEW.list_id;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP FUNCTION todos_lock_positions(VARCHAR, INTEGER);
+CREATE FUNCTION todos_lock_positions(_list_id INTEGER) RETURNS VOID AS $$
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('todos_position'), hashtext(COALESCE(_list_id::TEXT, '')));
+END;
+$$ LANGUAGE plpgsql;
+
+DROP INDEX todos_list_position_idx;
+CREATE INDEX todos_list_position_idx ON todos (list_id, position);
+ALTER TABLE users DROP CONSTRAINT users_tenant_id_external_id_key;
+ALTER TABLE users ADD CONSTRAINT users_external_id_key UNIQUE (external_id);
+ALTER TABLE users DROP CONSTRAINT users_tenant_id_email_key;
+ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);
+ALTER TABLE tags DROP CONSTRAINT tags_tenant_id_name_key;
+ALTER TABLE tags ADD CONSTRAINT tags_name_key UNIQUE (name);
+
+ALTER TABLE audit_events DROP COLUMN tenant_id;
+ALTER TABLE attachments DROP COLUMN tenant_id;
+ALTER TABLE comments DROP COLUMN tenant_id;
+ALTER TABLE todo_items DROP COLUMN tenant_id;
+ALTER TABLE idempotency_keys DROP COLUMN tenant_id;
+ALTER TABLE api_keys DROP COLUMN tenant_id;
+ALTER TABLE users DROP COLUMN tenant_id;
+ALTER TABLE tags DROP COLUMN tenant_id;
+ALTER TABLE todo_lists DROP COLUMN tenant_id;
+ALTER TABLE todos DROP COLUMN tenant_id;
@@ -0,0 +1,117 @@
+-- Every team hosted on the deployment is a tenant. Existing data belongs to the `default` one.
+ALTER TABLE todos ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT 'default';
+ALTER TABLE todo_lists ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT 'default';
+ALTER TABLE tags ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT 'default';
+ALTER TABLE users ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT 'default';
+ALTER TABLE api_keys ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT 'default';
+ALTER TABLE idempotency_keys ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT 'default';
+ALTER TABLE todo_items ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT 'default';
+ALTER TABLE comments ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT 'default';
+ALTER TABLE attachments ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT 'default';
+ALTER TABLE audit_events ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT 'default';
+
+UPDATE todo_items SET tenant_id = todos.tenant_id FROM todos WHERE todos.id = todo_items.todo_id;
+UPDATE comments SET tenant_id = todos.tenant_id FROM todos WHERE todos.id = comments.todo_id;
+UPDATE attachments SET tenant_id = todos.tenant_id FROM todos WHERE todos.id = attachments.todo_id;
+UPDATE audit_events SET tenant_id = todos.tenant_id FROM todos WHERE todos.id = audit_events.todo_id;
+
+-- New rows always name their tenant, a forgotten one fails instead of landing in `default`
+ALTER TABLE todos ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE todo_lists ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE tags ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE users ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE api_keys ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE idempotency_keys ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE todo_items ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE comments ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE attachments ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE audit_events ALTER COLUMN tenant_id DROP DEFAULT;
+
+CREATE INDEX todos_tenant_id_idx ON todos (tenant_id);
+CREATE INDEX todo_lists_tenant_id_idx ON todo_lists (tenant_id);
+CREATE INDEX audit_events_tenant_id_idx ON audit_events (tenant_id, id);
+
+-- Names, emails and idempotency keys only need to be unique within a tenant
+ALTER TABLE tags DROP CONSTRAINT tags_name_key;
+ALTER TABLE tags ADD CONSTRAINT tags_tenant_id_name_key UNIQUE (tenant_id, name);
+ALTER TABLE users DROP CONSTRAINT users_email_key;
+ALTER TABLE users ADD CONSTRAINT users_tenant_id_email_key UNIQUE (tenant_id, email);
+ALTER TABLE users DROP CONSTRAINT users_external_id_key;
+ALTER TABLE users ADD CONSTRAINT users_tenant_id_external_id_key UNIQUE (tenant_id, external_id);
+ALTER TABLE idempotency_keys DROP CONSTRAINT idempotency_keys_pkey;
+ALTER TABLE idempotency_keys ADD PRIMARY KEY (tenant_id, principal, key);
+
+-- Positions order the todos of a list within its tenant
+DROP INDEX todos_list_position_idx;
+CREATE INDEX todos_list_position_idx ON todos (tenant_id, list_id, position);
+
+DROP FUNCTION todos_lock_positions(INTEGER);
+CREATE FUNCTION todos_lock_positions(_tenant_id VARCHAR, _list_id INTEGER) RETURNS VOID AS $$
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('todos_position'), hashtext(_tenant_id || ':' || COALESCE(_list_id::TEXT, '')));
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION todos_assign_position() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'INSERT' AND NEW.position IS NOT NULL THEN
+        RETURN NEW;
+    END IF;
+    IF TG_OP = 'UPDATE' AND (NEW.list_id IS NOT DISTINCT FROM OLD.list_id OR NEW.position IS DISTINCT FROM OLD.position) THEN
+        RETURN NEW;
+    END IF;
+    PERFORM todos_lock_positions(NEW.tenant_id, NEW.list_id);
+    SELECT COALESCE(MAX(position), 0) + 1024 INTO NEW.position FROM todos
+        WHERE tenant_id = NEW.tenant_id AND list_id IS NOT DISTINCT FROM NEW.list_id;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Items, comments, attachments and audit events belong to the tenant of their todo
+CREATE OR REPLACE FUNCTION inherit_todo_tenant() RETURNS trigger AS $$
+BEGIN
+    SELECT tenant_id INTO NEW.tenant_id FROM todos WHERE id = NEW.todo_id;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER inherit_tenant BEFORE INSERT ON todo_items
+    FOR EACH ROW EXECUTE PROCEDURE inherit_todo_tenant();
+CREATE TRIGGER inherit_tenant BEFORE INSERT ON comments
+    FOR EACH ROW EXECUTE PROCEDURE inherit_todo_tenant();
+CREATE TRIGGER inherit_tenant BEFORE INSERT ON attachments
+    FOR EACH ROW EXECUTE PROCEDURE inherit_todo_tenant();
+CREATE TRIGGER inherit_tenant BEFORE INSERT ON audit_events
+    FOR EACH ROW EXECUTE PROCEDURE inherit_todo_tenant();
+
+-- Row-level security as a second line of defense. The policies check the tenant connections are
+-- tagged with (`app.tenant_id`, set when `TENANT_ROW_LEVEL_SECURITY` is enabled), an empty one
+-- being background work across all tenants. Untagged connections see nothing. Table owners
+-- bypass the policies, so they only apply when the app connects as a role of its own.
+DO $$
+DECLARE
+    tenant_table TEXT;
+BEGIN
+    FOREACH tenant_table IN ARRAY ARRAY[
+        'todos', 'todo_lists', 'tags', 'users', 'api_keys', 'idempotency_keys',
+        'todo_items', 'comments', 'attachments', 'audit_events'
+    ] LOOP
+        EXECUTE format(
+            'CREATE POLICY tenant_isolation ON %I USING ('
+            'tenant_id = current_setting(''app.tenant_id'', true) OR current_setting(''app.tenant_id'', true) = '''')',
+            tenant_table
+        );
+        EXECUTE format('ALTER TABLE %I ENABLE ROW LEVEL SECURITY', tenant_table);
+    END LOOP;
+END;
+$$;
+
+-- Requests can only be made to registered tenants. Anonymous requests naming a tenant are
+-- turned away unless it allows them, the default tenant leaves that to `ANONYMOUS_ROLE`.
+CREATE TABLE tenants (
+  id VARCHAR PRIMARY KEY,
+  allow_anonymous BOOLEAN NOT NULL DEFAULT FALSE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO tenants (id, allow_anonymous) VALUES ('default', TRUE);
@@ -0,0 +1,3 @@
+DROP TABLE todo_list_shares;
+ALTER TABLE users DROP COLUMN groups;
+ALTER TABLE todo_lists DROP COLUMN owner_id;
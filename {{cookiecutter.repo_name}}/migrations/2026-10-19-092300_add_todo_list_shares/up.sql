@@ -0,0 +1,30 @@
+-- Lists created by signed in users are private to them and those they share them with. Lists
+-- created before accounts existed, or anonymously, stay visible to everyone.
+ALTER TABLE todo_lists ADD COLUMN owner_id INTEGER REFERENCES users (id) ON DELETE SET NULL;
+
+CREATE INDEX todo_lists_owner_id_idx ON todo_lists (owner_id);
+
+-- Groups are plain names admins put users in, lists can be shared with all of their members
+ALTER TABLE users ADD COLUMN groups TEXT[] NOT NULL DEFAULT '{}';
+
+-- A share grants either one user or a group access to a list. Permissions are stored by name,
+-- see `SharePermission`.
+CREATE TABLE todo_list_shares (
+  id SERIAL PRIMARY KEY,
+  list_id INTEGER NOT NULL REFERENCES todo_lists (id) ON DELETE CASCADE,
+  user_id INTEGER REFERENCES users (id) ON DELETE CASCADE,
+  group_name VARCHAR,
+  permission VARCHAR NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  tenant_id VARCHAR NOT NULL,
+  CHECK ((user_id IS NULL) <> (group_name IS NULL))
+);
+
+CREATE UNIQUE INDEX todo_list_shares_user_idx ON todo_list_shares (list_id, user_id) WHERE user_id IS NOT NULL;
+CREATE UNIQUE INDEX todo_list_shares_group_idx ON todo_list_shares (list_id, group_name) WHERE group_name IS NOT NULL;
+CREATE INDEX todo_list_shares_user_id_idx ON todo_list_shares (user_id);
+
+CREATE POLICY tenant_isolation ON todo_list_shares USING (
+  tenant_id = current_setting('app.tenant_id', true) OR current_setting('app.tenant_id', true) = ''
+);
+ALTER TABLE todo_list_shares ENABLE ROW LEVEL SECURITY;
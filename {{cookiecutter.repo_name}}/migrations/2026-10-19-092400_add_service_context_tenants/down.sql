@@ -0,0 +1,2 @@
+DELETE FROM service_contexts WHERE tenant_id <> '';
+ALTER TABLE service_contexts DROP COLUMN tenant_id;
@@ -0,0 +1,5 @@
+-- Service contexts are kept per tenant, the one with an empty tenant id applying to all of
+-- them. The context there was so far becomes the global one.
+ALTER TABLE service_contexts ADD COLUMN tenant_id VARCHAR NOT NULL DEFAULT '';
+
+ALTER TABLE service_contexts ADD CONSTRAINT service_contexts_tenant_id_key UNIQUE (tenant_id);
@@ -0,0 +1,253 @@
+use std::future::{ready, Ready};
+use std::rc::Rc;
+
+use actix_web::{body::EitherBody, dev::{self, Payload, Service, ServiceRequest, ServiceResponse, Transform}, Error, FromRequest, HttpMessage, HttpRequest, HttpResponse, ResponseError, web};
+use actix_web::http::{header, Method};
+use futures_util::future::LocalBoxFuture;
+use crate::domain::constants::API_KEY_PREFIX;
+use crate::api::middleware::{in_maintenance, RequestedTenant};
+use crate::domain::error::{ApiError, CommonError, FORBIDDEN_ERROR_CODE, UNAUTHORIZED_ERROR_CODE};
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::authorization::{Permission, Principal};
+use crate::domain::models::tenant::Tenant;
+use crate::domain::models::user::User;
+use crate::domain::services::api_key::ApiKeyService;
+use crate::domain::services::auth::AuthService;
+
+// Carries an API key, as an alternative to passing it as a bearer token
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+// Reachable without credentials in tenants that turn away anonymous requests
+const ANONYMOUS_TENANT_PATHS: [&str; 2] = ["/auth/login", "/auth/refresh"];
+
+// The signed in user, put in the request extensions by `Authentication`. Extracting it fails
+// with 401 for anonymous requests, `Option<AuthenticatedUser>` accepts those as well.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser(pub User);
+
+impl FromRequest for AuthenticatedUser {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(request.extensions().get::<AuthenticatedUser>().cloned().ok_or_else(|| CommonError {
+            message: "Authentication required".to_string(),
+            code: UNAUTHORIZED_ERROR_CODE,
+        }.into()))
+    }
+}
+
+// Resolves the user of requests carrying an `Authorization: Bearer` access token or API key, or
+// an `X-Api-Key` header, and records them as the audit actor. Requests without credentials pass
+// through anonymously, unless they name a tenant not allowing that, while requests with unusable
+// credentials are rejected with 401. Either way the request runs with its `Principal`, both in
+// the request extensions and as the current principal of services. Signed in users stay in their
+// own tenant.
+// Needs to run within `TenantResolution`.
+pub struct Authentication;
+
+impl<S, B> Transform<S, ServiceRequest> for Authentication
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = AuthenticationMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuthenticationMiddleware { service: Rc::new(service) }))
+    }
+}
+pub struct AuthenticationMiddleware<S> {
+    service: Rc<S>,
+}
+
+enum Credentials {
+    AccessToken(String),
+    ApiKey(String),
+}
+
+fn credentials(request: &ServiceRequest) -> Option<Result<Credentials, CommonError>> {
+    if let Some(value) = request.headers().get(API_KEY_HEADER) {
+        let key = value.to_str().ok()
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty());
+        return Some(key.map(Credentials::ApiKey).ok_or_else(|| CommonError {
+            message: "Expected an API key".to_string(),
+            code: UNAUTHORIZED_ERROR_CODE,
+        }));
+    }
+    let value = request.headers().get(header::AUTHORIZATION)?;
+    let token = value.to_str().ok()
+        .and_then(|value| value.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim().to_string())
+        .filter(|token| !token.is_empty());
+    let credentials = token.map(|token| if token.starts_with(API_KEY_PREFIX) {
+        Credentials::ApiKey(token)
+    } else {
+        Credentials::AccessToken(token)
+    });
+    Some(credentials.ok_or_else(|| CommonError {
+        message: "Expected a bearer token".to_string(),
+        code: UNAUTHORIZED_ERROR_CODE,
+    }))
+}
+
+impl<S, B> Service<ServiceRequest> for AuthenticationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let (auth_service, api_key_service) = match (
+            request.app_data::<web::Data<dyn AuthService>>().cloned(),
+            request.app_data::<web::Data<dyn ApiKeyService>>().cloned(),
+        ) {
+            (Some(auth_service), Some(api_key_service)) => (auth_service, api_key_service),
+            _ => {
+                let res = self.service.call(request);
+                return Box::pin(async move { res.await.map(ServiceResponse::map_into_left_body) });
+            }
+        };
+        let credentials = credentials(&request);
+
+        let service = self.service.clone();
+        Box::pin(async move {
+            let user = match credentials {
+                Some(Ok(Credentials::AccessToken(token))) => auth_service.authenticate(&token).await
+                    .map(|user| Some((Principal::user(user.id, user.role).in_groups(user.groups.clone()), user))),
+                // Keys act as their user, but only within their scopes
+                Some(Ok(Credentials::ApiKey(key))) => api_key_service.authenticate(&key).await
+                    .map(|(user, api_key)| {
+                        let principal = Principal::user(user.id, user.role).in_groups(user.groups.clone()).scoped(api_key.scopes);
+                        Some((principal, user))
+                    }),
+                Some(Err(e)) => Err(e),
+                None => Ok(None),
+            };
+            let requested = request.extensions().get::<RequestedTenant>().map(|requested| requested.0.clone());
+            let (principal, context, tenant) = match user {
+                Ok(Some((_, user))) if requested.as_ref().is_some_and(|tenant| tenant.id != user.tenant_id) => {
+                    let response = ApiError::from(CommonError {
+                        message: "The credentials belong to another tenant".to_string(),
+                        code: FORBIDDEN_ERROR_CODE,
+                    }).error_response().map_into_right_body();
+                    return Ok(request.into_response(response));
+                }
+                Ok(Some((principal, user))) => {
+                    let context = AuditContext { actor: Some(user.email.clone()), ..AuditContext::current() };
+                    let tenant = Tenant::new(user.tenant_id.clone());
+                    // The maintenance check only knew of the tenant the request was made to
+                    if Tenant::current().as_ref() != Some(&tenant) {
+                        let response = match in_maintenance(&request, &tenant) {
+                            Ok(false) => None,
+                            Ok(true) => Some(HttpResponse::ServiceUnavailable().finish()),
+                            Err(e) => Some(ApiError::from(e).error_response()),
+                        };
+                        if let Some(response) = response {
+                            return Ok(request.into_response(response.map_into_right_body()));
+                        }
+                    }
+                    request.extensions_mut().insert(AuthenticatedUser(user));
+                    (principal, context, tenant)
+                }
+                // Signing in needs no credentials, everything else only where the tenant allows it
+                Ok(None) if requested.as_ref().is_some_and(|tenant| !tenant.allow_anonymous)
+                    && !ANONYMOUS_TENANT_PATHS.contains(&request.path()) => {
+                    let response = ApiError::from(CommonError {
+                        message: "Authentication required".to_string(),
+                        code: UNAUTHORIZED_ERROR_CODE,
+                    }).error_response().map_into_right_body();
+                    return Ok(request.into_response(response));
+                }
+                Ok(None) => (auth_service.anonymous(), AuditContext::current(), Tenant::current().unwrap_or_default()),
+                Err(e) => {
+                    let response = ApiError::from(e).error_response().map_into_right_body();
+                    return Ok(request.into_response(response));
+                }
+            };
+            request.extensions_mut().insert(principal.clone());
+            let res = tenant.scope(context.scope(principal.scope(async move { service.call(request).await }))).await;
+            res.map(ServiceResponse::map_into_left_body)
+        })
+    }
+}
+
+// Guards routes by permission, reads (GET and HEAD requests) may require a different one than
+// writes. Needs to run within `Authentication`.
+pub struct Authorize {
+    read: Permission,
+    write: Permission,
+}
+
+impl Authorize {
+    pub fn new(permission: Permission) -> Self {
+        Authorize { read: permission, write: permission }
+    }
+
+    pub fn read_write(read: Permission, write: Permission) -> Self {
+        Authorize { read, write }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Authorize
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = AuthorizeMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuthorizeMiddleware { service, read: self.read, write: self.write }))
+    }
+}
+pub struct AuthorizeMiddleware<S> {
+    service: S,
+    read: Permission,
+    write: Permission,
+}
+
+impl<S, B> Service<ServiceRequest> for AuthorizeMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let permission = match *request.method() {
+            Method::GET | Method::HEAD => self.read,
+            _ => self.write,
+        };
+        let principal = request.extensions().get::<Principal>().cloned()
+            .unwrap_or_else(|| Principal::anonymous(None));
+        if let Err(e) = principal.authorize(permission) {
+            let response = ApiError::from(e).error_response().map_into_right_body();
+            return Box::pin(async { Ok(request.into_response(response)) });
+        }
+
+        let res = self.service.call(request);
+        Box::pin(async move { res.await.map(ServiceResponse::map_into_left_body) })
+    }
+}
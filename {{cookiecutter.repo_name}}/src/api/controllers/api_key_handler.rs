@@ -0,0 +1,26 @@
+use actix_web::{web, Result};
+use crate::api::auth::AuthenticatedUser;
+use crate::api::dto::api_key::{ApiKeyDTO, CreateApiKeyDTO, IssuedApiKeyDTO};
+use crate::domain::error::ApiError;
+use crate::domain::services::api_key::ApiKeyService;
+
+pub async fn create_api_key_handler(
+    api_key_service: web::Data<dyn ApiKeyService>, user: AuthenticatedUser, post_data: web::Json<CreateApiKeyDTO>,
+) -> Result<web::Json<IssuedApiKeyDTO>, ApiError> {
+    let issued = api_key_service.create(&user.0, post_data.into_inner().into()).await?;
+    Ok(web::Json(issued.into()))
+}
+
+pub async fn list_api_keys_handler(
+    api_key_service: web::Data<dyn ApiKeyService>, user: AuthenticatedUser,
+) -> Result<web::Json<Vec<ApiKeyDTO>>, ApiError> {
+    let api_keys = api_key_service.list(&user.0).await?;
+    Ok(web::Json(api_keys.into_iter().map(ApiKeyDTO::from).collect()))
+}
+
+pub async fn revoke_api_key_handler(
+    api_key_service: web::Data<dyn ApiKeyService>, user: AuthenticatedUser, params: web::Path<i32>,
+) -> Result<web::Json<ApiKeyDTO>, ApiError> {
+    let api_key = api_key_service.revoke(&user.0, params.into_inner()).await?;
+    Ok(web::Json(api_key.into()))
+}
@@ -0,0 +1,121 @@
+use actix_multipart::Multipart;
+use actix_web::{web, Result, HttpResponse};
+use actix_web::http::header::{self, ContentDisposition, DispositionParam, DispositionType, EntityTag, ETag};
+use bytes::Bytes;
+use futures_util::future::join;
+use futures_util::{stream, StreamExt};
+use tokio::sync::mpsc;
+use crate::api::dto::attachment::AttachmentDTO;
+use crate::domain::error::{ApiError, CommonError, VALIDATION_ERROR_CODE};
+use crate::domain::models::attachment::UploadAttachment;
+use crate::domain::services::attachment::AttachmentService;
+
+// Chunks of an upload buffered between the multipart reader and the attachment service
+const UPLOAD_BUFFER_CHUNKS: usize = 16;
+
+fn invalid_upload(message: String) -> CommonError {
+    CommonError {
+        message,
+        code: VALIDATION_ERROR_CODE,
+    }
+}
+
+// Expects the file in a multipart field named `file`. The field is streamed to the service
+// through a channel, so oversized uploads are rejected without reading them completely.
+pub async fn upload_attachment_handler(
+    attachment_service: web::Data<dyn AttachmentService>, params: web::Path<i32>, mut payload: Multipart,
+) -> Result<web::Json<AttachmentDTO>, ApiError> {
+    let todo_id = params.into_inner();
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| invalid_upload(format!("Invalid upload: {}", e)))?;
+        if field.name() != Some("file") {
+            continue;
+        }
+        let file_name = field.content_disposition()
+            .and_then(|disposition| disposition.get_filename())
+            .unwrap_or_default()
+            .to_string();
+        let content_type = field.content_type()
+            .map(|mime| mime.to_string())
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+        let (sender, receiver) = mpsc::channel::<Result<Bytes, CommonError>>(UPLOAD_BUFFER_CHUNKS);
+        let content = stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        }).boxed();
+        let forward = async move {
+            while let Some(chunk) = field.next().await {
+                let chunk = chunk.map_err(|e| invalid_upload(format!("Invalid upload: {}", e)));
+                let failed = chunk.is_err();
+                // The service stops reading once it rejects the upload
+                if sender.send(chunk).await.is_err() || failed {
+                    break;
+                }
+            }
+        };
+        let upload = UploadAttachment { file_name, content_type, content };
+        let (_, attachment) = join(forward, attachment_service.upload(todo_id, upload)).await;
+        return Ok(web::Json(attachment?.into()));
+    }
+    Err(invalid_upload("Expected a multipart field named file".to_string()).into())
+}
+
+pub async fn list_attachments_handler(
+    attachment_service: web::Data<dyn AttachmentService>, params: web::Path<i32>,
+) -> Result<web::Json<Vec<AttachmentDTO>>, ApiError> {
+    let attachments = attachment_service.list(params.into_inner()).await?;
+    Ok(web::Json(attachments.into_iter().map(AttachmentDTO::from).collect()))
+}
+
+pub async fn get_attachment_handler(
+    attachment_service: web::Data<dyn AttachmentService>, params: web::Path<(i32, i32)>,
+) -> Result<web::Json<AttachmentDTO>, ApiError> {
+    let (todo_id, attachment_id) = params.into_inner();
+    let attachment = attachment_service.get(todo_id, attachment_id).await?;
+    Ok(web::Json(attachment.into()))
+}
+
+// Supports a single byte range, requests for several ranges get the whole file
+pub async fn download_attachment_handler(
+    attachment_service: web::Data<dyn AttachmentService>, params: web::Path<(i32, i32)>,
+    range: Option<web::Header<header::Range>>,
+) -> Result<HttpResponse, ApiError> {
+    let (todo_id, attachment_id) = params.into_inner();
+    let attachment = attachment_service.get(todo_id, attachment_id).await?;
+    let size = attachment.size as u64;
+    let range = match range.map(|header| header.into_inner()) {
+        Some(header::Range::Bytes(specs)) if specs.len() == 1 => match specs[0].to_satisfiable_range(size) {
+            Some(range) => Some(range),
+            None => return Ok(HttpResponse::RangeNotSatisfiable()
+                .insert_header((header::CONTENT_RANGE, format!("bytes */{}", size)))
+                .finish()),
+        },
+        _ => None,
+    };
+    let content = attachment_service.download(&attachment, range).await?;
+    let mut response = match range {
+        Some((start, end)) => {
+            let mut response = HttpResponse::PartialContent();
+            response.insert_header((header::CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, size)));
+            response
+        }
+        None => HttpResponse::Ok(),
+    };
+    Ok(response
+        .insert_header((header::ACCEPT_RANGES, "bytes"))
+        .insert_header((header::CONTENT_TYPE, attachment.content_type.clone()))
+        .insert_header(ETag(EntityTag::new_strong(attachment.checksum.clone())))
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(attachment.file_name.clone())],
+        })
+        .no_chunking(range.map_or(size, |(start, end)| end - start + 1))
+        .streaming(content))
+}
+
+pub async fn delete_attachment_handler(
+    attachment_service: web::Data<dyn AttachmentService>, params: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (todo_id, attachment_id) = params.into_inner();
+    attachment_service.delete(todo_id, attachment_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
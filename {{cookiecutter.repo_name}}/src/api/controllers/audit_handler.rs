@@ -0,0 +1,20 @@
+use actix_web::{web, Result};
+use crate::api::dto::audit::AuditEventDTO;
+use crate::domain::error::ApiError;
+use crate::domain::repositories::audit::AuditQueryParams;
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::services::audit::AuditService;
+
+pub async fn todo_history_handler(
+    audit_service: web::Data<dyn AuditService>, params: web::Path<i32>, query: web::Query<QueryParamsImpl>,
+) -> Result<web::Json<ResultPaging<AuditEventDTO>>, ApiError> {
+    let selection = audit_service.history(params.into_inner(), query.into_inner()).await?;
+    Ok(web::Json(selection.into()))
+}
+
+pub async fn list_audit_events_handler(
+    audit_service: web::Data<dyn AuditService>, query: web::Query<AuditQueryParams>,
+) -> Result<web::Json<ResultPaging<AuditEventDTO>>, ApiError> {
+    let selection = audit_service.list(query.into_inner()).await?;
+    Ok(web::Json(selection.into()))
+}
@@ -0,0 +1,52 @@
+use actix_web::{web, Result};
+use crate::api::auth::AuthenticatedUser;
+use crate::api::dto::auth::{AuthTokensDTO, LoginDTO, RefreshTokenDTO, RegisterDTO, SetGroupsDTO, SetRoleDTO, UserDTO};
+use crate::domain::error::ApiError;
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::services::auth::AuthService;
+
+pub async fn register_handler(
+    auth_service: web::Data<dyn AuthService>, post_data: web::Json<RegisterDTO>,
+) -> Result<web::Json<UserDTO>, ApiError> {
+    let user = auth_service.register(post_data.into_inner().into()).await?;
+    Ok(web::Json(user.into()))
+}
+
+pub async fn login_handler(
+    auth_service: web::Data<dyn AuthService>, post_data: web::Json<LoginDTO>,
+) -> Result<web::Json<AuthTokensDTO>, ApiError> {
+    let tokens = auth_service.login(post_data.into_inner().into()).await?;
+    Ok(web::Json(tokens.into()))
+}
+
+pub async fn refresh_token_handler(
+    auth_service: web::Data<dyn AuthService>, post_data: web::Json<RefreshTokenDTO>,
+) -> Result<web::Json<AuthTokensDTO>, ApiError> {
+    let tokens = auth_service.refresh(&post_data.refresh_token).await?;
+    Ok(web::Json(tokens.into()))
+}
+
+pub async fn current_user_handler(user: AuthenticatedUser) -> Result<web::Json<UserDTO>, ApiError> {
+    Ok(web::Json(user.0.into()))
+}
+
+pub async fn list_users_handler(
+    auth_service: web::Data<dyn AuthService>, query: web::Query<QueryParamsImpl>,
+) -> Result<web::Json<ResultPaging<UserDTO>>, ApiError> {
+    let selection = auth_service.list_users(query.into_inner()).await?;
+    Ok(web::Json(selection.into()))
+}
+
+pub async fn set_user_role_handler(
+    auth_service: web::Data<dyn AuthService>, params: web::Path<i32>, post_data: web::Json<SetRoleDTO>,
+) -> Result<web::Json<UserDTO>, ApiError> {
+    let user = auth_service.set_role(params.into_inner(), post_data.role).await?;
+    Ok(web::Json(user.into()))
+}
+
+pub async fn set_user_groups_handler(
+    auth_service: web::Data<dyn AuthService>, params: web::Path<i32>, post_data: web::Json<SetGroupsDTO>,
+) -> Result<web::Json<UserDTO>, ApiError> {
+    let user = auth_service.set_groups(params.into_inner(), post_data.into_inner().groups).await?;
+    Ok(web::Json(user.into()))
+}
@@ -0,0 +1,53 @@
+use actix_web::{web, Result, HttpResponse};
+use crate::api::auth::AuthenticatedUser;
+use crate::api::dto::comment::{CommentDTO, CreateCommentDTO, UpdateCommentDTO};
+use crate::domain::error::ApiError;
+use crate::domain::models::comment::CreateComment;
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::services::comment::CommentService;
+
+// Comments are written by the signed in user, anonymous ones have no author
+pub async fn create_comment_handler(
+    comment_service: web::Data<dyn CommentService>, params: web::Path<i32>, post_data: web::Json<CreateCommentDTO>,
+    user: Option<AuthenticatedUser>,
+) -> Result<web::Json<CommentDTO>, ApiError> {
+    let comment: CreateComment = post_data.into_inner().into();
+    let comment = CreateComment {
+        author: user.as_ref().map(|user| user.0.email.clone()),
+        author_id: user.map(|user| user.0.id),
+        ..comment
+    };
+    let comment = comment_service.create(params.into_inner(), comment).await?;
+    Ok(web::Json(comment.into()))
+}
+
+pub async fn list_comments_handler(
+    comment_service: web::Data<dyn CommentService>, params: web::Path<i32>, query: web::Query<QueryParamsImpl>,
+) -> Result<web::Json<ResultPaging<CommentDTO>>, ApiError> {
+    let selection = comment_service.list(params.into_inner(), query.into_inner()).await?;
+    Ok(web::Json(selection.into()))
+}
+
+pub async fn get_comment_handler(
+    comment_service: web::Data<dyn CommentService>, params: web::Path<(i32, i32)>,
+) -> Result<web::Json<CommentDTO>, ApiError> {
+    let (todo_id, comment_id) = params.into_inner();
+    let comment = comment_service.get(todo_id, comment_id).await?;
+    Ok(web::Json(comment.into()))
+}
+
+pub async fn update_comment_handler(
+    comment_service: web::Data<dyn CommentService>, params: web::Path<(i32, i32)>, post_data: web::Json<UpdateCommentDTO>,
+) -> Result<web::Json<CommentDTO>, ApiError> {
+    let (todo_id, comment_id) = params.into_inner();
+    let comment = comment_service.update(todo_id, comment_id, post_data.into_inner().into()).await?;
+    Ok(web::Json(comment.into()))
+}
+
+pub async fn delete_comment_handler(
+    comment_service: web::Data<dyn CommentService>, params: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (todo_id, comment_id) = params.into_inner();
+    comment_service.delete(todo_id, comment_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1 +1,12 @@
 pub mod todo_handler;
+pub mod tag_handler;
+pub mod todo_list_handler;
+
+pub mod todo_item_handler;
+pub mod comment_handler;
+pub mod attachment_handler;
+pub mod audit_handler;
+pub mod auth_handler;
+pub mod api_key_handler;
+pub mod service_context_handlers;
+pub mod tenant_handler;
@@ -1,16 +1,50 @@
-//
-//
-//
-// pub async fn update_service_context_handler(
-//     service_context_service: web::Data<dyn ServiceContextService>, post_data: web::Json<ServiceContextDTO>,
-// ) -> Result<web::Json<ServiceContextDTO>, ApiError> {
-//     let service_context = service_context_service.update(post_data.into_inner().into()).await?;
-//     Ok(web::Json(service_context.into()))
-// }
-//
-// pub async fn get_service_context(
-//     service_context_service: web::Data<dyn ServiceContextService>,
-// ) -> Result<web::Json<ServiceContextDTO>, ApiError> {
-//     let service_context = service_context_service.get_service_context().await?;
-//     Ok(web::Json(service_context.into()))
-// }
\ No newline at end of file
+use actix_web::{web, Result};
+use crate::api::dto::service_context::{ServiceContextDTO, ServiceContextParams, UpdateServiceContextDTO};
+use crate::domain::error::{ApiError, CommonError, FORBIDDEN_ERROR_CODE};
+use crate::domain::models::service_context::ServiceContext;
+use crate::domain::models::tenant::Tenant;
+use crate::domain::services::service_context::ServiceContextService;
+use crate::domain::services::tenant::TenantService;
+
+// The context a request works with, `None` standing for the global one
+fn target_tenant(query: &ServiceContextParams) -> Option<Tenant> {
+    if query.global.unwrap_or(false) {
+        return None;
+    }
+    Some(Tenant::current().unwrap_or_default())
+}
+
+// Reports the context along with whether maintenance is in effect, the way requests are checked
+fn service_context_dto(
+    service_context_service: &dyn ServiceContextService, tenant: Option<&Tenant>, service_context: ServiceContext,
+) -> Result<ServiceContextDTO, CommonError> {
+    let maintenance_active = match tenant {
+        Some(tenant) => service_context_service.is_maintenance_active(tenant)?,
+        None => service_context.maintenance,
+    };
+    Ok(ServiceContextDTO::new(service_context, maintenance_active))
+}
+
+pub async fn update_service_context_handler(
+    service_context_service: web::Data<dyn ServiceContextService>, tenant_service: web::Data<dyn TenantService>,
+    query: web::Query<ServiceContextParams>, post_data: web::Json<UpdateServiceContextDTO>,
+) -> Result<web::Json<ServiceContextDTO>, ApiError> {
+    let tenant = target_tenant(&query);
+    // Maintenance of all tenants is up to the admins of the admin tenant
+    if tenant.is_none() && Tenant::current_id() != tenant_service.admin_tenant() {
+        return Err(CommonError {
+            message: "Only admins of the admin tenant can change the global service context".to_string(),
+            code: FORBIDDEN_ERROR_CODE,
+        }.into());
+    }
+    let service_context = service_context_service.update(tenant.as_ref(), post_data.maintenance);
+    Ok(web::Json(service_context_dto(service_context_service.as_ref(), tenant.as_ref(), service_context)?))
+}
+
+pub async fn get_service_context_handler(
+    service_context_service: web::Data<dyn ServiceContextService>, query: web::Query<ServiceContextParams>,
+) -> Result<web::Json<ServiceContextDTO>, ApiError> {
+    let tenant = target_tenant(&query);
+    let service_context = service_context_service.get_service_context(tenant.as_ref());
+    Ok(web::Json(service_context_dto(service_context_service.as_ref(), tenant.as_ref(), service_context)?))
+}
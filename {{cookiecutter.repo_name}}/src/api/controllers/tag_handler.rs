@@ -0,0 +1,62 @@
+use actix_web::{web, Result, HttpResponse};
+use crate::api::dto::tag::{AttachTagsDTO, CreateTagDTO, TagDTO, UpdateTagDTO};
+use crate::domain::error::ApiError;
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::services::tag::TagService;
+
+pub async fn create_tag_handler(
+    tag_service: web::Data<dyn TagService>, post_data: web::Json<CreateTagDTO>,
+) -> Result<web::Json<TagDTO>, ApiError> {
+    let tag = tag_service.create(post_data.into_inner().into()).await?;
+    Ok(web::Json(tag.into()))
+}
+
+pub async fn list_tags_handler(
+    tag_service: web::Data<dyn TagService>, params: web::Query<QueryParamsImpl>,
+) -> Result<web::Json<ResultPaging<TagDTO>>, ApiError> {
+    let selection = tag_service.list(params.into_inner()).await?;
+    Ok(web::Json(selection.into()))
+}
+
+pub async fn get_tag_handler(
+    tag_service: web::Data<dyn TagService>, params: web::Path<i32>,
+) -> Result<web::Json<TagDTO>, ApiError> {
+    let tag = tag_service.get(params.into_inner()).await?;
+    Ok(web::Json(tag.into()))
+}
+
+pub async fn update_tag_handler(
+    tag_service: web::Data<dyn TagService>, params: web::Path<i32>, post_data: web::Json<UpdateTagDTO>,
+) -> Result<web::Json<TagDTO>, ApiError> {
+    let tag = tag_service.update(params.into_inner(), post_data.into_inner().into()).await?;
+    Ok(web::Json(tag.into()))
+}
+
+pub async fn delete_tag_handler(
+    tag_service: web::Data<dyn TagService>, params: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    tag_service.delete(params.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn list_todo_tags_handler(
+    tag_service: web::Data<dyn TagService>, params: web::Path<i32>,
+) -> Result<web::Json<Vec<TagDTO>>, ApiError> {
+    let tags = tag_service.list_for_todo(params.into_inner()).await?;
+    Ok(web::Json(tags.into_iter().map(TagDTO::from).collect()))
+}
+
+pub async fn attach_todo_tags_handler(
+    tag_service: web::Data<dyn TagService>, params: web::Path<i32>, post_data: web::Json<AttachTagsDTO>,
+) -> Result<web::Json<Vec<TagDTO>>, ApiError> {
+    let tags = tag_service.attach(params.into_inner(), post_data.into_inner().tag_ids).await?;
+    Ok(web::Json(tags.into_iter().map(TagDTO::from).collect()))
+}
+
+pub async fn detach_todo_tag_handler(
+    tag_service: web::Data<dyn TagService>, params: web::Path<(i32, i32)>,
+) -> Result<web::Json<Vec<TagDTO>>, ApiError> {
+    let (todo_id, tag_id) = params.into_inner();
+    let tags = tag_service.detach(todo_id, tag_id).await?;
+    Ok(web::Json(tags.into_iter().map(TagDTO::from).collect()))
+}
@@ -0,0 +1,26 @@
+use actix_web::{web, Result};
+use crate::api::dto::tenant::{CreateTenantDTO, TenantDTO, UpdateTenantDTO};
+use crate::domain::error::ApiError;
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::services::tenant::TenantService;
+
+pub async fn create_tenant_handler(
+    tenant_service: web::Data<dyn TenantService>, post_data: web::Json<CreateTenantDTO>,
+) -> Result<web::Json<TenantDTO>, ApiError> {
+    let tenant = tenant_service.create(post_data.into_inner().into()).await?;
+    Ok(web::Json(tenant.into()))
+}
+
+pub async fn list_tenants_handler(
+    tenant_service: web::Data<dyn TenantService>, params: web::Query<QueryParamsImpl>,
+) -> Result<web::Json<ResultPaging<TenantDTO>>, ApiError> {
+    let selection = tenant_service.list(params.into_inner()).await?;
+    Ok(web::Json(selection.into()))
+}
+
+pub async fn update_tenant_handler(
+    tenant_service: web::Data<dyn TenantService>, params: web::Path<String>, post_data: web::Json<UpdateTenantDTO>,
+) -> Result<web::Json<TenantDTO>, ApiError> {
+    let tenant = tenant_service.update(&params.into_inner(), post_data.into_inner().into()).await?;
+    Ok(web::Json(tenant.into()))
+}
@@ -1,14 +1,45 @@
 use actix_web::{web, Result, HttpResponse};
-use crate::api::dto::todo::{CreateTodoDTO, TodoDTO};
-use crate::domain::error::{ApiError};
+use actix_web::http::header::{ETag, EntityTag, IfMatch, IfNoneMatch};
+use chrono::{DateTime, Utc};
+use crate::api::auth::AuthenticatedUser;
+use crate::api::dto::todo::{BulkTodoRequestDTO, BulkTodoResponseDTO, CreateTodoDTO, MoveTodoDTO, OccurrencesQueryDTO, TodoDTO, TodoSearchHitDTO, UpdateTodoDTO};
+use crate::domain::error::{ApiError, CommonError, PRECONDITION_FAILED_ERROR_CODE};
+use crate::domain::models::todo::{CreateTodo, Todo, TodoOperation};
 use crate::domain::repositories::repository::ResultPaging;
-use crate::domain::repositories::todo::TodoQueryParams;
+use crate::domain::repositories::todo::{TodoQueryParams, TodoSortField};
+use crate::domain::repositories::todo_search::TodoSearchParams;
 use crate::domain::services::todo::TodoService;
 
+fn entity_tag(todo: &Todo) -> EntityTag {
+    EntityTag::new_strong(todo.version.to_string())
+}
+
+// Only a single strong ETag (as returned by `get_todo_handler`) or `*` can be honoured,
+// any other `If-Match` value can never match and fails the precondition. A missing header
+// parses as an empty list of tags.
+fn expected_version(if_match: Option<web::Header<IfMatch>>) -> Result<Option<i32>, ApiError> {
+    match if_match.map(|header| header.into_inner()) {
+        None | Some(IfMatch::Any) => Ok(None),
+        Some(IfMatch::Items(tags)) => match tags.as_slice() {
+            [] => Ok(None),
+            [tag] if !tag.weak => tag.tag().parse::<i32>().map(Some).map_err(|_| precondition_failed()),
+            _ => Err(precondition_failed()),
+        },
+    }
+}
+
+fn precondition_failed() -> ApiError {
+    CommonError {
+        message: "If-Match does not match the current version".to_string(),
+        code: PRECONDITION_FAILED_ERROR_CODE,
+    }.into()
+}
+
 pub async fn create_todo_handler(
-    todo_service: web::Data<dyn TodoService>, post_data: web::Json<CreateTodoDTO>,
+    todo_service: web::Data<dyn TodoService>, post_data: web::Json<CreateTodoDTO>, user: Option<AuthenticatedUser>,
 ) -> Result<web::Json<TodoDTO>, ApiError> {
-    let todo = todo_service.create(post_data.into_inner().into()).await?;
+    let todo: CreateTodo = post_data.into_inner().into();
+    let todo = todo_service.create(CreateTodo { owner_id: user.map(|user| user.0.id), ..todo }).await?;
     Ok(web::Json(todo.into()))
 }
 
@@ -19,16 +50,108 @@ pub async fn list_todos_handler(
     Ok(web::Json(selection.into()))
 }
 
+pub async fn list_overdue_todos_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Query<TodoQueryParams>,
+) -> Result<web::Json<ResultPaging<TodoDTO>>, ApiError> {
+    let mut params = params.into_inner();
+    params.overdue = Some(true);
+    params.sort_by = params.sort_by.or(Some(TodoSortField::DueAt));
+    let selection = todo_service.list(params).await?;
+    Ok(web::Json(selection.into()))
+}
+
+pub async fn search_todos_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Query<TodoSearchParams>,
+) -> Result<web::Json<ResultPaging<TodoSearchHitDTO>>, ApiError> {
+    let selection = todo_service.search(params.into_inner()).await?;
+    Ok(web::Json(selection.into()))
+}
+
 pub async fn get_todo_handler(
     todo_service: web::Data<dyn TodoService>, params: web::Path<i32>,
-) -> Result<web::Json<TodoDTO>, ApiError> {
+    if_none_match: Option<web::Header<IfNoneMatch>>,
+) -> Result<HttpResponse, ApiError> {
     let todo = todo_service.get(params.into_inner()).await?;
-    Ok(web::Json(todo.into()))
+    let etag = entity_tag(&todo);
+    let not_modified = match if_none_match.map(|header| header.into_inner()) {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        None => false,
+    };
+    if not_modified {
+        return Ok(HttpResponse::NotModified().insert_header(ETag(etag)).finish());
+    }
+    Ok(HttpResponse::Ok().insert_header(ETag(etag)).json(TodoDTO::from(todo)))
+}
+
+pub async fn update_todo_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<i32>,
+    post_data: web::Json<UpdateTodoDTO>, if_match: Option<web::Header<IfMatch>>,
+) -> Result<HttpResponse, ApiError> {
+    let expected_version = expected_version(if_match)?;
+    let todo = todo_service.update(params.into_inner(), post_data.into_inner().into(), expected_version).await?;
+    Ok(HttpResponse::Ok().insert_header(ETag(entity_tag(&todo))).json(TodoDTO::from(todo)))
 }
 
 pub async fn delete_todo_handler(
     todo_service: web::Data<dyn TodoService>, params: web::Path<i32>,
+    if_match: Option<web::Header<IfMatch>>,
 ) -> Result<HttpResponse, ApiError> {
-    todo_service.delete(params.into_inner()).await?;
+    let expected_version = expected_version(if_match)?;
+    todo_service.delete(params.into_inner(), expected_version).await?;
     Ok(HttpResponse::NoContent().finish())
 }
+
+// Todos are completed by the signed in user, anonymous requests leave the author empty
+pub async fn complete_todo_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<i32>, user: Option<AuthenticatedUser>,
+) -> Result<web::Json<TodoDTO>, ApiError> {
+    let completed_by = user.map(|user| user.0.email);
+    let todo = todo_service.complete(params.into_inner(), completed_by).await?;
+    Ok(web::Json(todo.into()))
+}
+
+pub async fn move_todo_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<i32>, post_data: web::Json<MoveTodoDTO>,
+) -> Result<web::Json<TodoDTO>, ApiError> {
+    let todo = todo_service.move_todo(params.into_inner(), post_data.into_inner().into()).await?;
+    Ok(web::Json(todo.into()))
+}
+
+pub async fn list_occurrences_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<i32>, query: web::Query<OccurrencesQueryDTO>,
+) -> Result<web::Json<Vec<DateTime<Utc>>>, ApiError> {
+    let occurrences = todo_service.occurrences(params.into_inner(), query.into_inner().limit).await?;
+    Ok(web::Json(occurrences))
+}
+
+pub async fn reopen_todo_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<i32>,
+) -> Result<web::Json<TodoDTO>, ApiError> {
+    let todo = todo_service.reopen(params.into_inner()).await?;
+    Ok(web::Json(todo.into()))
+}
+
+pub async fn bulk_todos_handler(
+    todo_service: web::Data<dyn TodoService>, post_data: web::Json<BulkTodoRequestDTO>, user: Option<AuthenticatedUser>,
+) -> Result<web::Json<BulkTodoResponseDTO>, ApiError> {
+    let request = post_data.into_inner();
+    let owner_id = user.as_ref().map(|user| user.0.id);
+    let completed_by = user.map(|user| user.0.email);
+    let operations = request.operations.into_iter()
+        .map(|operation| match operation.into() {
+            TodoOperation::Create(todo) => TodoOperation::Create(CreateTodo { owner_id, ..todo }),
+            TodoOperation::Complete { todo_id, .. } => TodoOperation::Complete { todo_id, completed_by: completed_by.clone() },
+            operation => operation,
+        })
+        .collect();
+    let results = todo_service.bulk(operations, request.atomic.unwrap_or(true)).await?;
+    Ok(web::Json(results.into()))
+}
+
+pub async fn restore_todo_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<i32>,
+) -> Result<web::Json<TodoDTO>, ApiError> {
+    let todo = todo_service.restore(params.into_inner()).await?;
+    Ok(web::Json(todo.into()))
+}
@@ -0,0 +1,49 @@
+use actix_web::{web, Result, HttpResponse};
+use crate::api::dto::todo_item::{CreateTodoItemDTO, ReorderTodoItemsDTO, TodoItemDTO, UpdateTodoItemDTO};
+use crate::domain::error::ApiError;
+use crate::domain::services::todo::TodoService;
+
+pub async fn list_todo_items_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<i32>,
+) -> Result<web::Json<Vec<TodoItemDTO>>, ApiError> {
+    let items = todo_service.list_items(params.into_inner()).await?;
+    Ok(web::Json(items.into_iter().map(TodoItemDTO::from).collect()))
+}
+
+pub async fn create_todo_item_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<i32>, post_data: web::Json<CreateTodoItemDTO>,
+) -> Result<web::Json<TodoItemDTO>, ApiError> {
+    let item = todo_service.add_item(params.into_inner(), post_data.into_inner().into()).await?;
+    Ok(web::Json(item.into()))
+}
+
+pub async fn update_todo_item_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<(i32, i32)>, post_data: web::Json<UpdateTodoItemDTO>,
+) -> Result<web::Json<TodoItemDTO>, ApiError> {
+    let (todo_id, item_id) = params.into_inner();
+    let item = todo_service.update_item(todo_id, item_id, post_data.into_inner().into()).await?;
+    Ok(web::Json(item.into()))
+}
+
+pub async fn toggle_todo_item_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<(i32, i32)>,
+) -> Result<web::Json<TodoItemDTO>, ApiError> {
+    let (todo_id, item_id) = params.into_inner();
+    let item = todo_service.toggle_item(todo_id, item_id).await?;
+    Ok(web::Json(item.into()))
+}
+
+pub async fn delete_todo_item_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (todo_id, item_id) = params.into_inner();
+    todo_service.delete_item(todo_id, item_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn reorder_todo_items_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<i32>, post_data: web::Json<ReorderTodoItemsDTO>,
+) -> Result<web::Json<Vec<TodoItemDTO>>, ApiError> {
+    let items = todo_service.reorder_items(params.into_inner(), post_data.into_inner().item_ids).await?;
+    Ok(web::Json(items.into_iter().map(TodoItemDTO::from).collect()))
+}
@@ -0,0 +1,117 @@
+use actix_web::{web, Result, HttpResponse};
+use crate::api::auth::AuthenticatedUser;
+use crate::api::dto::todo::{CreateTodoDTO, TodoDTO};
+use crate::api::dto::todo_list::{AccessibleTodoListDTO, CreateTodoListDTO, CreateTodoListShareDTO, DeleteTodoListParams, TodoListDTO, TodoListShareDTO, UpdateTodoListDTO};
+use crate::domain::error::ApiError;
+use crate::domain::models::todo::CreateTodo;
+use crate::domain::models::todo_list::CreateTodoList;
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::repositories::todo::TodoQueryParams;
+use crate::domain::repositories::todo_list::TodoListQueryParams;
+use crate::domain::services::todo::TodoService;
+use crate::domain::services::todo_list::TodoListService;
+
+pub async fn create_todo_list_handler(
+    todo_list_service: web::Data<dyn TodoListService>, post_data: web::Json<CreateTodoListDTO>,
+    user: Option<AuthenticatedUser>,
+) -> Result<web::Json<TodoListDTO>, ApiError> {
+    let list: CreateTodoList = post_data.into_inner().into();
+    let list = todo_list_service.create(CreateTodoList { owner_id: user.map(|user| user.0.id), ..list }).await?;
+    Ok(web::Json(list.into()))
+}
+
+pub async fn list_todo_lists_handler(
+    todo_list_service: web::Data<dyn TodoListService>, params: web::Query<TodoListQueryParams>,
+) -> Result<web::Json<ResultPaging<TodoListDTO>>, ApiError> {
+    let selection = todo_list_service.list(params.into_inner()).await?;
+    Ok(web::Json(selection.into()))
+}
+
+pub async fn list_accessible_todo_lists_handler(
+    todo_list_service: web::Data<dyn TodoListService>, params: web::Query<QueryParamsImpl>,
+) -> Result<web::Json<ResultPaging<AccessibleTodoListDTO>>, ApiError> {
+    let selection = todo_list_service.list_accessible(params.into_inner()).await?;
+    Ok(web::Json(selection.into()))
+}
+
+pub async fn get_todo_list_handler(
+    todo_list_service: web::Data<dyn TodoListService>, params: web::Path<i32>,
+) -> Result<web::Json<TodoListDTO>, ApiError> {
+    let list = todo_list_service.get(params.into_inner()).await?;
+    Ok(web::Json(list.into()))
+}
+
+pub async fn update_todo_list_handler(
+    todo_list_service: web::Data<dyn TodoListService>, params: web::Path<i32>, post_data: web::Json<UpdateTodoListDTO>,
+) -> Result<web::Json<TodoListDTO>, ApiError> {
+    let list = todo_list_service.update(params.into_inner(), post_data.into_inner().into()).await?;
+    Ok(web::Json(list.into()))
+}
+
+pub async fn delete_todo_list_handler(
+    todo_list_service: web::Data<dyn TodoListService>, params: web::Path<i32>, query: web::Query<DeleteTodoListParams>,
+) -> Result<HttpResponse, ApiError> {
+    todo_list_service.delete(params.into_inner(), query.cascade.unwrap_or(false)).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn archive_todo_list_handler(
+    todo_list_service: web::Data<dyn TodoListService>, params: web::Path<i32>,
+) -> Result<web::Json<TodoListDTO>, ApiError> {
+    let list = todo_list_service.archive(params.into_inner()).await?;
+    Ok(web::Json(list.into()))
+}
+
+pub async fn unarchive_todo_list_handler(
+    todo_list_service: web::Data<dyn TodoListService>, params: web::Path<i32>,
+) -> Result<web::Json<TodoListDTO>, ApiError> {
+    let list = todo_list_service.unarchive(params.into_inner()).await?;
+    Ok(web::Json(list.into()))
+}
+
+pub async fn list_todo_list_todos_handler(
+    todo_list_service: web::Data<dyn TodoListService>, todo_service: web::Data<dyn TodoService>,
+    params: web::Path<i32>, query: web::Query<TodoQueryParams>,
+) -> Result<web::Json<ResultPaging<TodoDTO>>, ApiError> {
+    let list = todo_list_service.get(params.into_inner()).await?;
+    let mut query = query.into_inner();
+    query.list_id = Some(list.id);
+    query.include_archived = Some(true);
+    let selection = todo_service.list(query).await?;
+    Ok(web::Json(selection.into()))
+}
+
+pub async fn create_todo_list_todo_handler(
+    todo_service: web::Data<dyn TodoService>, params: web::Path<i32>, post_data: web::Json<CreateTodoDTO>,
+    user: Option<AuthenticatedUser>,
+) -> Result<web::Json<TodoDTO>, ApiError> {
+    let todo: CreateTodo = post_data.into_inner().into();
+    let todo = todo_service.create(CreateTodo {
+        list_id: Some(params.into_inner()),
+        owner_id: user.map(|user| user.0.id),
+        ..todo
+    }).await?;
+    Ok(web::Json(todo.into()))
+}
+
+pub async fn list_todo_list_shares_handler(
+    todo_list_service: web::Data<dyn TodoListService>, params: web::Path<i32>,
+) -> Result<web::Json<Vec<TodoListShareDTO>>, ApiError> {
+    let shares = todo_list_service.list_shares(params.into_inner()).await?;
+    Ok(web::Json(shares.into_iter().map(TodoListShareDTO::from).collect()))
+}
+
+pub async fn share_todo_list_handler(
+    todo_list_service: web::Data<dyn TodoListService>, params: web::Path<i32>, post_data: web::Json<CreateTodoListShareDTO>,
+) -> Result<web::Json<TodoListShareDTO>, ApiError> {
+    let share = todo_list_service.share(params.into_inner(), post_data.into_inner().into()).await?;
+    Ok(web::Json(share.into()))
+}
+
+pub async fn unshare_todo_list_handler(
+    todo_list_service: web::Data<dyn TodoListService>, params: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, ApiError> {
+    let (list_id, share_id) = params.into_inner();
+    todo_list_service.unshare(list_id, share_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
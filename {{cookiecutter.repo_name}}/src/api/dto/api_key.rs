@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use crate::domain::models::api_key::{ApiKey, CreateApiKey, IssuedApiKey};
+use crate::domain::models::authorization::Permission;
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateApiKeyDTO {
+    pub name: String,
+    pub scopes: Vec<Permission>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiKeyDTO {
+    id: i32,
+    user_id: i32,
+    name: String,
+    prefix: String,
+    scopes: Vec<Permission>,
+    expires_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+// Returned once on creation, the key cannot be looked up later
+#[derive(Debug, Serialize)]
+pub struct IssuedApiKeyDTO {
+    #[serde(flatten)]
+    api_key: ApiKeyDTO,
+    key: String,
+}
+
+impl From<CreateApiKeyDTO> for CreateApiKey {
+    fn from(k: CreateApiKeyDTO) -> Self {
+        CreateApiKey {
+            name: k.name,
+            scopes: k.scopes,
+            expires_at: k.expires_at,
+        }
+    }
+}
+
+impl From<ApiKey> for ApiKeyDTO {
+    fn from(k: ApiKey) -> Self {
+        ApiKeyDTO {
+            id: k.id,
+            user_id: k.user_id,
+            name: k.name,
+            prefix: k.prefix,
+            scopes: k.scopes,
+            expires_at: k.expires_at,
+            last_used_at: k.last_used_at,
+            revoked_at: k.revoked_at,
+            created_at: k.created_at,
+        }
+    }
+}
+
+impl From<IssuedApiKey> for IssuedApiKeyDTO {
+    fn from(issued: IssuedApiKey) -> Self {
+        IssuedApiKeyDTO {
+            api_key: issued.api_key.into(),
+            key: issued.key,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::domain::models::attachment::Attachment;
+
+// The storage key is an implementation detail and not exposed
+#[derive(Debug, Serialize)]
+pub struct AttachmentDTO {
+    id: i32,
+    todo_id: i32,
+    file_name: String,
+    content_type: String,
+    size: i64,
+    checksum: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<Attachment> for AttachmentDTO {
+    fn from(attachment: Attachment) -> Self {
+        AttachmentDTO {
+            id: attachment.id,
+            todo_id: attachment.todo_id,
+            file_name: attachment.file_name,
+            content_type: attachment.content_type,
+            size: attachment.size,
+            checksum: attachment.checksum,
+            created_at: attachment.created_at,
+        }
+    }
+}
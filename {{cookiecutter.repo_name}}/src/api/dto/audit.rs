@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use crate::domain::models::audit::{AuditAction, AuditEvent};
+use crate::domain::repositories::repository::ResultPaging;
+
+#[derive(Debug, Serialize)]
+pub struct AuditEventDTO {
+    id: i64,
+    todo_id: i32,
+    action: AuditAction,
+    actor: Option<String>,
+    request_id: Option<String>,
+    changes: Value,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AuditEvent> for AuditEventDTO {
+    fn from(event: AuditEvent) -> Self {
+        AuditEventDTO {
+            id: event.id,
+            todo_id: event.todo_id,
+            action: event.action,
+            actor: event.actor,
+            request_id: event.request_id,
+            changes: event.changes,
+            created_at: event.created_at,
+        }
+    }
+}
+
+impl From<ResultPaging<AuditEvent>> for ResultPaging<AuditEventDTO> {
+    fn from(paging: ResultPaging<AuditEvent>) -> Self {
+        ResultPaging {
+            total: paging.total,
+            items: paging.items.into_iter().map(AuditEventDTO::from).collect(),
+        }
+    }
+}
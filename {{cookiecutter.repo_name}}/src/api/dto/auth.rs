@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use crate::domain::models::auth::{AuthTokens, Login, RegisterUser};
+use crate::domain::models::authorization::Role;
+use crate::domain::models::user::User;
+use crate::domain::repositories::repository::ResultPaging;
+
+#[derive(Deserialize, Serialize)]
+pub struct RegisterDTO {
+    pub email: String,
+    pub name: Option<String>,
+    pub password: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct LoginDTO {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct RefreshTokenDTO {
+    pub refresh_token: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct SetRoleDTO {
+    pub role: Role,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct SetGroupsDTO {
+    pub groups: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuthTokensDTO {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub token_type: String,
+    // Seconds until the access token expires
+    pub expires_in: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserDTO {
+    id: i32,
+    email: String,
+    name: Option<String>,
+    role: Role,
+    tenant_id: String,
+    groups: Vec<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<User> for UserDTO {
+    fn from(user: User) -> Self {
+        UserDTO {
+            id: user.id,
+            email: user.email,
+            name: user.name,
+            role: user.role,
+            tenant_id: user.tenant_id,
+            groups: user.groups,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+impl From<ResultPaging<User>> for ResultPaging<UserDTO> {
+    fn from(paging: ResultPaging<User>) -> Self {
+        ResultPaging {
+            total: paging.total,
+            items: paging.items.into_iter().map(UserDTO::from).collect(),
+        }
+    }
+}
+
+impl From<AuthTokens> for AuthTokensDTO {
+    fn from(tokens: AuthTokens) -> Self {
+        AuthTokensDTO {
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            token_type: "Bearer".to_string(),
+            expires_in: (tokens.expires_at - Utc::now()).num_seconds().max(0),
+        }
+    }
+}
+
+impl From<RegisterDTO> for RegisterUser {
+    fn from(u: RegisterDTO) -> Self {
+        RegisterUser {
+            email: u.email,
+            name: u.name,
+            password: u.password,
+        }
+    }
+}
+
+impl From<LoginDTO> for Login {
+    fn from(l: LoginDTO) -> Self {
+        Login {
+            email: l.email,
+            password: l.password,
+        }
+    }
+}
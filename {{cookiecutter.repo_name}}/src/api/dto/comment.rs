@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use crate::domain::models::comment::{Comment, CreateComment, UpdateComment};
+use crate::domain::repositories::repository::ResultPaging;
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateCommentDTO {
+    pub body: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateCommentDTO {
+    pub body: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentDTO {
+    id: i32,
+    todo_id: i32,
+    author: Option<String>,
+    author_id: Option<i32>,
+    body: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<Comment> for CommentDTO {
+    fn from(comment: Comment) -> Self {
+        CommentDTO {
+            id: comment.id,
+            todo_id: comment.todo_id,
+            author: comment.author,
+            author_id: comment.author_id,
+            body: comment.body,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+        }
+    }
+}
+
+impl From<CreateCommentDTO> for CreateComment {
+    fn from(c: CreateCommentDTO) -> Self {
+        CreateComment {
+            author: None,
+            author_id: None,
+            body: c.body,
+        }
+    }
+}
+
+impl From<UpdateCommentDTO> for UpdateComment {
+    fn from(c: UpdateCommentDTO) -> Self {
+        UpdateComment {
+            body: c.body,
+        }
+    }
+}
+
+impl From<ResultPaging<Comment>> for ResultPaging<CommentDTO> {
+    fn from(paging: ResultPaging<Comment>) -> Self {
+        ResultPaging {
+            total: paging.total,
+            items: paging.items.into_iter().map(CommentDTO::from).collect(),
+        }
+    }
+}
@@ -1 +1,11 @@
-pub mod todo;
\ No newline at end of file
+pub mod todo;
+pub mod tag;
+pub mod todo_list;
+pub mod todo_item;
+pub mod comment;
+pub mod attachment;
+pub mod audit;
+pub mod auth;
+pub mod service_context;
+pub mod api_key;
+pub mod tenant;
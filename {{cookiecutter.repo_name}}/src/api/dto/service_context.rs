@@ -0,0 +1,32 @@
+use serde::{Serialize, Deserialize};
+use crate::domain::models::service_context::ServiceContext;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceContextDTO {
+    // `None` for the global context
+    pub tenant_id: Option<String>,
+    pub maintenance: bool,
+    // Whether requests are turned away, which for a tenant is also the case during global maintenance
+    pub maintenance_active: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateServiceContextDTO {
+    pub maintenance: bool,
+}
+
+// Requests work with the context of their tenant unless `global` is set
+#[derive(Deserialize, Serialize)]
+pub struct ServiceContextParams {
+    pub global: Option<bool>,
+}
+
+impl ServiceContextDTO {
+    pub fn new(service_context: ServiceContext, maintenance_active: bool) -> Self {
+        ServiceContextDTO {
+            tenant_id: service_context.tenant_id,
+            maintenance: service_context.maintenance,
+            maintenance_active,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use crate::api::dto::todo::double_option;
+use crate::domain::models::tag::{CreateTag, Tag, UpdateTag};
+use crate::domain::repositories::repository::ResultPaging;
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateTagDTO {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateTagDTO {
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub color: Option<Option<String>>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct AttachTagsDTO {
+    pub tag_ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagDTO {
+    id: i32,
+    name: String,
+    color: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<Tag> for TagDTO {
+    fn from(tag: Tag) -> Self {
+        TagDTO {
+            id: tag.id,
+            name: tag.name,
+            color: tag.color,
+            created_at: tag.created_at,
+        }
+    }
+}
+
+impl From<CreateTagDTO> for CreateTag {
+    fn from(t: CreateTagDTO) -> Self {
+        CreateTag {
+            name: t.name,
+            color: t.color,
+        }
+    }
+}
+
+impl From<UpdateTagDTO> for UpdateTag {
+    fn from(t: UpdateTagDTO) -> Self {
+        UpdateTag {
+            name: t.name,
+            color: t.color,
+        }
+    }
+}
+
+impl From<ResultPaging<Tag>> for ResultPaging<TagDTO> {
+    fn from(paging: ResultPaging<Tag>) -> Self {
+        ResultPaging {
+            total: paging.total,
+            items: paging.items.into_iter().map(TagDTO::from).collect(),
+        }
+    }
+}
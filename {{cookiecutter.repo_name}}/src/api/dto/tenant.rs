@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use crate::domain::models::tenant::{CreateTenant, RegisteredTenant, UpdateTenant};
+use crate::domain::repositories::repository::ResultPaging;
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateTenantDTO {
+    pub id: String,
+    #[serde(default)]
+    pub allow_anonymous: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateTenantDTO {
+    pub allow_anonymous: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TenantDTO {
+    pub id: String,
+    pub allow_anonymous: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<RegisteredTenant> for TenantDTO {
+    fn from(tenant: RegisteredTenant) -> Self {
+        TenantDTO {
+            id: tenant.id,
+            allow_anonymous: tenant.allow_anonymous,
+            created_at: tenant.created_at,
+        }
+    }
+}
+
+impl From<CreateTenantDTO> for CreateTenant {
+    fn from(t: CreateTenantDTO) -> Self {
+        CreateTenant {
+            id: t.id,
+            allow_anonymous: t.allow_anonymous,
+        }
+    }
+}
+
+impl From<UpdateTenantDTO> for UpdateTenant {
+    fn from(t: UpdateTenantDTO) -> Self {
+        UpdateTenant {
+            allow_anonymous: t.allow_anonymous,
+        }
+    }
+}
+
+impl From<ResultPaging<RegisteredTenant>> for ResultPaging<TenantDTO> {
+    fn from(paging: ResultPaging<RegisteredTenant>) -> Self {
+        ResultPaging {
+            total: paging.total,
+            items: paging.items.into_iter().map(TenantDTO::from).collect(),
+        }
+    }
+}
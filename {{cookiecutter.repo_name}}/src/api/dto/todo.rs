@@ -1,11 +1,101 @@
-use crate::domain::models::todo::{CreateTodo, Todo};
-use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+use crate::api::dto::tag::TagDTO;
+use crate::domain::error::CommonError;
+use crate::domain::models::recurrence::Recurrence;
+use crate::domain::models::todo::{CreateTodo, MoveTodo, Todo, TodoOperation, TodoSearchHit, TodoPriority, TodoStatus, UpdateTodo};
+use serde::{Serialize, Deserialize, Deserializer};
 use crate::domain::repositories::repository::ResultPaging;
 
+// Tells an explicit `null` (clear the field) apart from a missing field (leave it untouched)
+pub fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct CreateTodoDTO {
     pub title: String,
     pub description: String,
+    pub due_at: Option<DateTime<Utc>>,
+    pub remind_at: Option<DateTime<Utc>>,
+    pub status: Option<TodoStatus>,
+    pub priority: Option<TodoPriority>,
+    pub list_id: Option<i32>,
+    pub recurrence: Option<Recurrence>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateTodoDTO {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub remind_at: Option<Option<DateTime<Utc>>>,
+    pub status: Option<TodoStatus>,
+    pub priority: Option<TodoPriority>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub list_id: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub recurrence: Option<Option<Recurrence>>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct OccurrencesQueryDTO {
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct MoveTodoDTO {
+    pub before: Option<i32>,
+    pub after: Option<i32>,
+}
+
+impl From<MoveTodoDTO> for MoveTodo {
+    fn from(t: MoveTodoDTO) -> Self {
+        MoveTodo {
+            before: t.before,
+            after: t.after,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TodoOperationDTO {
+    Create(CreateTodoDTO),
+    Update {
+        id: i32,
+        version: Option<i32>,
+        #[serde(flatten)]
+        todo: UpdateTodoDTO,
+    },
+    Delete { id: i32, version: Option<i32> },
+    Complete { id: i32 },
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct BulkTodoRequestDTO {
+    pub operations: Vec<TodoOperationDTO>,
+    pub atomic: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkTodoResultDTO {
+    index: usize,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    todo: Option<TodoDTO>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<CommonError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkTodoResponseDTO {
+    results: Vec<BulkTodoResultDTO>,
 }
 
 #[derive(Debug, Serialize)]
@@ -14,15 +104,51 @@ pub struct TodoDTO {
     title: String,
     description: String,
     completed: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    deleted_at: Option<DateTime<Utc>>,
+    version: i32,
+    completed_at: Option<DateTime<Utc>>,
+    completed_by: Option<String>,
+    due_at: Option<DateTime<Utc>>,
+    remind_at: Option<DateTime<Utc>>,
+    status: TodoStatus,
+    priority: TodoPriority,
+    list_id: Option<i32>,
+    position: i64,
+    recurrence: Option<Recurrence>,
+    owner_id: Option<i32>,
+    tags: Vec<TagDTO>,
+    done_items: i64,
+    total_items: i64,
+    comment_count: i64,
 }
 
-impl Into<TodoDTO> for Todo {
-    fn into(self) -> TodoDTO {
+impl From<Todo> for TodoDTO {
+    fn from(todo: Todo) -> Self {
         TodoDTO {
-            id: self.id,
-            title: self.title,
-            description: self.description,
-            completed: false
+            id: todo.id,
+            title: todo.title,
+            description: todo.description,
+            completed: todo.completed,
+            created_at: todo.created_at,
+            updated_at: todo.updated_at,
+            deleted_at: todo.deleted_at,
+            version: todo.version,
+            completed_at: todo.completed_at,
+            completed_by: todo.completed_by,
+            due_at: todo.due_at,
+            remind_at: todo.remind_at,
+            status: todo.status,
+            priority: todo.priority,
+            list_id: todo.list_id,
+            position: todo.position,
+            recurrence: todo.recurrence,
+            owner_id: todo.owner_id,
+            tags: todo.tags.into_iter().map(TagDTO::from).collect(),
+            done_items: todo.done_items,
+            total_items: todo.total_items,
+            comment_count: todo.comment_count,
         }
     }
 }
@@ -32,6 +158,13 @@ impl Into<CreateTodo> for CreateTodoDTO {
         CreateTodo {
             title: self.title,
             description: self.description,
+            due_at: self.due_at,
+            remind_at: self.remind_at,
+            status: self.status.unwrap_or_default(),
+            priority: self.priority.unwrap_or_default(),
+            list_id: self.list_id,
+            recurrence: self.recurrence,
+            owner_id: None,
         }
     }
 }
@@ -41,6 +174,27 @@ impl Into<CreateTodoDTO> for CreateTodo {
         CreateTodoDTO {
             title: self.title,
             description: self.description,
+            due_at: self.due_at,
+            remind_at: self.remind_at,
+            status: Some(self.status),
+            priority: Some(self.priority),
+            list_id: self.list_id,
+            recurrence: self.recurrence,
+        }
+    }
+}
+
+impl From<UpdateTodoDTO> for UpdateTodo {
+    fn from(t: UpdateTodoDTO) -> Self {
+        UpdateTodo {
+            title: t.title,
+            description: t.description,
+            due_at: t.due_at,
+            remind_at: t.remind_at,
+            status: t.status,
+            priority: t.priority,
+            list_id: t.list_id,
+            recurrence: t.recurrence,
         }
     }
 }
@@ -52,4 +206,56 @@ impl Into<ResultPaging<TodoDTO>> for ResultPaging<Todo> {
             items: self.items.into_iter().map(|todo| todo.into()).collect(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl From<TodoOperationDTO> for TodoOperation {
+    fn from(operation: TodoOperationDTO) -> Self {
+        match operation {
+            TodoOperationDTO::Create(todo) => TodoOperation::Create(todo.into()),
+            TodoOperationDTO::Update { id, version, todo } => TodoOperation::Update {
+                todo_id: id,
+                todo: todo.into(),
+                expected_version: version,
+            },
+            TodoOperationDTO::Delete { id, version } => TodoOperation::Delete { todo_id: id, expected_version: version },
+            TodoOperationDTO::Complete { id } => TodoOperation::Complete { todo_id: id, completed_by: None },
+        }
+    }
+}
+
+impl From<Vec<Result<Option<Todo>, CommonError>>> for BulkTodoResponseDTO {
+    fn from(results: Vec<Result<Option<Todo>, CommonError>>) -> Self {
+        BulkTodoResponseDTO {
+            results: results.into_iter().enumerate().map(|(index, result)| match result {
+                Ok(todo) => BulkTodoResultDTO { index, success: true, todo: todo.map(TodoDTO::from), error: None },
+                Err(error) => BulkTodoResultDTO { index, success: false, todo: None, error: Some(error) },
+            }).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TodoSearchHitDTO {
+    todo: TodoDTO,
+    rank: f32,
+    snippet: String,
+}
+
+impl From<TodoSearchHit> for TodoSearchHitDTO {
+    fn from(hit: TodoSearchHit) -> Self {
+        TodoSearchHitDTO {
+            todo: hit.todo.into(),
+            rank: hit.rank,
+            snippet: hit.snippet,
+        }
+    }
+}
+
+impl From<ResultPaging<TodoSearchHit>> for ResultPaging<TodoSearchHitDTO> {
+    fn from(paging: ResultPaging<TodoSearchHit>) -> Self {
+        ResultPaging {
+            total: paging.total,
+            items: paging.items.into_iter().map(TodoSearchHitDTO::from).collect(),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use crate::domain::models::todo_item::{CreateTodoItem, TodoItem, UpdateTodoItem};
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateTodoItemDTO {
+    pub title: String,
+    pub position: Option<i32>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateTodoItemDTO {
+    pub title: Option<String>,
+    pub done: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ReorderTodoItemsDTO {
+    pub item_ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TodoItemDTO {
+    id: i32,
+    todo_id: i32,
+    title: String,
+    position: i32,
+    done: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<TodoItem> for TodoItemDTO {
+    fn from(item: TodoItem) -> Self {
+        TodoItemDTO {
+            id: item.id,
+            todo_id: item.todo_id,
+            title: item.title,
+            position: item.position,
+            done: item.done,
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+        }
+    }
+}
+
+impl From<CreateTodoItemDTO> for CreateTodoItem {
+    fn from(t: CreateTodoItemDTO) -> Self {
+        CreateTodoItem {
+            title: t.title,
+            position: t.position,
+        }
+    }
+}
+
+impl From<UpdateTodoItemDTO> for UpdateTodoItem {
+    fn from(t: UpdateTodoItemDTO) -> Self {
+        UpdateTodoItem {
+            title: t.title,
+            done: t.done,
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use crate::api::dto::todo::double_option;
+use crate::domain::models::share::{AccessibleTodoList, CreateTodoListShare, SharePermission, TodoListShare};
+use crate::domain::models::todo_list::{CreateTodoList, TodoList, UpdateTodoList};
+use crate::domain::repositories::repository::ResultPaging;
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateTodoListDTO {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub owner: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateTodoListDTO {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub owner: Option<Option<String>>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct DeleteTodoListParams {
+    pub cascade: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateTodoListShareDTO {
+    pub user_id: Option<i32>,
+    pub group: Option<String>,
+    pub permission: SharePermission,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TodoListDTO {
+    id: i32,
+    name: String,
+    description: String,
+    owner: Option<String>,
+    owner_id: Option<i32>,
+    archived: bool,
+    archived_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<TodoList> for TodoListDTO {
+    fn from(list: TodoList) -> Self {
+        TodoListDTO {
+            id: list.id,
+            name: list.name,
+            description: list.description,
+            owner: list.owner,
+            owner_id: list.owner_id,
+            archived: list.archived,
+            archived_at: list.archived_at,
+            created_at: list.created_at,
+            updated_at: list.updated_at,
+        }
+    }
+}
+
+// `access` is `owner` for the principal's own lists, otherwise the permission they were granted
+#[derive(Debug, Serialize)]
+pub struct AccessibleTodoListDTO {
+    #[serde(flatten)]
+    list: TodoListDTO,
+    access: String,
+}
+
+impl From<AccessibleTodoList> for AccessibleTodoListDTO {
+    fn from(accessible: AccessibleTodoList) -> Self {
+        AccessibleTodoListDTO {
+            list: accessible.list.into(),
+            access: accessible.shared.map_or("owner", |shared| shared.as_str()).to_string(),
+        }
+    }
+}
+
+impl From<ResultPaging<AccessibleTodoList>> for ResultPaging<AccessibleTodoListDTO> {
+    fn from(paging: ResultPaging<AccessibleTodoList>) -> Self {
+        ResultPaging {
+            total: paging.total,
+            items: paging.items.into_iter().map(AccessibleTodoListDTO::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TodoListShareDTO {
+    id: i32,
+    list_id: i32,
+    user_id: Option<i32>,
+    group: Option<String>,
+    permission: SharePermission,
+    created_at: DateTime<Utc>,
+}
+
+impl From<TodoListShare> for TodoListShareDTO {
+    fn from(share: TodoListShare) -> Self {
+        TodoListShareDTO {
+            id: share.id,
+            list_id: share.list_id,
+            user_id: share.user_id,
+            group: share.group,
+            permission: share.permission,
+            created_at: share.created_at,
+        }
+    }
+}
+
+impl From<CreateTodoListShareDTO> for CreateTodoListShare {
+    fn from(s: CreateTodoListShareDTO) -> Self {
+        CreateTodoListShare {
+            user_id: s.user_id,
+            group: s.group,
+            permission: s.permission,
+        }
+    }
+}
+
+impl From<CreateTodoListDTO> for CreateTodoList {
+    fn from(l: CreateTodoListDTO) -> Self {
+        CreateTodoList {
+            name: l.name,
+            description: l.description,
+            owner: l.owner,
+            owner_id: None,
+        }
+    }
+}
+
+impl From<UpdateTodoListDTO> for UpdateTodoList {
+    fn from(l: UpdateTodoListDTO) -> Self {
+        UpdateTodoList {
+            name: l.name,
+            description: l.description,
+            owner: l.owner,
+        }
+    }
+}
+
+impl From<ResultPaging<TodoList>> for ResultPaging<TodoListDTO> {
+    fn from(paging: ResultPaging<TodoList>) -> Self {
+        ResultPaging {
+            total: paging.total,
+            items: paging.items.into_iter().map(TodoListDTO::from).collect(),
+        }
+    }
+}
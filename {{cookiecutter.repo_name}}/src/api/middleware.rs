@@ -1,10 +1,48 @@
 use std::future::{ready, Ready};
+use std::rc::Rc;
 
-use actix_web::{body::EitherBody, dev::{self, Service, ServiceRequest, ServiceResponse, Transform}, Error, HttpResponse, web};
+use actix_web::{body::{self, BoxBody, EitherBody, MessageBody}, dev::{self, Payload, Service, ServiceRequest, ServiceResponse, Transform}, Error, HttpMessage, HttpResponse, ResponseError, web};
+use actix_web::http::{header, Method};
+use actix_web::http::header::{HeaderName, HeaderValue};
 use futures_util::future::LocalBoxFuture;
-use log::info;
+use futures_util::StreamExt;
+use log::{error, info};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use crate::api::auth::AuthenticatedUser;
+use crate::domain::error::{ApiError, CommonError};
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::idempotency::StoredResponse;
+use crate::domain::models::tenant::{RegisteredTenant, Tenant};
+use crate::domain::services::idempotency::IdempotencyService;
 use crate::domain::services::service_context::ServiceContextService;
+use crate::domain::services::tenant::TenantService;
 
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+// Names the tenant a request is made to, taking precedence over the subdomain
+pub const TENANT_HEADER: &str = "X-Tenant-Id";
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+// Responses to signing in and creating API keys carry secrets, which are not to be stored
+const IDEMPOTENCY_EXEMPT_SCOPE: &str = "/auth/";
+// Stored along with idempotent responses, so replays can be told apart by the same means
+const REPLAYED_HEADERS: [HeaderName; 2] = [header::ETAG, header::LOCATION];
+// Stay reachable during maintenance, so admins can sign in and end it
+const MAINTENANCE_EXEMPT_PATHS: [&str; 3] = ["/auth/login", "/auth/refresh", "/admin/service-context"];
+
+// Whether the request is to be turned away as the tenant is in maintenance. `Authentication`
+// checks again when credentials switch the request to another tenant.
+pub(crate) fn in_maintenance(request: &ServiceRequest, tenant: &Tenant) -> Result<bool, CommonError> {
+    let service_context_service =
+        request.app_data::<web::Data<dyn ServiceContextService>>().unwrap();
+    if MAINTENANCE_EXEMPT_PATHS.contains(&request.path()) {
+        return Ok(false);
+    }
+    service_context_service.is_maintenance_active(tenant)
+}
+
+// Needs to run within `TenantResolution`
 pub struct ServiceContextMaintenanceCheck;
 
 impl<S, B> Transform<S, ServiceRequest> for ServiceContextMaintenanceCheck
@@ -40,13 +78,18 @@ where
     dev::forward_ready!(service);
 
     fn call(&self, request: ServiceRequest) -> Self::Future {
-        let service_context_service =
-            request.app_data::<web::Data<dyn ServiceContextService>>().unwrap();
-
-        if service_context_service.is_maintenance_active() {
-            info!("Service is in maintenance mode");
+        let tenant = Tenant::current().unwrap_or_default();
+        let response = match in_maintenance(&request, &tenant) {
+            Ok(false) => None,
+            Ok(true) => {
+                info!("Tenant {} is in maintenance mode", tenant);
+                Some(HttpResponse::ServiceUnavailable().finish())
+            }
+            Err(e) => Some(ApiError::from(e).error_response()),
+        };
+        if let Some(response) = response {
             let (request, _pl) = request.into_parts();
-            let response = HttpResponse::ServiceUnavailable().finish().map_into_right_body();
+            let response = response.map_into_right_body();
             return Box::pin(async { Ok(ServiceResponse::new(request, response)) });
         }
 
@@ -56,4 +99,331 @@ where
             res.await.map(ServiceResponse::map_into_left_body)
         })
     }
-}
\ No newline at end of file
+}
+
+// Needs to run within `Authentication`, keys are scoped to the signed in user. Bodies of requests
+// with a key are buffered up to `body_limit` bytes, larger ones are rejected with 413. Keys are
+// ignored in the auth scope.
+pub struct IdempotencyKeyCheck {
+    body_limit: usize,
+}
+
+impl IdempotencyKeyCheck {
+    pub fn new(body_limit: usize) -> Self {
+        IdempotencyKeyCheck { body_limit }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for IdempotencyKeyCheck
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = IdempotencyKeyCheckMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(IdempotencyKeyCheckMiddleware { service: Rc::new(service), body_limit: self.body_limit }))
+    }
+}
+pub struct IdempotencyKeyCheckMiddleware<S> {
+    service: Rc<S>,
+    body_limit: usize,
+}
+
+async fn read_body(request: &mut ServiceRequest, limit: usize) -> Result<web::Bytes, Error> {
+    let mut payload = request.take_payload();
+    let mut body = web::BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk?;
+        if body.len() + chunk.len() > limit {
+            return Err(actix_web::error::ErrorPayloadTooLarge(format!("Request bodies must not exceed {} bytes", limit)));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body.freeze())
+}
+
+// Keys are claimed per user, anonymous requests share the empty principal
+fn request_principal(request: &ServiceRequest) -> String {
+    request.extensions().get::<AuthenticatedUser>().map(|user| user.0.id.to_string()).unwrap_or_default()
+}
+
+// Identifies a request by its user, method, path, query and body so a reused key can be told apart
+fn request_fingerprint(request: &ServiceRequest, principal: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(principal);
+    hasher.update(request.method().as_str());
+    hasher.update(request.uri().to_string());
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+fn replay(response: StoredResponse) -> HttpResponse {
+    let status = actix_web::http::StatusCode::from_u16(response.status)
+        .unwrap_or(actix_web::http::StatusCode::OK);
+    let mut builder = HttpResponse::build(status);
+    builder.insert_header((IDEMPOTENT_REPLAYED_HEADER, "true"));
+    if let Some(content_type) = response.content_type {
+        builder.content_type(content_type);
+    }
+    for (name, value) in response.headers {
+        builder.insert_header((name, value));
+    }
+    builder.body(response.body)
+}
+
+impl<S, B> Service<ServiceRequest> for IdempotencyKeyCheckMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, mut request: ServiceRequest) -> Self::Future {
+        let is_mutating = matches!(*request.method(), Method::POST | Method::PUT | Method::PATCH | Method::DELETE)
+            && !request.path().starts_with(IDEMPOTENCY_EXEMPT_SCOPE);
+        let key = request.headers().get(IDEMPOTENCY_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+        let idempotency_service = request.app_data::<web::Data<dyn IdempotencyService>>().cloned();
+
+        let (key, idempotency_service) = match (is_mutating, key, idempotency_service) {
+            (true, Some(key), Some(idempotency_service)) => (key, idempotency_service),
+            _ => {
+                let res = self.service.call(request);
+                return Box::pin(async move { res.await.map(ServiceResponse::map_into_boxed_body) });
+            }
+        };
+
+        let service = self.service.clone();
+        let body_limit = self.body_limit;
+        Box::pin(async move {
+            let body = match read_body(&mut request, body_limit).await {
+                Ok(body) => body,
+                Err(e) => return Ok(request.error_response(e)),
+            };
+            let principal = request_principal(&request);
+            let fingerprint = request_fingerprint(&request, &principal, &body);
+            request.set_payload(Payload::from(body));
+
+            match idempotency_service.begin(&principal, &key, &fingerprint).await {
+                Ok(None) => {}
+                Ok(Some(stored)) => {
+                    info!("Replaying response for idempotency key {}", key);
+                    return Ok(request.into_response(replay(stored)));
+                }
+                Err(e) => return Ok(request.error_response(ApiError::from(e))),
+            }
+
+            let response = match service.call(request).await {
+                Ok(response) => response,
+                Err(e) => {
+                    let _ = idempotency_service.abandon(&principal, &key).await;
+                    return Err(e);
+                }
+            };
+            let (request, response) = response.into_parts();
+            let status = response.status();
+            let content_type = response.headers().get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string());
+            let headers = REPLAYED_HEADERS.iter()
+                .filter_map(|name| Some((name.to_string(), response.headers().get(name)?.to_str().ok()?.to_string())))
+                .collect();
+            let (response, response_body) = response.into_parts();
+            let bytes = match body::to_bytes(response_body).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    let _ = idempotency_service.abandon(&principal, &key).await;
+                    let e: Box<dyn std::error::Error> = e.into();
+                    return Err(actix_web::error::ErrorInternalServerError(e.to_string()));
+                }
+            };
+
+            // Server errors are not stored so the client can retry them with the same key
+            let outcome = if status.is_server_error() {
+                idempotency_service.abandon(&principal, &key).await
+            } else {
+                idempotency_service.complete(&principal, &key, StoredResponse {
+                    status: status.as_u16(),
+                    content_type,
+                    headers,
+                    body: bytes.to_vec(),
+                }).await
+            };
+            if let Err(e) = outcome {
+                error!("Could not record idempotency key {}: {}", key, e);
+            }
+
+            let response = response.set_body(bytes).map_into_boxed_body();
+            Ok(ServiceResponse::new(request, response))
+        })
+    }
+}
+
+// Runs the request within its audit context, taking the request id from the client when
+// it sends a usable one and echoing it back in the response
+pub struct RequestContext;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestContext
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestContextMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestContextMiddleware { service }))
+    }
+}
+pub struct RequestContextMiddleware<S> {
+    service: S,
+}
+
+fn header_text(request: &ServiceRequest, name: &str) -> Option<String> {
+    request.headers().get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+impl<S, B> Service<ServiceRequest> for RequestContextMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let request_id = header_text(&request, REQUEST_ID_HEADER)
+            .filter(|request_id| request_id.len() <= MAX_REQUEST_ID_LENGTH)
+            .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+        // The actor is only known once `Authentication` has found out who is signed in
+        let context = AuditContext {
+            actor: None,
+            request_id: Some(request_id.clone()),
+        };
+
+        let res = context.clone().sync_scope(|| self.service.call(request));
+        Box::pin(context.scope(async move {
+            let mut response = res.await?;
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                response.headers_mut().insert(HeaderName::from_static("x-request-id"), value);
+            }
+            Ok(response)
+        }))
+    }
+}
+
+// The tenant a request explicitly named, put in the request extensions by `TenantResolution`
+#[derive(Debug, Clone)]
+pub struct RequestedTenant(pub RegisteredTenant);
+
+// Runs the request within the tenant it is made to: the one named by the `X-Tenant-Id` header,
+// else the subdomain of the configured domain the request was sent to, else the default tenant.
+// Requests naming an invalid tenant are rejected with 400, ones naming a tenant that is not
+// registered with 404.
+pub struct TenantResolution {
+    domain: Option<String>,
+}
+
+impl TenantResolution {
+    pub fn new(domain: Option<String>) -> Self {
+        TenantResolution { domain: domain.map(|domain| domain.trim_start_matches('.').to_ascii_lowercase()) }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for TenantResolution
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = TenantResolutionMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(TenantResolutionMiddleware { service: Rc::new(service), domain: self.domain.clone() }))
+    }
+}
+pub struct TenantResolutionMiddleware<S> {
+    service: Rc<S>,
+    domain: Option<String>,
+}
+
+impl<S> TenantResolutionMiddleware<S> {
+    // The single label in front of the domain, ignoring the port
+    fn subdomain(&self, request: &ServiceRequest) -> Option<String> {
+        let domain = self.domain.as_deref()?;
+        let host = request.connection_info().host().to_ascii_lowercase();
+        let host = host.split(':').next().unwrap_or_default();
+        host.strip_suffix(domain)
+            .and_then(|rest| rest.strip_suffix('.'))
+            .filter(|label| !label.is_empty() && !label.contains('.'))
+            .map(str::to_string)
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for TenantResolutionMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let requested = match header_text(&request, TENANT_HEADER).or_else(|| self.subdomain(&request)) {
+            Some(tenant) => match Tenant::parse(&tenant) {
+                Ok(tenant) => Some(tenant),
+                Err(e) => return Box::pin(async { Ok(request.error_response(ApiError::from(e)).map_into_right_body()) }),
+            },
+            None => None,
+        };
+        let tenant_service = request.app_data::<web::Data<dyn TenantService>>().unwrap().clone();
+
+        let service = self.service.clone();
+        Box::pin(async move {
+            let tenant = match requested {
+                Some(requested) => match tenant_service.find(&requested.id).await {
+                    Ok(registered) => {
+                        request.extensions_mut().insert(RequestedTenant(registered));
+                        requested
+                    }
+                    Err(e) => return Ok(request.error_response(ApiError::from(e)).map_into_right_body()),
+                },
+                None => Tenant::default(),
+            };
+            let res = tenant.clone().sync_scope(|| service.call(request));
+            tenant.scope(res).await.map(ServiceResponse::map_into_left_body)
+        })
+    }
+}
@@ -1,3 +1,4 @@
 pub mod controllers;
 pub mod dto;
-pub mod middleware;
\ No newline at end of file
+pub mod middleware;
+pub mod auth;
@@ -1,15 +1,100 @@
 use std::sync::Arc;
+use log::warn;
+use uuid::Uuid;
+use crate::domain::constants::{
+    ADMIN_EMAILS, ADMIN_PASSWORD, ADMIN_TENANT, ANONYMOUS_ROLE, ATTACHMENT_CONTENT_TYPES, ATTACHMENT_MAX_SIZE_BYTES, ATTACHMENT_STORAGE,
+    ATTACHMENT_STORAGE_PATH, DEFAULT_ANONYMOUS_ROLE, DEFAULT_ATTACHMENT_CONTENT_TYPES, DEFAULT_ATTACHMENT_MAX_SIZE_BYTES,
+    DEFAULT_ATTACHMENT_STORAGE, DEFAULT_ATTACHMENT_STORAGE_PATH, DEFAULT_IDEMPOTENCY_KEY_TTL_HOURS, DEFAULT_JOB_LOCK_TIMEOUT_SECS,
+    DEFAULT_JWT_ACCESS_TOKEN_TTL_SECS, DEFAULT_JWT_REFRESH_TOKEN_TTL_SECS, DEFAULT_OIDC_EMAIL_CLAIM,
+    DEFAULT_OIDC_JWKS_CACHE_TTL_SECS, DEFAULT_OIDC_JWKS_MIN_REFRESH_SECS, DEFAULT_OIDC_ROLES_CLAIM, DEFAULT_S3_REGION,
+    DEFAULT_TENANT, DEFAULT_TODO_SEARCH_BACKEND, IDEMPOTENCY_KEY_TTL_HOURS, JOB_LOCK_TIMEOUT_SECS, JWT_ACCESS_TOKEN_TTL_SECS,
+    MIN_IDEMPOTENT_BODY_BYTES, MULTIPART_OVERHEAD_BYTES,
+    JWT_REFRESH_TOKEN_TTL_SECS, JWT_SECRET, OIDC_AUDIENCE, OIDC_EMAIL_CLAIM, OIDC_ISSUER, OIDC_JWKS_CACHE_TTL_SECS,
+    OIDC_JWKS_MIN_REFRESH_SECS, OIDC_JWKS_PATH, OIDC_JWKS_URL, OIDC_ROLES_CLAIM, OIDC_TENANT, OIDC_TENANT_CLAIM, OIDC_TRUST_ROLES, S3_ACCESS_KEY_ID, S3_BUCKET, S3_ENDPOINT,
+    S3_REGION, S3_SECRET_ACCESS_KEY, TENANT_DOMAIN, TODO_AUTO_COMPLETE_ON_ITEMS, TODO_SEARCH_BACKEND, TODO_STATUS_TRANSITIONS,
+};
+use crate::domain::models::todo::TodoWorkflow;
+use crate::domain::repositories::api_key::ApiKeyRepository;
+use crate::domain::repositories::attachment::AttachmentRepository;
+use crate::domain::repositories::audit::AuditRepository;
+use crate::domain::repositories::comment::CommentRepository;
+use crate::domain::repositories::idempotency::IdempotencyRepository;
+use crate::domain::repositories::job::JobRepository;
+use crate::domain::repositories::tag::TagRepository;
+use crate::domain::repositories::tenant::TenantRepository;
 use crate::domain::repositories::todo::TodoRepository;
+use crate::domain::repositories::todo_item::TodoItemRepository;
+use crate::domain::repositories::todo_list::TodoListRepository;
+use crate::domain::repositories::todo_search::TodoSearchRepository;
+use crate::domain::repositories::user::UserRepository;
+use crate::domain::services::api_key::ApiKeyService;
+use crate::domain::services::attachment::AttachmentService;
+use crate::domain::services::audit::AuditService;
+use crate::domain::services::auth::AuthService;
+use crate::domain::services::blob_store::BlobStore;
+use crate::domain::services::comment::CommentService;
+use crate::domain::services::idempotency::IdempotencyService;
+use crate::domain::services::identity_provider::IdentityProvider;
+use crate::domain::services::job::JobService;
+use crate::domain::services::reminder::ReminderNotifier;
 use crate::domain::services::service_context::ServiceContextService;
+use crate::domain::services::tag::TagService;
+use crate::domain::services::tenant::TenantService;
 use crate::domain::services::todo::TodoService;
+use crate::domain::services::todo_list::TodoListService;
+use crate::infrastructure::config::env_or;
 use crate::infrastructure::databases::postgresql::db_pool;
+use crate::infrastructure::repositories::api_key::ApiKeyDieselRepository;
+use crate::infrastructure::repositories::attachment::AttachmentDieselRepository;
+use crate::infrastructure::repositories::audit::AuditDieselRepository;
+use crate::infrastructure::repositories::comment::CommentDieselRepository;
+use crate::infrastructure::repositories::idempotency::IdempotencyDieselRepository;
+use crate::infrastructure::repositories::job::JobDieselRepository;
+use crate::infrastructure::repositories::tag::TagDieselRepository;
+use crate::infrastructure::repositories::tenant::TenantDieselRepository;
 use crate::infrastructure::repositories::todo::TodoDieselRepository;
+use crate::infrastructure::repositories::todo_item::TodoItemDieselRepository;
+use crate::infrastructure::repositories::todo_list::TodoListDieselRepository;
+use crate::infrastructure::repositories::todo_search::{FullTextTodoSearchRepository, LikeTodoSearchRepository};
+use crate::infrastructure::repositories::user::UserDieselRepository;
+use crate::infrastructure::services::argon2_password_hasher::Argon2PasswordHasher;
+use crate::infrastructure::services::jwks_identity_provider::{JwksIdentityProvider, JwksSource, OidcConfig};
+use crate::infrastructure::services::jwt_token_codec::JwtTokenCodec;
+use crate::infrastructure::services::local_blob_store::LocalBlobStore;
+use crate::infrastructure::services::reminder_notifier::LogReminderNotifier;
+use crate::infrastructure::services::s3_blob_store::{S3BlobStore, S3Config};
 use crate::infrastructure::services::service_context::ServiceContextServiceImpl;
-use crate::services::todo::TodoServiceImpl;
+use crate::services::api_key::ApiKeyServiceImpl;
+use crate::services::attachment::{AttachmentLimits, AttachmentServiceImpl};
+use crate::services::audit::AuditServiceImpl;
+use crate::services::auth::{AccessPolicy, AuthServiceImpl, TokenLifetimes};
+use crate::services::comment::CommentServiceImpl;
+use crate::services::idempotency::IdempotencyServiceImpl;
+use crate::services::job::JobServiceImpl;
+use crate::services::tag::TagServiceImpl;
+use crate::services::tenant::TenantServiceImpl;
+use crate::services::todo::{TodoRules, TodoServiceImpl};
+use crate::services::todo_list::TodoListServiceImpl;
 
 pub struct Container {
     pub todo_service: Arc<dyn TodoService>,
-    pub service_context_service: Arc<dyn ServiceContextService>
+    pub service_context_service: Arc<dyn ServiceContextService>,
+    pub idempotency_service: Arc<dyn IdempotencyService>,
+    pub job_service: Arc<dyn JobService>,
+    pub reminder_notifier: Arc<dyn ReminderNotifier>,
+    pub tag_service: Arc<dyn TagService>,
+    pub todo_list_service: Arc<dyn TodoListService>,
+    pub comment_service: Arc<dyn CommentService>,
+    pub attachment_service: Arc<dyn AttachmentService>,
+    pub blob_store: Arc<dyn BlobStore>,
+    pub audit_service: Arc<dyn AuditService>,
+    pub auth_service: Arc<dyn AuthService>,
+    pub api_key_service: Arc<dyn ApiKeyService>,
+    pub tenant_service: Arc<dyn TenantService>,
+    // Requests to its subdomains belong to the tenant named by the subdomain
+    pub tenant_domain: Option<String>,
+    // Largest body of a request with an idempotency key, which is buffered to fingerprint it
+    pub idempotent_body_limit: usize,
 }
 
 impl Container {
@@ -18,13 +103,186 @@ impl Container {
         let todo_repository: Arc<dyn TodoRepository> = Arc::new(
             TodoDieselRepository::new(pool.clone())
         );
+        let tag_repository: Arc<dyn TagRepository> = Arc::new(
+            TagDieselRepository::new(pool.clone())
+        );
+        let todo_list_repository: Arc<dyn TodoListRepository> = Arc::new(
+            TodoListDieselRepository::new(pool.clone())
+        );
+        let todo_item_repository: Arc<dyn TodoItemRepository> = Arc::new(
+            TodoItemDieselRepository::new(pool.clone())
+        );
+        let todo_search_repository: Arc<dyn TodoSearchRepository> =
+            match env_or(TODO_SEARCH_BACKEND, DEFAULT_TODO_SEARCH_BACKEND.to_string()).as_str() {
+                "like" => Arc::new(LikeTodoSearchRepository::new(pool.clone())),
+                _ => Arc::new(FullTextTodoSearchRepository::new(pool.clone())),
+            };
+        let comment_repository: Arc<dyn CommentRepository> = Arc::new(
+            CommentDieselRepository::new(pool.clone())
+        );
         let todo_service = Arc::new(
-            TodoServiceImpl { repository: todo_repository }
+            TodoServiceImpl::new(
+                todo_repository.clone(),
+                tag_repository.clone(),
+                todo_list_repository.clone(),
+                todo_item_repository,
+                todo_search_repository,
+                comment_repository.clone(),
+                TodoRules {
+                    workflow: env_or(TODO_STATUS_TRANSITIONS, TodoWorkflow::default()),
+                    auto_complete: env_or(TODO_AUTO_COMPLETE_ON_ITEMS, false),
+                },
+            )
+        );
+        let tag_service = Arc::new(
+            TagServiceImpl::new(tag_repository)
+        );
+        let todo_list_service = Arc::new(
+            TodoListServiceImpl::new(todo_list_repository.clone())
+        );
+        let comment_service = Arc::new(
+            CommentServiceImpl::new(comment_repository)
+        );
+        let blob_store: Arc<dyn BlobStore> =
+            match env_or(ATTACHMENT_STORAGE, DEFAULT_ATTACHMENT_STORAGE.to_string()).as_str() {
+                "s3" => Arc::new(S3BlobStore::new(S3Config {
+                    endpoint: env_or(S3_ENDPOINT, String::new()),
+                    bucket: env_or(S3_BUCKET, String::new()),
+                    region: env_or(S3_REGION, DEFAULT_S3_REGION.to_string()),
+                    access_key_id: env_or(S3_ACCESS_KEY_ID, String::new()),
+                    secret_access_key: env_or(S3_SECRET_ACCESS_KEY, String::new()),
+                })),
+                _ => Arc::new(LocalBlobStore::new(env_or(ATTACHMENT_STORAGE_PATH, DEFAULT_ATTACHMENT_STORAGE_PATH.to_string()))),
+            };
+        let attachment_repository: Arc<dyn AttachmentRepository> = Arc::new(
+            AttachmentDieselRepository::new(pool.clone())
+        );
+        let attachment_limits = AttachmentLimits {
+            max_size: env_or(ATTACHMENT_MAX_SIZE_BYTES, DEFAULT_ATTACHMENT_MAX_SIZE_BYTES),
+            content_types: env_or(ATTACHMENT_CONTENT_TYPES, DEFAULT_ATTACHMENT_CONTENT_TYPES.to_string())
+                .split(',')
+                .map(|content_type| content_type.trim().to_ascii_lowercase())
+                .filter(|content_type| !content_type.is_empty())
+                .collect(),
+        };
+        // Uploads the attachment limit allows must not be turned away for carrying an idempotency key
+        let idempotent_body_limit = (attachment_limits.max_size as usize)
+            .saturating_add(MULTIPART_OVERHEAD_BYTES)
+            .max(MIN_IDEMPOTENT_BODY_BYTES);
+        let attachment_service = Arc::new(
+            AttachmentServiceImpl::new(
+                attachment_repository,
+                todo_repository,
+                todo_list_repository,
+                blob_store.clone(),
+                attachment_limits,
+            )
+        );
+        let audit_repository: Arc<dyn AuditRepository> = Arc::new(
+            AuditDieselRepository::new(pool.clone())
+        );
+        let audit_service = Arc::new(
+            AuditServiceImpl::new(audit_repository)
+        );
+        let user_repository: Arc<dyn UserRepository> = Arc::new(
+            UserDieselRepository::new(pool.clone())
+        );
+        let jwt_secret = env_or(JWT_SECRET, String::new());
+        // Without a configured secret, tokens only stay valid for the lifetime of the process
+        let jwt_secret = if jwt_secret.is_empty() {
+            warn!("{} is not set, using a random secret", JWT_SECRET);
+            format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+        } else {
+            jwt_secret
+        };
+        let api_key_repository: Arc<dyn ApiKeyRepository> = Arc::new(
+            ApiKeyDieselRepository::new(pool.clone())
+        );
+        let api_key_service = Arc::new(
+            ApiKeyServiceImpl::new(api_key_repository, user_repository.clone())
+        );
+        let jwks_url = env_or(OIDC_JWKS_URL, String::new());
+        let jwks_path = env_or(OIDC_JWKS_PATH, String::new());
+        let jwks = if !jwks_url.is_empty() {
+            Some(JwksSource::Url(jwks_url))
+        } else if !jwks_path.is_empty() {
+            Some(JwksSource::File(jwks_path.into()))
+        } else {
+            None
+        };
+        let identity_provider = jwks.map(|jwks| -> Arc<dyn IdentityProvider> {
+            let optional = |key: &str| Some(env_or(key, String::new())).filter(|value| !value.is_empty());
+            Arc::new(JwksIdentityProvider::new(OidcConfig {
+                jwks,
+                issuer: optional(OIDC_ISSUER),
+                audience: optional(OIDC_AUDIENCE),
+                email_claim: env_or(OIDC_EMAIL_CLAIM, DEFAULT_OIDC_EMAIL_CLAIM.to_string()),
+                roles_claim: env_or(OIDC_ROLES_CLAIM, DEFAULT_OIDC_ROLES_CLAIM.to_string()),
+                trust_roles: env_or(OIDC_TRUST_ROLES, false),
+                tenant_claim: optional(OIDC_TENANT_CLAIM),
+                tenant: env_or(OIDC_TENANT, DEFAULT_TENANT.to_string()),
+                cache_ttl: std::time::Duration::from_secs(env_or(OIDC_JWKS_CACHE_TTL_SECS, DEFAULT_OIDC_JWKS_CACHE_TTL_SECS)),
+                min_refresh_interval: std::time::Duration::from_secs(env_or(OIDC_JWKS_MIN_REFRESH_SECS, DEFAULT_OIDC_JWKS_MIN_REFRESH_SECS)),
+            }))
+        });
+        // Manages every tenant, admin emails only make admins there
+        let admin_tenant = env_or(ADMIN_TENANT, DEFAULT_TENANT.to_string());
+        let auth_service = Arc::new(
+            AuthServiceImpl::new(
+                user_repository,
+                Arc::new(Argon2PasswordHasher),
+                Arc::new(JwtTokenCodec::new(jwt_secret.as_bytes())),
+                TokenLifetimes {
+                    access: chrono::Duration::seconds(env_or(JWT_ACCESS_TOKEN_TTL_SECS, DEFAULT_JWT_ACCESS_TOKEN_TTL_SECS)),
+                    refresh: chrono::Duration::seconds(env_or(JWT_REFRESH_TOKEN_TTL_SECS, DEFAULT_JWT_REFRESH_TOKEN_TTL_SECS)),
+                },
+                AccessPolicy {
+                    admin_emails: env_or(ADMIN_EMAILS, String::new())
+                        .split(',')
+                        .map(|email| email.trim().to_lowercase())
+                        .filter(|email| !email.is_empty())
+                        .collect(),
+                    admin_password: Some(env_or(ADMIN_PASSWORD, String::new())).filter(|password| !password.is_empty()),
+                    admin_tenant: admin_tenant.clone(),
+                    anonymous_role: match env_or(ANONYMOUS_ROLE, DEFAULT_ANONYMOUS_ROLE.to_string()).as_str() {
+                        "none" => None,
+                        role => Some(role.parse()
+                            .unwrap_or_else(|e| panic!("Invalid {} {}: {}", ANONYMOUS_ROLE, role, e))),
+                    },
+                },
+                identity_provider,
+            )
         );
         let service_context_service = Arc::new(
             ServiceContextServiceImpl::new(pool.clone())
         );
-        Container { todo_service, service_context_service }
+        let idempotency_repository: Arc<dyn IdempotencyRepository> = Arc::new(
+            IdempotencyDieselRepository::new(pool.clone())
+        );
+        let idempotency_service = Arc::new(
+            IdempotencyServiceImpl::new(
+                idempotency_repository,
+                chrono::Duration::hours(env_or(IDEMPOTENCY_KEY_TTL_HOURS, DEFAULT_IDEMPOTENCY_KEY_TTL_HOURS)),
+            )
+        );
+        let job_repository: Arc<dyn JobRepository> = Arc::new(
+            JobDieselRepository::new(pool.clone())
+        );
+        let job_service = Arc::new(
+            JobServiceImpl::new(
+                job_repository,
+                chrono::Duration::seconds(env_or(JOB_LOCK_TIMEOUT_SECS, DEFAULT_JOB_LOCK_TIMEOUT_SECS)),
+            )
+        );
+        let reminder_notifier = Arc::new(LogReminderNotifier);
+        let tenant_repository: Arc<dyn TenantRepository> = Arc::new(
+            TenantDieselRepository::new(pool.clone())
+        );
+        let tenant_service = Arc::new(
+            TenantServiceImpl::new(tenant_repository, admin_tenant)
+        );
+        let tenant_domain = Some(env_or(TENANT_DOMAIN, String::new())).filter(|domain| !domain.is_empty());
+        Container { todo_service, service_context_service, idempotency_service, job_service, reminder_notifier, tag_service, todo_list_service, comment_service, attachment_service, blob_store, audit_service, auth_service, api_key_service, tenant_service, tenant_domain, idempotent_body_limit }
     }
 }
 
@@ -4,8 +4,20 @@ use actix_web::{Error};
 use actix_web::body::MessageBody;
 use actix_web::dev::{ServiceFactory, ServiceRequest, ServiceResponse};
 use actix_web::middleware::Logger;
-use crate::api::controllers::todo_handler::{create_todo_handler, delete_todo_handler, get_todo_handler, list_todos_handler};
-use crate::api::middleware::{ServiceContextMaintenanceCheck};
+use crate::api::auth::{Authentication, Authorize};
+use crate::api::controllers::api_key_handler::{create_api_key_handler, list_api_keys_handler, revoke_api_key_handler};
+use crate::api::controllers::auth_handler::{current_user_handler, list_users_handler, login_handler, refresh_token_handler, register_handler, set_user_groups_handler, set_user_role_handler};
+use crate::api::controllers::attachment_handler::{delete_attachment_handler, download_attachment_handler, get_attachment_handler, list_attachments_handler, upload_attachment_handler};
+use crate::api::controllers::audit_handler::{list_audit_events_handler, todo_history_handler};
+use crate::api::controllers::comment_handler::{create_comment_handler, delete_comment_handler, get_comment_handler, list_comments_handler, update_comment_handler};
+use crate::api::controllers::service_context_handlers::{get_service_context_handler, update_service_context_handler};
+use crate::api::controllers::tag_handler::{attach_todo_tags_handler, create_tag_handler, delete_tag_handler, detach_todo_tag_handler, get_tag_handler, list_tags_handler, list_todo_tags_handler, update_tag_handler};
+use crate::api::controllers::tenant_handler::{create_tenant_handler, list_tenants_handler, update_tenant_handler};
+use crate::api::controllers::todo_item_handler::{create_todo_item_handler, delete_todo_item_handler, list_todo_items_handler, reorder_todo_items_handler, toggle_todo_item_handler, update_todo_item_handler};
+use crate::api::controllers::todo_list_handler::{archive_todo_list_handler, create_todo_list_handler, create_todo_list_todo_handler, delete_todo_list_handler, get_todo_list_handler, list_accessible_todo_lists_handler, list_todo_list_shares_handler, list_todo_list_todos_handler, list_todo_lists_handler, share_todo_list_handler, unarchive_todo_list_handler, unshare_todo_list_handler, update_todo_list_handler};
+use crate::api::controllers::todo_handler::{bulk_todos_handler, complete_todo_handler, create_todo_handler, delete_todo_handler, get_todo_handler, list_occurrences_handler, list_overdue_todos_handler, list_todos_handler, move_todo_handler, reopen_todo_handler, restore_todo_handler, search_todos_handler, update_todo_handler};
+use crate::api::middleware::{IdempotencyKeyCheck, RequestContext, ServiceContextMaintenanceCheck, TenantResolution};
+use crate::domain::models::authorization::Permission;
 use crate::container::Container;
 
 pub fn create_app(container: Arc<Container>) -> App<
@@ -19,17 +31,147 @@ pub fn create_app(container: Arc<Container>) -> App<
 > {
     let todo_service = container.todo_service.clone();
     let service_context_service = container.service_context_service.clone();
+    let idempotency_service = container.idempotency_service.clone();
+    let tag_service = container.tag_service.clone();
+    let todo_list_service = container.todo_list_service.clone();
+    let comment_service = container.comment_service.clone();
+    let attachment_service = container.attachment_service.clone();
+    let audit_service = container.audit_service.clone();
+    let auth_service = container.auth_service.clone();
+    let api_key_service = container.api_key_service.clone();
+    let tenant_service = container.tenant_service.clone();
+    let tenant_domain = container.tenant_domain.clone();
+    let idempotent_body_limit = container.idempotent_body_limit;
 
     App::new()
         .app_data(web::Data::from(todo_service.clone()))
         .app_data(web::Data::from(service_context_service.clone()))
-        .wrap(Logger::default())
+        .app_data(web::Data::from(idempotency_service.clone()))
+        .app_data(web::Data::from(tag_service.clone()))
+        .app_data(web::Data::from(todo_list_service.clone()))
+        .app_data(web::Data::from(comment_service.clone()))
+        .app_data(web::Data::from(attachment_service.clone()))
+        .app_data(web::Data::from(audit_service.clone()))
+        .app_data(web::Data::from(auth_service.clone()))
+        .app_data(web::Data::from(api_key_service.clone()))
+        .app_data(web::Data::from(tenant_service.clone()))
+        .wrap(IdempotencyKeyCheck::new(idempotent_body_limit))
+        .wrap(Authentication)
         .wrap(ServiceContextMaintenanceCheck)
+        .wrap(TenantResolution::new(tenant_domain))
+        .wrap(Logger::default())
+        .wrap(RequestContext)
+        .service(
+            web::scope("/auth")
+                .route("/register", web::post().to(register_handler))
+                .route("/login", web::post().to(login_handler))
+                .route("/refresh", web::post().to(refresh_token_handler))
+                .route("/me", web::get().to(current_user_handler))
+                .route("/api-keys", web::post().to(create_api_key_handler))
+                .route("/api-keys", web::get().to(list_api_keys_handler))
+                .route("/api-keys/{id}", web::delete().to(revoke_api_key_handler))
+        )
         .service(
             web::scope("/todos")
+                .wrap(Authorize::read_write(Permission::ReadTodos, Permission::WriteTodos))
                 .route("", web::post().to(create_todo_handler))
                 .route("", web::get().to(list_todos_handler))
+                .route("/bulk", web::post().to(bulk_todos_handler))
+                .route("/overdue", web::get().to(list_overdue_todos_handler))
+                .route("/search", web::get().to(search_todos_handler))
                 .route("/{id}", web::get().to(get_todo_handler))
+                .route("/{id}", web::patch().to(update_todo_handler))
                 .route("/{id}", web::delete().to(delete_todo_handler))
+                .route("/{id}/restore", web::post().to(restore_todo_handler))
+                .route("/{id}/complete", web::post().to(complete_todo_handler))
+                .route("/{id}/reopen", web::post().to(reopen_todo_handler))
+                .route("/{id}/move", web::post().to(move_todo_handler))
+                .route("/{id}/occurrences", web::get().to(list_occurrences_handler))
+                .route("/{id}/history", web::get().to(todo_history_handler))
+                .route("/{id}/tags", web::get().to(list_todo_tags_handler))
+                .route("/{id}/tags", web::post().to(attach_todo_tags_handler))
+                .route("/{id}/tags/{tag_id}", web::delete().to(detach_todo_tag_handler))
+                .route("/{id}/items", web::get().to(list_todo_items_handler))
+                .route("/{id}/items", web::post().to(create_todo_item_handler))
+                .route("/{id}/items/reorder", web::post().to(reorder_todo_items_handler))
+                .route("/{id}/items/{item_id}", web::patch().to(update_todo_item_handler))
+                .route("/{id}/items/{item_id}", web::delete().to(delete_todo_item_handler))
+                .route("/{id}/items/{item_id}/toggle", web::post().to(toggle_todo_item_handler))
+                .route("/{id}/comments", web::get().to(list_comments_handler))
+                .route("/{id}/comments", web::post().to(create_comment_handler))
+                .route("/{id}/comments/{comment_id}", web::get().to(get_comment_handler))
+                .route("/{id}/comments/{comment_id}", web::patch().to(update_comment_handler))
+                .route("/{id}/comments/{comment_id}", web::delete().to(delete_comment_handler))
+                .route("/{id}/attachments", web::get().to(list_attachments_handler))
+                .route("/{id}/attachments", web::post().to(upload_attachment_handler))
+                .route("/{id}/attachments/{attachment_id}", web::get().to(get_attachment_handler))
+                .route("/{id}/attachments/{attachment_id}", web::delete().to(delete_attachment_handler))
+                .route("/{id}/attachments/{attachment_id}/content", web::get().to(download_attachment_handler))
+        )
+        .service(
+            web::scope("/tags")
+                .wrap(Authorize::read_write(Permission::ReadTodos, Permission::WriteTodos))
+                .route("", web::post().to(create_tag_handler))
+                .route("", web::get().to(list_tags_handler))
+                .route("/{id}", web::get().to(get_tag_handler))
+                .route("/{id}", web::patch().to(update_tag_handler))
+                .route("/{id}", web::delete().to(delete_tag_handler))
+        )
+        .service(
+            web::scope("/lists")
+                .wrap(Authorize::read_write(Permission::ReadTodos, Permission::WriteTodos))
+                .route("", web::post().to(create_todo_list_handler))
+                .route("", web::get().to(list_todo_lists_handler))
+                .route("/accessible", web::get().to(list_accessible_todo_lists_handler))
+                .route("/{id}", web::get().to(get_todo_list_handler))
+                .route("/{id}", web::patch().to(update_todo_list_handler))
+                .route("/{id}", web::delete().to(delete_todo_list_handler))
+                .route("/{id}/archive", web::post().to(archive_todo_list_handler))
+                .route("/{id}/unarchive", web::post().to(unarchive_todo_list_handler))
+                .route("/{id}/todos", web::get().to(list_todo_list_todos_handler))
+                .route("/{id}/todos", web::post().to(create_todo_list_todo_handler))
+                .route("/{id}/shares", web::get().to(list_todo_list_shares_handler))
+                .route("/{id}/shares", web::post().to(share_todo_list_handler))
+                .route("/{id}/shares/{share_id}", web::delete().to(unshare_todo_list_handler))
+        )
+        .service(
+            web::scope("/admin")
+                .service(
+                    web::resource("/audit-events")
+                        .wrap(Authorize::new(Permission::ReadAuditLog))
+                        .route(web::get().to(list_audit_events_handler))
+                )
+                .service(
+                    web::resource("/service-context")
+                        .wrap(Authorize::new(Permission::ManageServiceContext))
+                        .route(web::get().to(get_service_context_handler))
+                        .route(web::patch().to(update_service_context_handler))
+                )
+                .service(
+                    web::resource("/users")
+                        .wrap(Authorize::new(Permission::ManageUsers))
+                        .route(web::get().to(list_users_handler))
+                )
+                .service(
+                    web::resource("/users/{id}/role")
+                        .wrap(Authorize::new(Permission::ManageUsers))
+                        .route(web::put().to(set_user_role_handler))
+                )
+                .service(
+                    web::resource("/users/{id}/groups")
+                        .wrap(Authorize::new(Permission::ManageUsers))
+                        .route(web::put().to(set_user_groups_handler))
+                )
+                .service(
+                    web::resource("/tenants")
+                        .wrap(Authorize::new(Permission::ManageTenants))
+                        .route(web::get().to(list_tenants_handler))
+                        .route(web::post().to(create_tenant_handler))
+                )
+                .service(
+                    web::resource("/tenants/{id}")
+                        .wrap(Authorize::new(Permission::ManageTenants))
+                        .route(web::patch().to(update_tenant_handler))
+                )
         )
 }
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use crate::container::Container;
+use crate::domain::constants::{
+    DEFAULT_IDEMPOTENCY_PURGE_SCHEDULE, DEFAULT_JOB_PURGE_SCHEDULE, DEFAULT_TODO_PURGE_SCHEDULE,
+    DEFAULT_TODO_REMINDERS_SCHEDULE, IDEMPOTENCY_PURGE_SCHEDULE, JOB_PURGE_SCHEDULE, TODO_PURGE_SCHEDULE,
+    TODO_REMINDERS_SCHEDULE,
+};
+use crate::jobs::{delete_blobs, purge_deleted_todos, purge_finished_jobs, purge_idempotency_keys, todo_reminders};
+use crate::jobs::delete_blobs::DeleteBlobsJob;
+use crate::jobs::purge_deleted_todos::{retention, PurgeDeletedTodosJob};
+use crate::jobs::purge_finished_jobs::PurgeFinishedJobsJob;
+use crate::jobs::purge_idempotency_keys::PurgeIdempotencyKeysJob;
+use crate::jobs::runner::{schedule_from_env, JobRunner, JobRunnerConfig};
+use crate::jobs::todo_reminders::TodoRemindersJob;
+
+pub fn create_job_runner(container: Arc<Container>) -> JobRunner {
+    JobRunner::new(container.job_service.clone(), JobRunnerConfig::from_env())
+        .register(todo_reminders::KIND, Arc::new(TodoRemindersJob {
+            todo_service: container.todo_service.clone(),
+            notifier: container.reminder_notifier.clone(),
+        }))
+        .register(purge_deleted_todos::KIND, Arc::new(PurgeDeletedTodosJob {
+            todo_service: container.todo_service.clone(),
+            retention: retention(),
+        }))
+        .register(delete_blobs::KIND, Arc::new(DeleteBlobsJob {
+            blob_store: container.blob_store.clone(),
+        }))
+        .register(purge_idempotency_keys::KIND, Arc::new(PurgeIdempotencyKeysJob {
+            idempotency_service: container.idempotency_service.clone(),
+        }))
+        .register(purge_finished_jobs::KIND, Arc::new(PurgeFinishedJobsJob {
+            job_service: container.job_service.clone(),
+        }))
+        .schedule(todo_reminders::KIND, schedule_from_env(TODO_REMINDERS_SCHEDULE, DEFAULT_TODO_REMINDERS_SCHEDULE))
+        .schedule(purge_deleted_todos::KIND, schedule_from_env(TODO_PURGE_SCHEDULE, DEFAULT_TODO_PURGE_SCHEDULE))
+        .schedule(purge_idempotency_keys::KIND, schedule_from_env(IDEMPOTENCY_PURGE_SCHEDULE, DEFAULT_IDEMPOTENCY_PURGE_SCHEDULE))
+        .schedule(purge_finished_jobs::KIND, schedule_from_env(JOB_PURGE_SCHEDULE, DEFAULT_JOB_PURGE_SCHEDULE))
+}
@@ -1 +1,101 @@
 pub const POSTGRESQL_DB_URI: &str = "DATABASE_URL";
+pub const TODO_RETENTION_DAYS: &str = "TODO_RETENTION_DAYS";
+pub const DEFAULT_TODO_RETENTION_DAYS: i64 = 30;
+pub const MAX_BULK_OPERATIONS: usize = 100;
+pub const IDEMPOTENCY_KEY_TTL_HOURS: &str = "IDEMPOTENCY_KEY_TTL_HOURS";
+pub const DEFAULT_IDEMPOTENCY_KEY_TTL_HOURS: i64 = 24;
+pub const JOB_WORKERS: &str = "JOB_WORKERS";
+pub const DEFAULT_JOB_WORKERS: usize = 2;
+pub const JOB_POLL_INTERVAL_MS: &str = "JOB_POLL_INTERVAL_MS";
+pub const DEFAULT_JOB_POLL_INTERVAL_MS: u64 = 1000;
+pub const JOB_LOCK_TIMEOUT_SECS: &str = "JOB_LOCK_TIMEOUT_SECS";
+pub const DEFAULT_JOB_LOCK_TIMEOUT_SECS: i64 = 300;
+pub const DEFAULT_JOB_MAX_ATTEMPTS: i32 = 5;
+pub const JOB_BACKOFF_BASE_SECS: i64 = 10;
+pub const JOB_BACKOFF_MAX_SECS: i64 = 60 * 60;
+pub const FINISHED_JOB_RETENTION_DAYS: i64 = 7;
+pub const DELETE_BLOBS_JOB: &str = "delete_blobs";
+pub const TODO_REMINDERS_SCHEDULE: &str = "TODO_REMINDERS_SCHEDULE";
+pub const DEFAULT_TODO_REMINDERS_SCHEDULE: &str = "0 * * * * *";
+pub const TODO_PURGE_SCHEDULE: &str = "TODO_PURGE_SCHEDULE";
+pub const DEFAULT_TODO_PURGE_SCHEDULE: &str = "0 0 * * * *";
+pub const IDEMPOTENCY_PURGE_SCHEDULE: &str = "IDEMPOTENCY_PURGE_SCHEDULE";
+pub const DEFAULT_IDEMPOTENCY_PURGE_SCHEDULE: &str = "0 */15 * * * *";
+pub const JOB_PURGE_SCHEDULE: &str = "JOB_PURGE_SCHEDULE";
+pub const DEFAULT_JOB_PURGE_SCHEDULE: &str = "0 30 3 * * *";
+pub const TODO_STATUS_TRANSITIONS: &str = "TODO_STATUS_TRANSITIONS";
+pub const TODO_AUTO_COMPLETE_ON_ITEMS: &str = "TODO_AUTO_COMPLETE_ON_ITEMS";
+pub const TODO_POSITION_GAP: i64 = 1024;
+pub const TODO_SEARCH_BACKEND: &str = "TODO_SEARCH_BACKEND";
+pub const DEFAULT_TODO_SEARCH_BACKEND: &str = "full_text";
+pub const SEARCH_HIGHLIGHT_START: &str = "<mark>";
+pub const SEARCH_HIGHLIGHT_END: &str = "</mark>";
+pub const MAX_SEARCH_QUERY_LENGTH: usize = 200;
+pub const MAX_COMMENT_LENGTH: usize = 10_000;
+pub const ATTACHMENT_STORAGE: &str = "ATTACHMENT_STORAGE";
+pub const DEFAULT_ATTACHMENT_STORAGE: &str = "local";
+pub const ATTACHMENT_STORAGE_PATH: &str = "ATTACHMENT_STORAGE_PATH";
+pub const DEFAULT_ATTACHMENT_STORAGE_PATH: &str = "./storage";
+pub const ATTACHMENT_MAX_SIZE_BYTES: &str = "ATTACHMENT_MAX_SIZE_BYTES";
+pub const DEFAULT_ATTACHMENT_MAX_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+// Room for the boundaries and part headers around the file of a multipart upload
+pub const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
+// Requests with an idempotency key are buffered, at least up to what the JSON extractor accepts
+pub const MIN_IDEMPOTENT_BODY_BYTES: usize = 2 * 1024 * 1024;
+// Comma separated, `type/*` allows every subtype
+pub const ATTACHMENT_CONTENT_TYPES: &str = "ATTACHMENT_CONTENT_TYPES";
+pub const DEFAULT_ATTACHMENT_CONTENT_TYPES: &str = "image/*,text/plain,text/csv,application/pdf,application/zip";
+pub const S3_ENDPOINT: &str = "S3_ENDPOINT";
+pub const S3_BUCKET: &str = "S3_BUCKET";
+pub const S3_REGION: &str = "S3_REGION";
+pub const DEFAULT_S3_REGION: &str = "us-east-1";
+pub const S3_ACCESS_KEY_ID: &str = "S3_ACCESS_KEY_ID";
+pub const S3_SECRET_ACCESS_KEY: &str = "S3_SECRET_ACCESS_KEY";
+pub const DEFAULT_OCCURRENCE_PREVIEW_LIMIT: usize = 5;
+pub const MAX_OCCURRENCE_PREVIEW_LIMIT: usize = 100;
+pub const JWT_SECRET: &str = "JWT_SECRET";
+pub const JWT_ACCESS_TOKEN_TTL_SECS: &str = "JWT_ACCESS_TOKEN_TTL_SECS";
+pub const DEFAULT_JWT_ACCESS_TOKEN_TTL_SECS: i64 = 15 * 60;
+pub const JWT_REFRESH_TOKEN_TTL_SECS: &str = "JWT_REFRESH_TOKEN_TTL_SECS";
+pub const DEFAULT_JWT_REFRESH_TOKEN_TTL_SECS: i64 = 30 * 24 * 60 * 60;
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+pub const MAX_PASSWORD_LENGTH: usize = 128;
+// Comma separated, admins of the admin tenant, the default tenant unless set. Their accounts are
+// seeded at startup with ADMIN_PASSWORD, or created on signing in with a verified external identity.
+pub const ADMIN_EMAILS: &str = "ADMIN_EMAILS";
+pub const ADMIN_PASSWORD: &str = "ADMIN_PASSWORD";
+pub const ADMIN_TENANT: &str = "ADMIN_TENANT";
+// The role of requests without credentials, `none` requires signing in
+pub const ANONYMOUS_ROLE: &str = "ANONYMOUS_ROLE";
+pub const DEFAULT_ANONYMOUS_ROLE: &str = "none";
+// Starts every API key, which tells them apart from access tokens
+pub const API_KEY_PREFIX: &str = "tk_";
+pub const MAX_API_KEY_NAME_LENGTH: usize = 100;
+// Saves a write on every request, the last use is only recorded once per interval
+pub const API_KEY_LAST_USED_INTERVAL_SECS: i64 = 60;
+// Accepting tokens of an external OpenID Connect provider needs its keys, from a URL or a file
+pub const OIDC_JWKS_URL: &str = "OIDC_JWKS_URL";
+pub const OIDC_JWKS_PATH: &str = "OIDC_JWKS_PATH";
+pub const OIDC_ISSUER: &str = "OIDC_ISSUER";
+pub const OIDC_AUDIENCE: &str = "OIDC_AUDIENCE";
+pub const OIDC_EMAIL_CLAIM: &str = "OIDC_EMAIL_CLAIM";
+pub const DEFAULT_OIDC_EMAIL_CLAIM: &str = "email";
+pub const OIDC_ROLES_CLAIM: &str = "OIDC_ROLES_CLAIM";
+pub const DEFAULT_OIDC_ROLES_CLAIM: &str = "roles";
+// Roles the provider asserts are ignored unless it is trusted to hand them out
+pub const OIDC_TRUST_ROLES: &str = "OIDC_TRUST_ROLES";
+// Names the user's tenant, when the provider puts one in its tokens
+pub const OIDC_TENANT_CLAIM: &str = "OIDC_TENANT_CLAIM";
+// The tenant of users whose tokens name none, the default tenant unless set
+pub const OIDC_TENANT: &str = "OIDC_TENANT";
+pub const OIDC_JWKS_CACHE_TTL_SECS: &str = "OIDC_JWKS_CACHE_TTL_SECS";
+pub const DEFAULT_OIDC_JWKS_CACHE_TTL_SECS: u64 = 60 * 60;
+pub const OIDC_JWKS_MIN_REFRESH_SECS: &str = "OIDC_JWKS_MIN_REFRESH_SECS";
+pub const DEFAULT_OIDC_JWKS_MIN_REFRESH_SECS: u64 = 30;
+// Requests that name no tenant, and data from before tenants existed, belong to this one
+pub const DEFAULT_TENANT: &str = "default";
+pub const MAX_TENANT_ID_LENGTH: usize = 63;
+// Requests to `<tenant>.<TENANT_DOMAIN>` belong to that tenant, unless a header names another
+pub const TENANT_DOMAIN: &str = "TENANT_DOMAIN";
+// Tags database connections with their tenant for the row-level security policies to check
+pub const TENANT_ROW_LEVEL_SECURITY: &str = "TENANT_ROW_LEVEL_SECURITY";
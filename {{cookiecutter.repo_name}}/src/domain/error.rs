@@ -1,5 +1,18 @@
+use actix_web::http::{header, StatusCode};
 use serde::Serialize;
 
+pub const REPOSITORY_ERROR_CODE: u32 = 1;
+pub const NOT_FOUND_ERROR_CODE: u32 = 2;
+pub const PRECONDITION_FAILED_ERROR_CODE: u32 = 3;
+pub const VALIDATION_ERROR_CODE: u32 = 4;
+pub const CONFLICT_ERROR_CODE: u32 = 5;
+pub const UNPROCESSABLE_ERROR_CODE: u32 = 6;
+pub const PAYLOAD_TOO_LARGE_ERROR_CODE: u32 = 7;
+pub const UNSUPPORTED_MEDIA_TYPE_ERROR_CODE: u32 = 8;
+pub const UNAUTHORIZED_ERROR_CODE: u32 = 9;
+pub const FORBIDDEN_ERROR_CODE: u32 = 10;
+pub const SERVICE_UNAVAILABLE_ERROR_CODE: u32 = 11;
+
 #[derive(Debug, Serialize)]
 pub struct CommonError {
     pub message: String,
@@ -12,6 +25,8 @@ impl std::fmt::Display for CommonError {
     }
 }
 
+impl std::error::Error for CommonError {}
+
 #[derive(Debug)]
 pub struct ApiError(CommonError);
 
@@ -28,21 +43,41 @@ impl std::fmt::Display for ApiError {
 }
 
 impl actix_web::ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self.0.code {
+            NOT_FOUND_ERROR_CODE => StatusCode::NOT_FOUND,
+            PRECONDITION_FAILED_ERROR_CODE => StatusCode::PRECONDITION_FAILED,
+            CONFLICT_ERROR_CODE => StatusCode::CONFLICT,
+            UNPROCESSABLE_ERROR_CODE => StatusCode::UNPROCESSABLE_ENTITY,
+            PAYLOAD_TOO_LARGE_ERROR_CODE => StatusCode::PAYLOAD_TOO_LARGE,
+            UNSUPPORTED_MEDIA_TYPE_ERROR_CODE => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            UNAUTHORIZED_ERROR_CODE => StatusCode::UNAUTHORIZED,
+            FORBIDDEN_ERROR_CODE => StatusCode::FORBIDDEN,
+            SERVICE_UNAVAILABLE_ERROR_CODE => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
     fn error_response(&self) -> actix_web::HttpResponse {
-        actix_web::HttpResponse::BadRequest().json(&self.0)
+        let mut response = actix_web::HttpResponse::build(self.status_code());
+        if self.0.code == UNAUTHORIZED_ERROR_CODE {
+            response.insert_header((header::WWW_AUTHENTICATE, "Bearer"));
+        }
+        response.json(&self.0)
     }
 }
 
 #[derive(Debug)]
 pub struct RepositoryError {
     pub message: String,
+    pub code: u32,
 }
 
 impl Into<CommonError> for RepositoryError {
     fn into(self) -> CommonError {
         CommonError {
             message: self.message,
-            code: 1,
+            code: self.code,
         }
     }
 }
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::models::authorization::Permission;
+
+// A key machine clients authenticate with on behalf of the user who created it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    // Identifies the key without revealing it, keys look like `tk_<prefix>_<secret>`
+    pub prefix: String,
+    pub scopes: Vec<Permission>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+// A key along with the hash it is checked against, only used to authenticate
+#[derive(Clone)]
+pub struct ApiKeyCredentials {
+    pub api_key: ApiKey,
+    pub key_hash: String,
+    // Keys are looked up across tenants, the key decides which one the request belongs to
+    pub tenant_id: String,
+}
+
+#[derive(Clone)]
+pub struct CreateApiKey {
+    pub name: String,
+    pub scopes: Vec<Permission>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+pub struct NewApiKey {
+    pub user_id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<Permission>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// A newly created key, the only time the key itself is available
+#[derive(Debug, Clone)]
+pub struct IssuedApiKey {
+    pub api_key: ApiKey,
+    pub key: String,
+}
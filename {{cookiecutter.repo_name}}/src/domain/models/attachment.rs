@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::services::blob_store::BlobStream;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: i32,
+    pub todo_id: i32,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub checksum: String,
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct CreateAttachment {
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub checksum: String,
+    pub storage_key: String,
+}
+
+pub struct UploadAttachment {
+    pub file_name: String,
+    pub content_type: String,
+    pub content: BlobStream,
+}
+
+// Inclusive byte range of a download, `None` downloads the whole file
+pub type ByteRange = Option<(u64, u64)>;
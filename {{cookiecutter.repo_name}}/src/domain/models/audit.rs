@@ -0,0 +1,125 @@
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+    Completed,
+    Reopened,
+    Moved,
+    Purged,
+    ItemAdded,
+    ItemUpdated,
+    ItemDeleted,
+    ItemsReordered,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Created => "created",
+            AuditAction::Updated => "updated",
+            AuditAction::Deleted => "deleted",
+            AuditAction::Restored => "restored",
+            AuditAction::Completed => "completed",
+            AuditAction::Reopened => "reopened",
+            AuditAction::Moved => "moved",
+            AuditAction::Purged => "purged",
+            AuditAction::ItemAdded => "item_added",
+            AuditAction::ItemUpdated => "item_updated",
+            AuditAction::ItemDeleted => "item_deleted",
+            AuditAction::ItemsReordered => "items_reordered",
+        }
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuditAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "created" => Ok(AuditAction::Created),
+            "updated" => Ok(AuditAction::Updated),
+            "deleted" => Ok(AuditAction::Deleted),
+            "restored" => Ok(AuditAction::Restored),
+            "completed" => Ok(AuditAction::Completed),
+            "reopened" => Ok(AuditAction::Reopened),
+            "moved" => Ok(AuditAction::Moved),
+            "purged" => Ok(AuditAction::Purged),
+            "item_added" => Ok(AuditAction::ItemAdded),
+            "item_updated" => Ok(AuditAction::ItemUpdated),
+            "item_deleted" => Ok(AuditAction::ItemDeleted),
+            "items_reordered" => Ok(AuditAction::ItemsReordered),
+            _ => Err(format!("Unknown audit action {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub todo_id: i32,
+    pub action: AuditAction,
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+    // Changed fields as `{"field": {"before": ..., "after": ...}}`
+    pub changes: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+// Diffs two snapshots of an audited entity, a missing snapshot counts as all fields being null
+pub fn audit_changes(before: Option<&Value>, after: Option<&Value>) -> Map<String, Value> {
+    let empty = Map::new();
+    let fields = |snapshot: Option<&Value>| snapshot.and_then(Value::as_object).unwrap_or(&empty).clone();
+    let (before, after) = (fields(before), fields(after));
+    let mut names = before.keys().chain(after.keys()).cloned().collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names.into_iter()
+        .filter_map(|name| {
+            let old = before.get(&name).cloned().unwrap_or(Value::Null);
+            let new = after.get(&name).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| (name, json!({ "before": old, "after": new })))
+        })
+        .collect()
+}
+
+// Who is making the current request, attached to the audit events it causes
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+}
+
+tokio::task_local! {
+    static AUDIT_CONTEXT: AuditContext;
+}
+
+impl AuditContext {
+    // The context of the running request, or an empty one for background work such as jobs
+    pub fn current() -> AuditContext {
+        AUDIT_CONTEXT.try_with(AuditContext::clone).unwrap_or_default()
+    }
+
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        AUDIT_CONTEXT.scope(self, future).await
+    }
+
+    pub fn sync_scope<F: FnOnce() -> R, R>(self, f: F) -> R {
+        AUDIT_CONTEXT.sync_scope(self, f)
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::models::authorization::Role;
+
+#[derive(Clone)]
+pub struct RegisterUser {
+    pub email: String,
+    pub name: Option<String>,
+    pub password: String,
+}
+
+#[derive(Clone)]
+pub struct Login {
+    pub email: String,
+    pub password: String,
+}
+
+// Access tokens authenticate requests, refresh tokens can only be traded for new tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenKind {
+    Access,
+    Refresh,
+}
+
+// What a signed token asserts about its bearer
+#[derive(Debug, Clone)]
+pub struct TokenClaims {
+    pub user_id: i32,
+    pub tenant: String,
+    pub kind: TokenKind,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    // When the access token expires
+    pub expires_at: DateTime<Utc>,
+}
+
+// Who a token of an external identity provider was issued to
+#[derive(Debug, Clone)]
+pub struct ExternalIdentity {
+    // Stable identifier of the user at the provider
+    pub subject: String,
+    pub email: String,
+    // Whether the provider vouches for the email, as stated by the `email_verified` claim
+    pub email_verified: bool,
+    pub name: Option<String>,
+    // Only set when the token carries a role claim and the provider is trusted with roles
+    pub role: Option<Role>,
+    // The tenant claim, else the tenant configured for the provider's users
+    pub tenant: String,
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::domain::error::{CommonError, FORBIDDEN_ERROR_CODE, UNAUTHORIZED_ERROR_CODE};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    #[default]
+    Member,
+    Viewer,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Member => "member",
+            Role::Viewer => "viewer",
+        }
+    }
+
+    // The policy table
+    pub fn permissions(&self) -> &'static [Permission] {
+        use Permission::*;
+        match self {
+            Role::Admin => &[ReadTodos, WriteTodos, ModifyAnyTodo, ReadAuditLog, ManageServiceContext, ManageUsers, ManageTenants],
+            Role::Member => &[ReadTodos, WriteTodos],
+            Role::Viewer => &[ReadTodos],
+        }
+    }
+
+    pub fn allows(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "admin" => Ok(Role::Admin),
+            "member" => Ok(Role::Member),
+            "viewer" => Ok(Role::Viewer),
+            _ => Err(format!("Unknown role {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    // Todos along with their tags, lists, items, comments and attachments
+    ReadTodos,
+    WriteTodos,
+    // Change todos owned by someone else
+    ModifyAnyTodo,
+    ReadAuditLog,
+    ManageServiceContext,
+    ManageUsers,
+    // Register tenants and choose who may make requests to them
+    ManageTenants,
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::ReadTodos => "read_todos",
+            Permission::WriteTodos => "write_todos",
+            Permission::ModifyAnyTodo => "modify_any_todo",
+            Permission::ReadAuditLog => "read_audit_log",
+            Permission::ManageServiceContext => "manage_service_context",
+            Permission::ManageUsers => "manage_users",
+            Permission::ManageTenants => "manage_tenants",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read_todos" => Ok(Permission::ReadTodos),
+            "write_todos" => Ok(Permission::WriteTodos),
+            "modify_any_todo" => Ok(Permission::ModifyAnyTodo),
+            "read_audit_log" => Ok(Permission::ReadAuditLog),
+            "manage_service_context" => Ok(Permission::ManageServiceContext),
+            "manage_users" => Ok(Permission::ManageUsers),
+            "manage_tenants" => Ok(Permission::ManageTenants),
+            _ => Err(format!("Unknown permission {}", s)),
+        }
+    }
+}
+
+// Who a request acts on behalf of. Anonymous requests have no user and the role configured
+// for them, if any. Requests made with an API key are further limited to the key's scopes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub user_id: Option<i32>,
+    pub role: Option<Role>,
+    pub scopes: Option<Vec<Permission>>,
+    // The groups of the user, which lists can be shared with
+    pub groups: Vec<String>,
+}
+
+tokio::task_local! {
+    static PRINCIPAL: Principal;
+}
+
+impl Principal {
+    pub fn user(user_id: i32, role: Role) -> Self {
+        Principal { user_id: Some(user_id), role: Some(role), scopes: None, groups: Vec::new() }
+    }
+
+    pub fn anonymous(role: Option<Role>) -> Self {
+        Principal { user_id: None, role, scopes: None, groups: Vec::new() }
+    }
+
+    // Work outside of requests, such as jobs, is not restricted, but needs to run within its scope
+    pub fn system() -> Self {
+        Principal { user_id: None, role: Some(Role::Admin), scopes: None, groups: Vec::new() }
+    }
+
+    // Limits the principal to the given permissions, on top of what its role allows
+    pub fn scoped(self, scopes: Vec<Permission>) -> Self {
+        Principal { scopes: Some(scopes), ..self }
+    }
+
+    pub fn in_groups(self, groups: Vec<String>) -> Self {
+        Principal { groups, ..self }
+    }
+
+    // The principal of the running request. Outside of any scope nothing is allowed, so code
+    // that misses it fails closed.
+    pub fn current() -> Principal {
+        PRINCIPAL.try_with(Principal::clone).unwrap_or_else(|_| Principal::anonymous(None))
+    }
+
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        PRINCIPAL.scope(self, future).await
+    }
+
+    pub fn can(&self, permission: Permission) -> bool {
+        self.role.is_some_and(|role| role.allows(permission))
+            && self.scopes.as_ref().is_none_or(|scopes| scopes.contains(&permission))
+    }
+
+    // Admins may change any todo, everyone else who may write only their own and those nobody owns
+    pub fn can_modify(&self, owner_id: Option<i32>) -> bool {
+        self.can(Permission::ModifyAnyTodo)
+            || (self.can(Permission::WriteTodos) && (owner_id.is_none() || owner_id == self.user_id))
+    }
+
+    pub fn authorize(&self, permission: Permission) -> Result<(), CommonError> {
+        if self.can(permission) {
+            return Ok(());
+        }
+        Err(self.denied())
+    }
+
+    pub fn authorize_modify(&self, owner_id: Option<i32>) -> Result<(), CommonError> {
+        if self.can_modify(owner_id) {
+            return Ok(());
+        }
+        Err(self.denied())
+    }
+
+    // Anonymous requests without any rights are asked to sign in, everyone else is forbidden
+    pub fn denied(&self) -> CommonError {
+        if self.user_id.is_none() && self.role.is_none() {
+            return CommonError {
+                message: "Authentication required".to_string(),
+                code: UNAUTHORIZED_ERROR_CODE,
+            };
+        }
+        CommonError {
+            message: "You are not allowed to do this".to_string(),
+            code: FORBIDDEN_ERROR_CODE,
+        }
+    }
+}
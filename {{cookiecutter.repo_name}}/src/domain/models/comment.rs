@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: i32,
+    pub todo_id: i32,
+    pub author: Option<String>,
+    // The user who wrote the comment, none for anonymous ones
+    pub author_id: Option<i32>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct CreateComment {
+    pub author: Option<String>,
+    pub author_id: Option<i32>,
+    pub body: String,
+}
+
+#[derive(Clone)]
+pub struct UpdateComment {
+    pub body: String,
+}
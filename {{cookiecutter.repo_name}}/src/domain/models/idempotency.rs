@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Clone)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    // Names and values of the headers replayed along with the body
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Clone)]
+pub struct IdempotencyKey {
+    pub key: String,
+    pub request_fingerprint: String,
+    // `None` while the original request is still being processed
+    pub response: Option<StoredResponse>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
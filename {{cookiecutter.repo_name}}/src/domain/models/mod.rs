@@ -1,2 +1,17 @@
 pub mod todo;
 pub mod service_context;
+pub mod idempotency;
+pub mod job;
+pub mod tag;
+pub mod todo_list;
+pub mod todo_item;
+pub mod comment;
+pub mod attachment;
+pub mod audit;
+pub mod recurrence;
+pub mod user;
+pub mod auth;
+pub mod authorization;
+pub mod api_key;
+pub mod tenant;
+pub mod share;
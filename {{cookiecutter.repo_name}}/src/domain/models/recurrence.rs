@@ -0,0 +1,377 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+// Periods looked at before giving up on a rule that never (or no longer) produces an occurrence
+const MAX_RECURRENCE_PERIODS: u32 = 100_000;
+
+fn default_interval() -> u32 {
+    1
+}
+
+// How a todo repeats. The series starts at the todo's due date and occurrences keep
+// its time of day, all in UTC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "frequency", rename_all = "snake_case")]
+pub enum Recurrence {
+    Daily {
+        #[serde(default = "default_interval")]
+        interval: u32,
+    },
+    Weekly {
+        #[serde(default = "default_interval")]
+        interval: u32,
+        // Defaults to the weekday of the due date
+        #[serde(default)]
+        weekdays: Vec<Weekday>,
+    },
+    Monthly {
+        #[serde(default = "default_interval")]
+        interval: u32,
+        // Defaults to the day of the due date, negative days count from the end of the month
+        day_of_month: Option<i32>,
+    },
+    // An RFC 5545 recurrence rule such as `FREQ=MONTHLY;BYDAY=-1FR;COUNT=6`
+    Rrule { rule: String },
+}
+
+impl Recurrence {
+    pub fn rule(&self) -> Result<RecurrenceRule, String> {
+        let rule = match self {
+            Recurrence::Daily { interval } => RecurrenceRule::new(Frequency::Daily, *interval),
+            Recurrence::Weekly { interval, weekdays } => RecurrenceRule {
+                by_day: weekdays.iter().map(|weekday| (None, *weekday)).collect(),
+                ..RecurrenceRule::new(Frequency::Weekly, *interval)
+            },
+            Recurrence::Monthly { interval, day_of_month } => RecurrenceRule {
+                by_month_day: day_of_month.iter().copied().collect(),
+                ..RecurrenceRule::new(Frequency::Monthly, *interval)
+            },
+            Recurrence::Rrule { rule } => rule.parse()?,
+        };
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    // The due date of the occurrence following the one due at `due_at`, along with the
+    // recurrence it carries on with, or `None` once the series has ended
+    pub fn next(&self, due_at: DateTime<Utc>) -> Result<Option<(DateTime<Utc>, Recurrence)>, String> {
+        let rule = self.rule()?;
+        let next_due_at = match rule.occurrences_after(due_at).next() {
+            Some(next_due_at) => next_due_at,
+            None => return Ok(None),
+        };
+        let recurrence = match (self, rule.count) {
+            // The occurrence due at `due_at` used up one of the remaining occurrences
+            (Recurrence::Rrule { .. }, Some(count)) => Recurrence::Rrule {
+                rule: RecurrenceRule { count: Some(count - 1), ..rule }.to_string(),
+            },
+            _ => self.clone(),
+        };
+        Ok(Some((next_due_at, recurrence)))
+    }
+
+    // The due dates of the occurrences following the one due at `due_at`
+    pub fn preview(&self, due_at: DateTime<Utc>, limit: usize) -> Result<Vec<DateTime<Utc>>, String> {
+        Ok(self.rule()?.occurrences_after(due_at).take(limit).collect())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+impl FromStr for Frequency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "DAILY" => Ok(Frequency::Daily),
+            "WEEKLY" => Ok(Frequency::Weekly),
+            "MONTHLY" => Ok(Frequency::Monthly),
+            "YEARLY" => Ok(Frequency::Yearly),
+            _ => Err(format!("Unsupported recurrence frequency {}", s)),
+        }
+    }
+}
+
+// The supported subset of RFC 5545 recurrence rules: FREQ (DAILY to YEARLY), INTERVAL,
+// BYDAY, BYMONTHDAY, BYMONTH, COUNT and UNTIL, with weeks starting on Monday. Ordinal
+// weekdays such as `2TU` count within the month.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+    // Occurrences in the series, counting the one the series starts with
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl RecurrenceRule {
+    pub fn new(frequency: Frequency, interval: u32) -> Self {
+        RecurrenceRule {
+            frequency,
+            interval,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            count: None,
+            until: None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval == 0 {
+            return Err("Recurrence interval must be at least 1".to_string());
+        }
+        if self.count == Some(0) {
+            return Err("Recurrence count must be at least 1".to_string());
+        }
+        if self.count.is_some() && self.until.is_some() {
+            return Err("Recurrence cannot have both a count and an end date".to_string());
+        }
+        if let Some(day) = self.by_month_day.iter().find(|day| **day == 0 || day.abs() > 31) {
+            return Err(format!("Invalid day of month {}", day));
+        }
+        if let Some(month) = self.by_month.iter().find(|month| !(1..=12).contains(*month)) {
+            return Err(format!("Invalid month {}", month));
+        }
+        for (ordinal, _) in &self.by_day {
+            match (ordinal, self.frequency) {
+                (None, _) => {}
+                (Some(ordinal), Frequency::Monthly | Frequency::Yearly) if *ordinal != 0 && ordinal.abs() <= 5 => {}
+                (Some(ordinal), Frequency::Monthly | Frequency::Yearly) => {
+                    return Err(format!("Invalid weekday ordinal {}", ordinal));
+                }
+                (Some(_), _) => {
+                    return Err("Weekday ordinals are only supported for monthly and yearly recurrences".to_string());
+                }
+            }
+        }
+        if self.frequency == Frequency::Yearly && !self.by_day.is_empty() && self.by_month.is_empty() {
+            return Err("Yearly recurrences by weekday need the months they apply to".to_string());
+        }
+        Ok(())
+    }
+
+    // Occurrences strictly after `start`, which is the first occurrence of the series
+    pub fn occurrences_after(&self, start: DateTime<Utc>) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        let time = start.time();
+        let remaining = self.count.map(|count| count as usize - 1).unwrap_or(usize::MAX);
+        (0..MAX_RECURRENCE_PERIODS)
+            .map_while(move |period| self.period_dates(start.date_naive(), period))
+            .flatten()
+            .map(move |date| Utc.from_utc_datetime(&date.and_time(time)))
+            .skip_while(move |occurrence| *occurrence <= start)
+            .take_while(move |occurrence| self.until.is_none_or(|until| *occurrence <= until))
+            .take(remaining)
+    }
+
+    // The candidate dates of the `period`th day, week, month or year of the series, in order,
+    // or `None` past the supported range of dates
+    fn period_dates(&self, start: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = period.checked_mul(self.interval)?;
+        let mut dates = match self.frequency {
+            Frequency::Daily => {
+                let date = start.checked_add_signed(Duration::days(step as i64))?;
+                let matches_day = self.by_month_day.is_empty() || self.by_month_day.iter()
+                    .any(|day| month_day(date.year(), date.month(), *day) == Some(date));
+                let matches_weekday = self.by_day.is_empty() || self.by_day.iter()
+                    .any(|(_, weekday)| *weekday == date.weekday());
+                if matches_day && matches_weekday { vec![date] } else { Vec::new() }
+            }
+            Frequency::Weekly => {
+                let monday = start.checked_sub_signed(Duration::days(start.weekday().num_days_from_monday() as i64))?
+                    .checked_add_signed(Duration::weeks(step as i64))?;
+                let mut weekdays = self.by_day.iter().map(|(_, weekday)| *weekday).collect::<Vec<_>>();
+                if weekdays.is_empty() {
+                    weekdays.push(start.weekday());
+                }
+                weekdays.iter()
+                    .map(|weekday| monday + Duration::days(weekday.num_days_from_monday() as i64))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let months = start.month0() as i64 + step as i64;
+                let year = start.year().checked_add(i32::try_from(months / 12).ok()?)?;
+                self.month_dates(year, (months % 12) as u32 + 1, start.day())
+            }
+            Frequency::Yearly => {
+                let year = start.year().checked_add(i32::try_from(step).ok()?)?;
+                let months = if self.by_month.is_empty() { vec![start.month()] } else { self.by_month.clone() };
+                months.into_iter().flat_map(|month| self.month_dates(year, month, start.day())).collect()
+            }
+        };
+        if !self.by_month.is_empty() {
+            dates.retain(|date| self.by_month.contains(&date.month()));
+        }
+        dates.sort_unstable();
+        dates.dedup();
+        Some(dates)
+    }
+
+    // Days of a month matching BYMONTHDAY and BYDAY, or `default_day` when neither is given
+    fn month_dates(&self, year: i32, month: u32, default_day: u32) -> Vec<NaiveDate> {
+        if self.by_month_day.is_empty() && self.by_day.is_empty() {
+            return NaiveDate::from_ymd_opt(year, month, default_day).into_iter().collect();
+        }
+        let days = if self.by_month_day.is_empty() {
+            (1..=days_in_month(year, month)).filter_map(|day| NaiveDate::from_ymd_opt(year, month, day)).collect::<Vec<_>>()
+        } else {
+            self.by_month_day.iter().filter_map(|day| month_day(year, month, *day)).collect()
+        };
+        if self.by_day.is_empty() {
+            return days;
+        }
+        let last_day = days_in_month(year, month);
+        days.into_iter()
+            .filter(|date| self.by_day.iter().any(|(ordinal, weekday)| {
+                *weekday == date.weekday() && match ordinal {
+                    None => true,
+                    Some(ordinal) if *ordinal > 0 => (date.day() as i32 - 1) / 7 + 1 == *ordinal,
+                    Some(ordinal) => (last_day as i32 - date.day() as i32) / 7 + 1 == -ordinal,
+                }
+            }))
+            .collect()
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|first| first.pred_opt())
+        .map_or(28, |last| last.day())
+}
+
+// Resolves a BYMONTHDAY value, negative days counting back from the last day of the month
+fn month_day(year: i32, month: u32, day: i32) -> Option<NaiveDate> {
+    let day = if day < 0 { days_in_month(year, month) as i32 + day + 1 } else { day };
+    u32::try_from(day).ok().and_then(|day| NaiveDate::from_ymd_opt(year, month, day))
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_weekday(s: &str) -> Result<(Option<i32>, Weekday), String> {
+    let split = s.len().checked_sub(2).filter(|split| s.is_char_boundary(*split))
+        .ok_or_else(|| format!("Invalid weekday {}", s))?;
+    let (ordinal, code) = s.split_at(split);
+    let weekday = match code {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return Err(format!("Invalid weekday {}", s)),
+    };
+    let ordinal = match ordinal {
+        "" => None,
+        ordinal => Some(ordinal.parse::<i32>().map_err(|_| format!("Invalid weekday {}", s))?),
+    };
+    Ok((ordinal, weekday))
+}
+
+// UNTIL is either a UTC date-time or a date, which includes the whole day
+fn parse_until(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Some(date_time) = s.strip_suffix('Z') {
+        return chrono::NaiveDateTime::parse_from_str(date_time, "%Y%m%dT%H%M%S")
+            .map(|until| Utc.from_utc_datetime(&until))
+            .map_err(|_| format!("Invalid end date {}", s));
+    }
+    NaiveDate::parse_from_str(s, "%Y%m%d")
+        .map(|until| Utc.from_utc_datetime(&until.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default())))
+        .map_err(|_| format!("Invalid end date {}, expected YYYYMMDD or YYYYMMDDTHHMMSSZ", s))
+}
+
+fn parse_list<T, F: Fn(&str) -> Result<T, String>>(value: &str, parse: F) -> Result<Vec<T>, String> {
+    value.split(',').map(|item| parse(item.trim())).collect()
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix("RRULE:").unwrap_or(s);
+        let mut frequency = None;
+        let mut rule = RecurrenceRule::new(Frequency::Daily, 1);
+        for part in s.split(';').filter(|part| !part.trim().is_empty()) {
+            let (name, value) = part.split_once('=')
+                .ok_or_else(|| format!("Invalid recurrence rule part {}", part))?;
+            let value = value.trim();
+            match name.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => frequency = Some(value.to_ascii_uppercase().parse::<Frequency>()?),
+                "INTERVAL" => rule.interval = value.parse().map_err(|_| format!("Invalid interval {}", value))?,
+                "COUNT" => rule.count = Some(value.parse().map_err(|_| format!("Invalid count {}", value))?),
+                "UNTIL" => rule.until = Some(parse_until(value)?),
+                "BYDAY" => rule.by_day = parse_list(&value.to_ascii_uppercase(), parse_weekday)?,
+                "BYMONTHDAY" => rule.by_month_day = parse_list(value, |day| day.parse().map_err(|_| format!("Invalid day of month {}", day)))?,
+                "BYMONTH" => rule.by_month = parse_list(value, |month| month.parse().map_err(|_| format!("Invalid month {}", month)))?,
+                "WKST" if value.eq_ignore_ascii_case("MO") => {}
+                name => return Err(format!("Unsupported recurrence rule part {}", name)),
+            }
+        }
+        rule.frequency = frequency.ok_or_else(|| "Recurrence rule needs a FREQ".to_string())?;
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FREQ={}", self.frequency.as_str())?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days = self.by_day.iter()
+                .map(|(ordinal, weekday)| format!("{}{}", ordinal.map(|o| o.to_string()).unwrap_or_default(), weekday_code(*weekday)))
+                .collect::<Vec<_>>();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days = self.by_month_day.iter().map(i32::to_string).collect::<Vec<_>>();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if !self.by_month.is_empty() {
+            let months = self.by_month.iter().map(u32::to_string).collect::<Vec<_>>();
+            write!(f, ";BYMONTH={}", months.join(","))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 use serde::Deserialize;
 
+// Contexts either belong to a tenant or, without one, are global and apply to all tenants
 #[derive(Clone, Deserialize)]
 pub struct ServiceContext {
     pub id: i32,
+    pub tenant_id: Option<String>,
     pub maintenance: bool,
 }
@@ -0,0 +1,105 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::error::CommonError;
+use crate::domain::models::authorization::{Permission, Principal};
+use crate::domain::models::todo_list::TodoList;
+
+// What a share allows on a list and its todos, on top of what the role allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SharePermission {
+    Read,
+    Write,
+}
+
+impl SharePermission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SharePermission::Read => "read",
+            SharePermission::Write => "write",
+        }
+    }
+}
+
+impl fmt::Display for SharePermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SharePermission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(SharePermission::Read),
+            "write" => Ok(SharePermission::Write),
+            _ => Err(format!("Unknown share permission {}", s)),
+        }
+    }
+}
+
+// Grants either one user or every member of a group access to a list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoListShare {
+    pub id: i32,
+    pub list_id: i32,
+    pub user_id: Option<i32>,
+    pub group: Option<String>,
+    pub permission: SharePermission,
+    pub created_at: DateTime<Utc>,
+}
+
+// Sharing a list again with the same user or group changes the permission
+#[derive(Clone)]
+pub struct CreateTodoListShare {
+    pub user_id: Option<i32>,
+    pub group: Option<String>,
+    pub permission: SharePermission,
+}
+
+// How the current principal may access a list. Lists nobody owns are open to everyone, as
+// todos nobody owns are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListAccess {
+    pub owner_id: Option<i32>,
+    // The strongest share granted to the principal or one of their groups
+    pub shared: Option<SharePermission>,
+}
+
+impl ListAccess {
+    // Renaming, deleting and sharing the list is up to its owner (and admins)
+    pub fn can_manage(&self, principal: &Principal) -> bool {
+        principal.can_modify(self.owner_id)
+    }
+
+    // Changing the list's todos, including those owned by someone else
+    pub fn can_write(&self, principal: &Principal) -> bool {
+        self.can_manage(principal)
+            || (principal.can(Permission::WriteTodos) && self.shared == Some(SharePermission::Write))
+    }
+
+    pub fn authorize_manage(&self, principal: &Principal) -> Result<(), CommonError> {
+        if self.can_manage(principal) {
+            return Ok(());
+        }
+        Err(principal.denied())
+    }
+
+    pub fn authorize_write(&self, principal: &Principal) -> Result<(), CommonError> {
+        if self.can_write(principal) {
+            return Ok(());
+        }
+        Err(principal.denied())
+    }
+}
+
+// A list the current principal owns or that is shared with them, `shared` being `None` for
+// their own lists
+#[derive(Debug, Clone)]
+pub struct AccessibleTodoList {
+    pub list: TodoList,
+    pub shared: Option<SharePermission>,
+}
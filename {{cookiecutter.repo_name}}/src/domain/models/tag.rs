@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: i32,
+    pub name: String,
+    pub color: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct CreateTag {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+// `None` leaves a field untouched, `Some(None)` clears a nullable field
+#[derive(Clone)]
+pub struct UpdateTag {
+    pub name: Option<String>,
+    pub color: Option<Option<String>>,
+}
+
+impl UpdateTag {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.color.is_none()
+    }
+}
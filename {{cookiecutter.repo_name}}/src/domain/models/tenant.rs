@@ -0,0 +1,103 @@
+use std::fmt;
+use std::future::Future;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::constants::{DEFAULT_TENANT, MAX_TENANT_ID_LENGTH};
+use crate::domain::error::{CommonError, VALIDATION_ERROR_CODE};
+
+// The team whose data a request works with. Requests run within their tenant, which
+// repositories scope every query to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tenant {
+    pub id: String,
+}
+
+tokio::task_local! {
+    static TENANT: Tenant;
+}
+
+impl Default for Tenant {
+    fn default() -> Self {
+        Tenant::new(DEFAULT_TENANT)
+    }
+}
+
+impl Tenant {
+    pub fn new(id: impl Into<String>) -> Self {
+        Tenant { id: id.into() }
+    }
+
+    // Tenant ids double as subdomains, so they follow the rules of DNS labels
+    pub fn parse(id: &str) -> Result<Tenant, CommonError> {
+        let id = id.trim().to_ascii_lowercase();
+        let valid = !id.is_empty()
+            && id.len() <= MAX_TENANT_ID_LENGTH
+            && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && !id.starts_with('-')
+            && !id.ends_with('-');
+        if !valid {
+            return Err(CommonError {
+                message: format!("Invalid tenant {}", id),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(Tenant { id })
+    }
+
+    // The tenant of the running request, or none outside of requests, such as in jobs, whose
+    // work spans all tenants
+    pub fn current() -> Option<Tenant> {
+        TENANT.try_with(Tenant::clone).ok()
+    }
+
+    // What queries are scoped to, the default tenant outside of requests
+    pub fn current_id() -> String {
+        Tenant::current().unwrap_or_default().id
+    }
+
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        TENANT.scope(self, future).await
+    }
+
+    pub fn sync_scope<F: FnOnce() -> R, R>(self, f: F) -> R {
+        TENANT.sync_scope(self, f)
+    }
+}
+
+impl fmt::Display for Tenant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.id)
+    }
+}
+
+// A tenant requests can be made to, as kept in the registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredTenant {
+    pub id: String,
+    // Whether requests naming the tenant may come without credentials
+    pub allow_anonymous: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RegisteredTenant {
+    pub fn tenant(&self) -> Tenant {
+        Tenant::new(self.id.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct CreateTenant {
+    pub id: String,
+    pub allow_anonymous: bool,
+}
+
+#[derive(Clone)]
+pub struct UpdateTenant {
+    pub allow_anonymous: Option<bool>,
+}
+
+impl UpdateTenant {
+    pub fn is_empty(&self) -> bool {
+        self.allow_anonymous.is_none()
+    }
+}
@@ -1,4 +1,141 @@
-use serde::Deserialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::models::recurrence::Recurrence;
+use crate::domain::models::tag::Tag;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoStatus {
+    Backlog,
+    #[default]
+    Todo,
+    InProgress,
+    Blocked,
+    Done,
+}
+
+impl TodoStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TodoStatus::Backlog => "backlog",
+            TodoStatus::Todo => "todo",
+            TodoStatus::InProgress => "in_progress",
+            TodoStatus::Blocked => "blocked",
+            TodoStatus::Done => "done",
+        }
+    }
+}
+
+impl fmt::Display for TodoStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TodoStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "backlog" => Ok(TodoStatus::Backlog),
+            "todo" => Ok(TodoStatus::Todo),
+            "in_progress" => Ok(TodoStatus::InProgress),
+            "blocked" => Ok(TodoStatus::Blocked),
+            "done" => Ok(TodoStatus::Done),
+            _ => Err(format!("Unknown todo status {}", s)),
+        }
+    }
+}
+
+// Declared from lowest to highest, which is also how they sort
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoPriority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
+impl TodoPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TodoPriority::Low => "low",
+            TodoPriority::Medium => "medium",
+            TodoPriority::High => "high",
+            TodoPriority::Urgent => "urgent",
+        }
+    }
+}
+
+impl FromStr for TodoPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(TodoPriority::Low),
+            "medium" => Ok(TodoPriority::Medium),
+            "high" => Ok(TodoPriority::High),
+            "urgent" => Ok(TodoPriority::Urgent),
+            _ => Err(format!("Unknown todo priority {}", s)),
+        }
+    }
+}
+
+// Allowed status transitions. Staying in the same status is always allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoWorkflow {
+    transitions: HashSet<(TodoStatus, TodoStatus)>,
+}
+
+impl TodoWorkflow {
+    pub fn new(transitions: impl IntoIterator<Item = (TodoStatus, TodoStatus)>) -> Self {
+        TodoWorkflow { transitions: transitions.into_iter().collect() }
+    }
+
+    pub fn allows(&self, from: TodoStatus, to: TodoStatus) -> bool {
+        from == to || self.transitions.contains(&(from, to))
+    }
+}
+
+impl Default for TodoWorkflow {
+    fn default() -> Self {
+        use TodoStatus::*;
+        TodoWorkflow::new([
+            (Backlog, Todo),
+            (Backlog, InProgress),
+            (Todo, Backlog),
+            (Todo, InProgress),
+            (Todo, Done),
+            (InProgress, Todo),
+            (InProgress, Blocked),
+            (InProgress, Done),
+            (Blocked, Todo),
+            (Blocked, InProgress),
+            (Done, Todo),
+        ])
+    }
+}
+
+// Parses a comma separated list of `from>to` pairs, e.g. `backlog>todo,todo>done,done>todo`
+impl FromStr for TodoWorkflow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|transition| {
+                let (from, to) = transition.trim().split_once('>')
+                    .ok_or_else(|| format!("Invalid status transition {}", transition))?;
+                Ok((from.trim().parse()?, to.trim().parse()?))
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map(TodoWorkflow::new)
+    }
+}
 
 #[derive(Clone, Deserialize)]
 pub struct Todo {
@@ -6,10 +143,131 @@ pub struct Todo {
     pub title: String,
     pub description: String,
     pub completed: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub version: i32,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub completed_by: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub remind_at: Option<DateTime<Utc>>,
+    pub status: TodoStatus,
+    pub priority: TodoPriority,
+    pub list_id: Option<i32>,
+    // Manual sort order, only meaningful relative to other todos
+    pub position: i64,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    // The user who created the todo, if it was created by a signed in user
+    #[serde(default)]
+    pub owner_id: Option<i32>,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    #[serde(default)]
+    pub done_items: i64,
+    #[serde(default)]
+    pub total_items: i64,
+    #[serde(default)]
+    pub comment_count: i64,
+}
+
+impl Todo {
+    // The next occurrence of a recurring todo that has just been completed, or `None` once the
+    // series has ended. It starts afresh, and its reminder keeps the same distance to the due date.
+    pub fn next_occurrence(&self) -> Result<Option<CreateTodo>, String> {
+        let (recurrence, due_at) = match (&self.recurrence, self.due_at) {
+            (Some(recurrence), Some(due_at)) => (recurrence, due_at),
+            _ => return Ok(None),
+        };
+        Ok(recurrence.next(due_at)?.map(|(next_due_at, next_recurrence)| CreateTodo {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            due_at: Some(next_due_at),
+            remind_at: self.remind_at.map(|remind_at| next_due_at - (due_at - remind_at)),
+            status: TodoStatus::default(),
+            priority: self.priority,
+            list_id: self.list_id,
+            recurrence: Some(next_recurrence),
+            owner_id: self.owner_id,
+        }))
+    }
 }
 
 #[derive(Clone)]
 pub struct CreateTodo {
     pub title: String,
     pub description: String,
+    pub due_at: Option<DateTime<Utc>>,
+    pub remind_at: Option<DateTime<Utc>>,
+    pub status: TodoStatus,
+    pub priority: TodoPriority,
+    pub list_id: Option<i32>,
+    pub recurrence: Option<Recurrence>,
+    pub owner_id: Option<i32>,
+}
+
+// Who may change a todo depends on its owner and, for todos of shared lists, the list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TodoOwnership {
+    pub owner_id: Option<i32>,
+    pub list_id: Option<i32>,
+}
+
+// `None` leaves a field untouched, `Some(None)` clears a nullable field
+#[derive(Clone)]
+pub struct UpdateTodo {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    pub remind_at: Option<Option<DateTime<Utc>>>,
+    pub status: Option<TodoStatus>,
+    pub priority: Option<TodoPriority>,
+    // Moves the todo to another list, or out of its list with `Some(None)`
+    pub list_id: Option<Option<i32>>,
+    pub recurrence: Option<Option<Recurrence>>,
+}
+
+impl UpdateTodo {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.description.is_none() && self.due_at.is_none() && self.remind_at.is_none()
+            && self.status.is_none() && self.priority.is_none() && self.list_id.is_none() && self.recurrence.is_none()
+    }
+}
+
+// `snippet` is an excerpt of the matching text with the matched terms wrapped in
+// `SEARCH_HIGHLIGHT_START` and `SEARCH_HIGHLIGHT_END`
+#[derive(Clone)]
+pub struct TodoSearchHit {
+    pub todo: Todo,
+    pub rank: f32,
+    pub snippet: String,
+}
+
+// Exactly one of `before` and `after` names the todo to place the moved todo next to
+#[derive(Clone)]
+pub struct MoveTodo {
+    pub before: Option<i32>,
+    pub after: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TodoPlacement {
+    Before(i32),
+    After(i32),
+}
+
+impl TodoPlacement {
+    pub fn anchor_id(&self) -> i32 {
+        match self {
+            TodoPlacement::Before(anchor_id) | TodoPlacement::After(anchor_id) => *anchor_id,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum TodoOperation {
+    Create(CreateTodo),
+    Update { todo_id: i32, todo: UpdateTodo, expected_version: Option<i32> },
+    Delete { todo_id: i32, expected_version: Option<i32> },
+    Complete { todo_id: i32, completed_by: Option<String> },
 }
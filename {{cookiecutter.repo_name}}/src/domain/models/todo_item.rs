@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoItem {
+    pub id: i32,
+    pub todo_id: i32,
+    pub title: String,
+    pub position: i32,
+    pub done: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Without a position the item is appended, otherwise it is inserted before the item at `position`
+#[derive(Clone)]
+pub struct CreateTodoItem {
+    pub title: String,
+    pub position: Option<i32>,
+}
+
+#[derive(Clone)]
+pub struct UpdateTodoItem {
+    pub title: Option<String>,
+    pub done: Option<bool>,
+}
+
+impl UpdateTodoItem {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.done.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TodoProgress {
+    pub done_items: i64,
+    pub total_items: i64,
+}
+
+impl TodoProgress {
+    // Todos without items are never considered finished by their checklist
+    pub fn is_finished(&self) -> bool {
+        self.total_items > 0 && self.done_items == self.total_items
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoList {
+    pub id: i32,
+    pub name: String,
+    pub description: String,
+    pub owner: Option<String>,
+    // The user who created the list, lists nobody owns are visible to everyone
+    pub owner_id: Option<i32>,
+    pub archived: bool,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct CreateTodoList {
+    pub name: String,
+    pub description: String,
+    pub owner: Option<String>,
+    pub owner_id: Option<i32>,
+}
+
+// `None` leaves a field untouched, `Some(None)` clears a nullable field
+#[derive(Clone)]
+pub struct UpdateTodoList {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub owner: Option<Option<String>>,
+}
+
+impl UpdateTodoList {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.description.is_none() && self.owner.is_none()
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::models::authorization::Role;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: i32,
+    pub email: String,
+    pub name: Option<String>,
+    pub role: Role,
+    pub tenant_id: String,
+    // Lists can be shared with every member of a group
+    pub groups: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// A user along with the hash their password is checked against, only used to sign in.
+// Users of an external identity provider have no password.
+#[derive(Clone)]
+pub struct UserCredentials {
+    pub user: User,
+    pub password_hash: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct CreateUser {
+    pub email: String,
+    pub name: Option<String>,
+    pub password_hash: Option<String>,
+    pub role: Role,
+    pub external_id: Option<String>,
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::models::api_key::{ApiKey, ApiKeyCredentials, NewApiKey};
+use crate::domain::repositories::repository::RepositoryResult;
+
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    async fn create(&self, new_api_key: &NewApiKey) -> RepositoryResult<ApiKey>;
+    async fn list_for_user(&self, user_id: i32) -> RepositoryResult<Vec<ApiKey>>;
+    async fn get(&self, api_key_id: i32) -> RepositoryResult<ApiKey>;
+    async fn find_credentials(&self, prefix: &str) -> RepositoryResult<Option<ApiKeyCredentials>>;
+    // Keeps revoked keys around, so they still show up in listings
+    async fn revoke(&self, api_key_id: i32, revoked_at: DateTime<Utc>) -> RepositoryResult<ApiKey>;
+    async fn mark_used(&self, api_key_id: i32, used_at: DateTime<Utc>) -> RepositoryResult<()>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use crate::domain::models::attachment::{Attachment, CreateAttachment};
+use crate::domain::repositories::repository::RepositoryResult;
+
+#[async_trait]
+pub trait AttachmentRepository: Send + Sync {
+    async fn create(&self, todo_id: i32, new_attachment: &CreateAttachment) -> RepositoryResult<Attachment>;
+    async fn list(&self, todo_id: i32) -> RepositoryResult<Vec<Attachment>>;
+    async fn get(&self, todo_id: i32, attachment_id: i32) -> RepositoryResult<Attachment>;
+    // Returns the deleted attachment so its content can be removed from the blob store
+    async fn delete(&self, todo_id: i32, attachment_id: i32) -> RepositoryResult<Attachment>;
+}
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::models::audit::{AuditAction, AuditEvent};
+use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging, SortOrder, DEFAULT_LIMIT, DEFAULT_OFFSET};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AuditQueryParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub todo_id: Option<i32>,
+    pub actor: Option<String>,
+    pub action: Option<AuditAction>,
+    pub request_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    // Events are listed in the order they happened unless asked otherwise
+    pub sort_order: Option<SortOrder>,
+}
+
+impl QueryParams for AuditQueryParams {
+    fn limit(&self) -> i64 {
+        self.limit.or(DEFAULT_LIMIT).unwrap_or_default()
+    }
+    fn offset(&self) -> i64 {
+        self.offset.or(DEFAULT_OFFSET).unwrap_or_default()
+    }
+}
+
+// Events are only ever appended by the repositories of the audited entities,
+// as part of the transaction making the change
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn list(&self, params: AuditQueryParams) -> RepositoryResult<ResultPaging<AuditEvent>>;
+}
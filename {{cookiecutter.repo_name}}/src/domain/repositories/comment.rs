@@ -0,0 +1,16 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use crate::domain::models::comment::{Comment, CreateComment, UpdateComment};
+use crate::domain::repositories::repository::{QueryParamsImpl, RepositoryResult, ResultPaging};
+
+#[async_trait]
+pub trait CommentRepository: Send + Sync {
+    async fn create(&self, todo_id: i32, new_comment: &CreateComment) -> RepositoryResult<Comment>;
+    // Oldest comments first
+    async fn list(&self, todo_id: i32, params: QueryParamsImpl) -> RepositoryResult<ResultPaging<Comment>>;
+    async fn get(&self, todo_id: i32, comment_id: i32) -> RepositoryResult<Comment>;
+    async fn update(&self, todo_id: i32, comment_id: i32, comment: &UpdateComment) -> RepositoryResult<Comment>;
+    async fn delete(&self, todo_id: i32, comment_id: i32) -> RepositoryResult<()>;
+    // Number of comments of each of `todo_ids`, todos without comments are left out
+    async fn count_for_todos(&self, todo_ids: &[i32]) -> RepositoryResult<HashMap<i32, i64>>;
+}
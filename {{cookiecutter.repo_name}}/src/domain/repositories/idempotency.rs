@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::models::idempotency::{IdempotencyKey, StoredResponse};
+use crate::domain::repositories::repository::RepositoryResult;
+
+#[async_trait]
+pub trait IdempotencyRepository: Send + Sync {
+    // Claims `key` for a new request of `principal`, returning the existing unexpired key if they
+    // already claimed it
+    async fn claim(&self, principal: &str, key: &str, request_fingerprint: &str, expires_at: DateTime<Utc>) -> RepositoryResult<Option<IdempotencyKey>>;
+    async fn store_response(&self, principal: &str, key: &str, response: &StoredResponse) -> RepositoryResult<()>;
+    async fn release(&self, principal: &str, key: &str) -> RepositoryResult<()>;
+    async fn purge_expired(&self, now: DateTime<Utc>) -> RepositoryResult<usize>;
+}
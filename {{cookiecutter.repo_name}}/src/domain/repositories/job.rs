@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::models::job::{CreateJob, Job};
+use crate::domain::repositories::repository::RepositoryResult;
+
+#[async_trait]
+pub trait JobRepository: Send + Sync {
+    // Returns `None` when a job with the same dedup key already exists
+    async fn enqueue(&self, new_job: &CreateJob) -> RepositoryResult<Option<Job>>;
+    // Locks the next due job of one of `kinds`, also reclaiming running jobs locked before `stale_before`
+    async fn claim_next(&self, kinds: &[String], stale_before: DateTime<Utc>) -> RepositoryResult<Option<Job>>;
+    async fn complete(&self, job_id: i64) -> RepositoryResult<()>;
+    async fn retry(&self, job_id: i64, error: &str, run_at: DateTime<Utc>) -> RepositoryResult<()>;
+    async fn fail(&self, job_id: i64, error: &str) -> RepositoryResult<()>;
+    async fn purge_finished(&self, finished_before: DateTime<Utc>) -> RepositoryResult<usize>;
+}
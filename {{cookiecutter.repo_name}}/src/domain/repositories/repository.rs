@@ -9,6 +9,14 @@ pub struct ResultPaging<T> {
     pub items: Vec<T>,
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
 pub const DEFAULT_OFFSET: Option<i64> = Some(0);
 pub const DEFAULT_LIMIT: Option<i64> = Some(25);
 
@@ -0,0 +1,19 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use crate::domain::models::tag::{CreateTag, Tag, UpdateTag};
+use crate::domain::repositories::repository::{QueryParamsImpl, RepositoryResult, ResultPaging};
+
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    async fn create(&self, new_tag: &CreateTag) -> RepositoryResult<Tag>;
+    async fn list(&self, params: QueryParamsImpl) -> RepositoryResult<ResultPaging<Tag>>;
+    async fn get(&self, tag_id: i32) -> RepositoryResult<Tag>;
+    async fn update(&self, tag_id: i32, tag: &UpdateTag) -> RepositoryResult<Tag>;
+    async fn delete(&self, tag_id: i32) -> RepositoryResult<()>;
+    async fn list_for_todo(&self, todo_id: i32) -> RepositoryResult<Vec<Tag>>;
+    // Tags of each of `todo_ids`, todos without tags are left out
+    async fn list_for_todos(&self, todo_ids: &[i32]) -> RepositoryResult<HashMap<i32, Vec<Tag>>>;
+    // Attaching and detaching return the todo's tags afterwards
+    async fn attach(&self, todo_id: i32, tag_ids: &[i32]) -> RepositoryResult<Vec<Tag>>;
+    async fn detach(&self, todo_id: i32, tag_id: i32) -> RepositoryResult<Vec<Tag>>;
+}
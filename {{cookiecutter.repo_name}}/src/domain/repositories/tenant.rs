@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use crate::domain::models::tenant::{CreateTenant, RegisteredTenant, UpdateTenant};
+use crate::domain::repositories::repository::{QueryParamsImpl, RepositoryResult, ResultPaging};
+
+// The registry of tenants, which spans all of them
+#[async_trait]
+pub trait TenantRepository: Send + Sync {
+    async fn create(&self, new_tenant: &CreateTenant) -> RepositoryResult<RegisteredTenant>;
+    async fn list(&self, params: QueryParamsImpl) -> RepositoryResult<ResultPaging<RegisteredTenant>>;
+    async fn get(&self, tenant_id: &str) -> RepositoryResult<RegisteredTenant>;
+    async fn update(&self, tenant_id: &str, tenant: &UpdateTenant) -> RepositoryResult<RegisteredTenant>;
+}
@@ -1,13 +1,63 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use crate::domain::repositories::repository::{QueryParams, ResultPaging, RepositoryResult, DEFAULT_LIMIT, DEFAULT_OFFSET};
-use crate::domain::models::todo::{Todo, CreateTodo};
+use crate::domain::repositories::repository::{QueryParams, ResultPaging, RepositoryResult, SortOrder, DEFAULT_LIMIT, DEFAULT_OFFSET};
+use crate::domain::models::todo::{Todo, CreateTodo, TodoOperation, TodoOwnership, TodoPlacement, TodoPriority, TodoStatus, UpdateTodo};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoSortField {
+    #[default]
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    DueAt,
+    Priority,
+    Position,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMatch {
+    #[default]
+    Any,
+    All,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TodoQueryParams {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
     pub title: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub updated_since: Option<DateTime<Utc>>,
+    pub sort_by: Option<TodoSortField>,
+    pub sort_order: Option<SortOrder>,
+    pub include_deleted: Option<bool>,
+    // Open todos whose due date has passed
+    pub overdue: Option<bool>,
+    pub due_before: Option<DateTime<Utc>>,
+    pub due_after: Option<DateTime<Utc>>,
+    pub status: Option<TodoStatus>,
+    pub priority: Option<TodoPriority>,
+    // Comma separated tag names, matched according to `tags_match`
+    pub tags: Option<String>,
+    pub tags_match: Option<TagMatch>,
+    pub list_id: Option<i32>,
+    // Todos of archived lists are hidden unless asked for
+    pub include_archived: Option<bool>,
+    pub owner_id: Option<i32>,
+}
+
+impl TodoQueryParams {
+    pub fn tag_names(&self) -> Vec<String> {
+        self.tags.as_deref().unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
 }
 
 impl QueryParams for TodoQueryParams {
@@ -24,5 +74,20 @@ pub trait TodoRepository: Send + Sync {
     async fn create(&self, new_todo: &CreateTodo) -> RepositoryResult<Todo>;
     async fn list(&self, params: TodoQueryParams) -> RepositoryResult<ResultPaging<Todo>>;
     async fn get(&self, todo_id: i32) -> RepositoryResult<Todo>;
-    async fn delete(&self, todo_id: i32) -> RepositoryResult<()>;
+    // Who owns a todo, whether it is deleted or not
+    async fn ownership(&self, todo_id: i32) -> RepositoryResult<TodoOwnership>;
+    async fn update(&self, todo_id: i32, todo: &UpdateTodo, expected_version: Option<i32>) -> RepositoryResult<Todo>;
+    async fn delete(&self, todo_id: i32, expected_version: Option<i32>) -> RepositoryResult<()>;
+    async fn create_many(&self, new_todos: &[CreateTodo]) -> RepositoryResult<Vec<Todo>>;
+    async fn delete_many(&self, todo_ids: &[i32]) -> RepositoryResult<usize>;
+    async fn complete_many(&self, todo_ids: &[i32], completed_by: Option<String>) -> RepositoryResult<Vec<Todo>>;
+    async fn reopen(&self, todo_id: i32) -> RepositoryResult<Todo>;
+    // Runs all operations in a single transaction, returning the affected todo per operation
+    async fn execute_batch(&self, operations: &[TodoOperation]) -> RepositoryResult<Vec<Option<Todo>>>;
+    async fn restore(&self, todo_id: i32) -> RepositoryResult<Todo>;
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> RepositoryResult<usize>;
+    // Open todos whose reminder is due and has not been sent yet
+    async fn list_due_reminders(&self, now: DateTime<Utc>, limit: i64) -> RepositoryResult<Vec<Todo>>;
+    async fn mark_reminded(&self, reminders: &[(i32, DateTime<Utc>)]) -> RepositoryResult<()>;
+    async fn move_todo(&self, todo_id: i32, placement: TodoPlacement) -> RepositoryResult<Todo>;
 }
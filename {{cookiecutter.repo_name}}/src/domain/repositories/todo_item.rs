@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use crate::domain::models::todo_item::{CreateTodoItem, TodoItem, TodoProgress, UpdateTodoItem};
+use crate::domain::repositories::repository::RepositoryResult;
+
+#[async_trait]
+pub trait TodoItemRepository: Send + Sync {
+    async fn list(&self, todo_id: i32) -> RepositoryResult<Vec<TodoItem>>;
+    async fn create(&self, todo_id: i32, item: &CreateTodoItem) -> RepositoryResult<TodoItem>;
+    async fn update(&self, todo_id: i32, item_id: i32, item: &UpdateTodoItem) -> RepositoryResult<TodoItem>;
+    async fn toggle(&self, todo_id: i32, item_id: i32) -> RepositoryResult<TodoItem>;
+    async fn delete(&self, todo_id: i32, item_id: i32) -> RepositoryResult<()>;
+    // `item_ids` must list every item of the todo exactly once, in the new order
+    async fn reorder(&self, todo_id: i32, item_ids: &[i32]) -> RepositoryResult<Vec<TodoItem>>;
+    // Progress of each of `todo_ids`, todos without items are left out
+    async fn progress(&self, todo_ids: &[i32]) -> RepositoryResult<HashMap<i32, TodoProgress>>;
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::domain::models::share::{AccessibleTodoList, CreateTodoListShare, ListAccess, TodoListShare};
+use crate::domain::models::todo_list::{CreateTodoList, TodoList, UpdateTodoList};
+use crate::domain::repositories::repository::{QueryParams, QueryParamsImpl, RepositoryResult, ResultPaging, DEFAULT_LIMIT, DEFAULT_OFFSET};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TodoListQueryParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub owner: Option<String>,
+    pub include_archived: Option<bool>,
+}
+
+impl QueryParams for TodoListQueryParams {
+    fn limit(&self) -> i64 {
+        self.limit.or(DEFAULT_LIMIT).unwrap_or_default()
+    }
+    fn offset(&self) -> i64 {
+        self.offset.or(DEFAULT_OFFSET).unwrap_or_default()
+    }
+}
+
+// Reads only see the lists visible to the current principal, see `ListAccess`
+#[async_trait]
+pub trait TodoListRepository: Send + Sync {
+    async fn create(&self, new_list: &CreateTodoList) -> RepositoryResult<TodoList>;
+    async fn list(&self, params: TodoListQueryParams) -> RepositoryResult<ResultPaging<TodoList>>;
+    // The lists the current principal owns or that are shared with them
+    async fn list_accessible(&self, params: QueryParamsImpl) -> RepositoryResult<ResultPaging<AccessibleTodoList>>;
+    async fn get(&self, list_id: i32) -> RepositoryResult<TodoList>;
+    async fn access(&self, list_id: i32) -> RepositoryResult<ListAccess>;
+    async fn update(&self, list_id: i32, list: &UpdateTodoList) -> RepositoryResult<TodoList>;
+    // Without `cascade`, lists that still contain (non deleted) todos are not deleted
+    async fn delete(&self, list_id: i32, cascade: bool) -> RepositoryResult<()>;
+    async fn set_archived(&self, list_id: i32, archived: bool) -> RepositoryResult<TodoList>;
+    async fn list_shares(&self, list_id: i32) -> RepositoryResult<Vec<TodoListShare>>;
+    async fn share(&self, list_id: i32, share: &CreateTodoListShare) -> RepositoryResult<TodoListShare>;
+    async fn unshare(&self, list_id: i32, share_id: i32) -> RepositoryResult<()>;
+}
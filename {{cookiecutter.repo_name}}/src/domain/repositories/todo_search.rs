@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::domain::models::todo::TodoSearchHit;
+use crate::domain::repositories::repository::{QueryParams, ResultPaging, RepositoryResult, DEFAULT_LIMIT, DEFAULT_OFFSET};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoSearchParams {
+    pub q: String,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub list_id: Option<i32>,
+}
+
+impl QueryParams for TodoSearchParams {
+    fn limit(&self) -> i64 {
+        self.limit.or(DEFAULT_LIMIT).unwrap_or_default()
+    }
+    fn offset(&self) -> i64 {
+        self.offset.or(DEFAULT_OFFSET).unwrap_or_default()
+    }
+}
+
+// Searches titles and descriptions of live todos outside archived lists, best matches first
+#[async_trait]
+pub trait TodoSearchRepository: Send + Sync {
+    async fn search(&self, params: TodoSearchParams) -> RepositoryResult<ResultPaging<TodoSearchHit>>;
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use crate::domain::models::authorization::Role;
+use crate::domain::models::user::{CreateUser, User, UserCredentials};
+use crate::domain::repositories::repository::{QueryParamsImpl, RepositoryResult, ResultPaging};
+
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    async fn create(&self, new_user: &CreateUser) -> RepositoryResult<User>;
+    async fn list(&self, params: QueryParamsImpl) -> RepositoryResult<ResultPaging<User>>;
+    async fn get(&self, user_id: i32) -> RepositoryResult<User>;
+    async fn set_role(&self, user_id: i32, role: Role) -> RepositoryResult<User>;
+    async fn set_groups(&self, user_id: i32, groups: &[String]) -> RepositoryResult<User>;
+    // Emails are stored lower-cased, `email` is expected to be as well
+    async fn find_credentials(&self, email: &str) -> RepositoryResult<Option<UserCredentials>>;
+    async fn find_external(&self, external_id: &str) -> RepositoryResult<Option<User>>;
+    async fn link_external(&self, user_id: i32, external_id: &str) -> RepositoryResult<User>;
+}
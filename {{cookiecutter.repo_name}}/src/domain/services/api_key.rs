@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::api_key::{ApiKey, CreateApiKey, IssuedApiKey};
+use crate::domain::models::user::User;
+
+#[async_trait]
+pub trait ApiKeyService: 'static + Sync + Send {
+    async fn create(&self, user: &User, api_key: CreateApiKey) -> Result<IssuedApiKey, CommonError>;
+    async fn list(&self, user: &User) -> Result<Vec<ApiKey>, CommonError>;
+    async fn revoke(&self, user: &User, api_key_id: i32) -> Result<ApiKey, CommonError>;
+    // The user a key belongs to along with the key, as long as it is neither revoked nor expired
+    async fn authenticate(&self, key: &str) -> Result<(User, ApiKey), CommonError>;
+}
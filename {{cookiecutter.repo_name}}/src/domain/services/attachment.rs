@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::attachment::{Attachment, ByteRange, UploadAttachment};
+use crate::domain::services::blob_store::BlobStream;
+
+#[async_trait]
+pub trait AttachmentService: 'static + Sync + Send {
+    async fn upload(&self, todo_id: i32, upload: UploadAttachment) -> Result<Attachment, CommonError>;
+    async fn list(&self, todo_id: i32) -> Result<Vec<Attachment>, CommonError>;
+    async fn get(&self, todo_id: i32, attachment_id: i32) -> Result<Attachment, CommonError>;
+    async fn download(&self, attachment: &Attachment, range: ByteRange) -> Result<BlobStream, CommonError>;
+    async fn delete(&self, todo_id: i32, attachment_id: i32) -> Result<(), CommonError>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::audit::AuditEvent;
+use crate::domain::repositories::audit::AuditQueryParams;
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+
+#[async_trait]
+pub trait AuditService: 'static + Sync + Send {
+    async fn history(&self, todo_id: i32, params: QueryParamsImpl) -> Result<ResultPaging<AuditEvent>, CommonError>;
+    async fn list(&self, params: AuditQueryParams) -> Result<ResultPaging<AuditEvent>, CommonError>;
+}
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::auth::{AuthTokens, Login, RegisterUser};
+use crate::domain::models::authorization::{Principal, Role};
+use crate::domain::models::user::User;
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+
+#[async_trait]
+pub trait AuthService: 'static + Sync + Send {
+    async fn register(&self, user: RegisterUser) -> Result<User, CommonError>;
+    async fn login(&self, login: Login) -> Result<AuthTokens, CommonError>;
+    // Trades a refresh token for a new pair of tokens
+    async fn refresh(&self, refresh_token: &str) -> Result<AuthTokens, CommonError>;
+    // The user an access token was issued to
+    async fn authenticate(&self, access_token: &str) -> Result<User, CommonError>;
+    // The principal of requests without credentials
+    fn anonymous(&self) -> Principal;
+    // Creates the accounts of the configured admins that do not exist yet, run at startup
+    async fn seed_admins(&self) -> Result<(), CommonError>;
+    async fn list_users(&self, params: QueryParamsImpl) -> Result<ResultPaging<User>, CommonError>;
+    async fn set_role(&self, user_id: i32, role: Role) -> Result<User, CommonError>;
+    // Replaces the groups of a user, which lists can be shared with
+    async fn set_groups(&self, user_id: i32, groups: Vec<String>) -> Result<User, CommonError>;
+}
@@ -0,0 +1,18 @@
+use std::pin::Pin;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::Stream;
+
+use crate::domain::error::CommonError;
+
+pub type BlobStream = Pin<Box<dyn Stream<Item = Result<Bytes, CommonError>> + Send>>;
+
+// Stores the content of attachments by key, metadata lives in the database
+#[async_trait]
+pub trait BlobStore: 'static + Sync + Send {
+    async fn put(&self, key: &str, content_type: &str, content: Bytes) -> Result<(), CommonError>;
+    // Streams the blob, or only the inclusive byte range `start..=end` of it
+    async fn get(&self, key: &str, range: Option<(u64, u64)>) -> Result<BlobStream, CommonError>;
+    // Deleting a missing blob is not an error
+    async fn delete(&self, key: &str) -> Result<(), CommonError>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::comment::{Comment, CreateComment, UpdateComment};
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+
+#[async_trait]
+pub trait CommentService: 'static + Sync + Send {
+    async fn create(&self, todo_id: i32, comment: CreateComment) -> Result<Comment, CommonError>;
+    async fn list(&self, todo_id: i32, params: QueryParamsImpl) -> Result<ResultPaging<Comment>, CommonError>;
+    async fn get(&self, todo_id: i32, comment_id: i32) -> Result<Comment, CommonError>;
+    async fn update(&self, todo_id: i32, comment_id: i32, comment: UpdateComment) -> Result<Comment, CommonError>;
+    async fn delete(&self, todo_id: i32, comment_id: i32) -> Result<(), CommonError>;
+}
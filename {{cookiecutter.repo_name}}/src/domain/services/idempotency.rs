@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::idempotency::StoredResponse;
+
+#[async_trait]
+pub trait IdempotencyService: 'static + Sync + Send {
+    // Returns the stored response to replay, or `None` when the request should be processed. Keys
+    // are scoped to `principal`, the signed in user or empty for anonymous requests.
+    async fn begin(&self, principal: &str, key: &str, request_fingerprint: &str) -> Result<Option<StoredResponse>, CommonError>;
+    async fn complete(&self, principal: &str, key: &str, response: StoredResponse) -> Result<(), CommonError>;
+    // Forgets the key so that a retry is processed again, e.g. after a server error
+    async fn abandon(&self, principal: &str, key: &str) -> Result<(), CommonError>;
+    async fn purge_expired(&self) -> Result<usize, CommonError>;
+}
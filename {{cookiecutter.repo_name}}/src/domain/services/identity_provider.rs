@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::auth::ExternalIdentity;
+
+// Verifies access tokens issued by an external identity provider, such as an OpenID Connect SSO
+#[async_trait]
+pub trait IdentityProvider: 'static + Sync + Send {
+    // Fails for tokens that are malformed, not signed by the provider or expired
+    async fn verify(&self, token: &str) -> Result<ExternalIdentity, CommonError>;
+}
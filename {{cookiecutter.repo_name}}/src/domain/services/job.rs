@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use chrono::Duration;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::job::{CreateJob, Job};
+
+#[async_trait]
+pub trait JobService: 'static + Sync + Send {
+    async fn enqueue(&self, job: CreateJob) -> Result<Option<Job>, CommonError>;
+    async fn claim_next(&self, kinds: &[String]) -> Result<Option<Job>, CommonError>;
+    async fn complete(&self, job: &Job) -> Result<(), CommonError>;
+    // Schedules a retry with exponential backoff, or marks the job as failed once it ran out of attempts
+    async fn fail(&self, job: &Job, error: &CommonError) -> Result<(), CommonError>;
+    async fn purge_finished(&self, older_than: Duration) -> Result<usize, CommonError>;
+}
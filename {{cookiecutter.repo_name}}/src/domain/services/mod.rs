@@ -1,2 +1,17 @@
 pub mod todo;
 pub mod service_context;
+pub mod idempotency;
+pub mod job;
+pub mod tag;
+pub mod todo_list;
+pub mod reminder;
+pub mod comment;
+pub mod attachment;
+pub mod blob_store;
+pub mod audit;
+pub mod password_hasher;
+pub mod token_codec;
+pub mod auth;
+pub mod api_key;
+pub mod identity_provider;
+pub mod tenant;
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+
+// Hashing is deliberately slow, implementations should keep it off the async workers
+#[async_trait]
+pub trait PasswordHasher: 'static + Sync + Send {
+    async fn hash(&self, password: String) -> Result<String, CommonError>;
+    async fn verify(&self, password: String, password_hash: String) -> Result<bool, CommonError>;
+}
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::todo::Todo;
+
+#[async_trait]
+pub trait ReminderNotifier: 'static + Sync + Send {
+    async fn notify(&self, todo: &Todo) -> Result<(), CommonError>;
+}
@@ -1,7 +1,13 @@
+use crate::domain::error::CommonError;
 use crate::domain::models::service_context::ServiceContext;
+use crate::domain::models::tenant::Tenant;
 
 pub trait ServiceContextService: 'static + Sync + Send {
-    fn get_service_context(&self) -> ServiceContext;
-    fn update(&self, service_context: ServiceContext) -> ServiceContext;
-    fn is_maintenance_active(&self) -> bool;
+    // The tenant's context, falling back to the global one for tenants without their own.
+    // Without a tenant, the global context.
+    fn get_service_context(&self, tenant: Option<&Tenant>) -> ServiceContext;
+    // Creates the context of a tenant on its first update
+    fn update(&self, tenant: Option<&Tenant>, maintenance: bool) -> ServiceContext;
+    // Requests to a tenant are turned away while either the tenant or everything is in maintenance
+    fn is_maintenance_active(&self, tenant: &Tenant) -> Result<bool, CommonError>;
 }
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::tag::{CreateTag, Tag, UpdateTag};
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+
+#[async_trait]
+pub trait TagService: 'static + Sync + Send {
+    async fn create(&self, tag: CreateTag) -> Result<Tag, CommonError>;
+    async fn list(&self, params: QueryParamsImpl) -> Result<ResultPaging<Tag>, CommonError>;
+    async fn get(&self, tag_id: i32) -> Result<Tag, CommonError>;
+    async fn update(&self, tag_id: i32, tag: UpdateTag) -> Result<Tag, CommonError>;
+    async fn delete(&self, tag_id: i32) -> Result<(), CommonError>;
+    async fn list_for_todo(&self, todo_id: i32) -> Result<Vec<Tag>, CommonError>;
+    async fn attach(&self, todo_id: i32, tag_ids: Vec<i32>) -> Result<Vec<Tag>, CommonError>;
+    async fn detach(&self, todo_id: i32, tag_id: i32) -> Result<Vec<Tag>, CommonError>;
+}
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::tenant::{CreateTenant, RegisteredTenant, UpdateTenant};
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+
+#[async_trait]
+pub trait TenantService: 'static + Sync + Send {
+    // The tenant whose admins manage every tenant
+    fn admin_tenant(&self) -> &str;
+    // Looks up the tenant a request is made to, failing for unregistered ones
+    async fn find(&self, tenant_id: &str) -> Result<RegisteredTenant, CommonError>;
+    async fn create(&self, tenant: CreateTenant) -> Result<RegisteredTenant, CommonError>;
+    async fn list(&self, params: QueryParamsImpl) -> Result<ResultPaging<RegisteredTenant>, CommonError>;
+    async fn update(&self, tenant_id: &str, tenant: UpdateTenant) -> Result<RegisteredTenant, CommonError>;
+}
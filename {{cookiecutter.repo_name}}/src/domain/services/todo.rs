@@ -1,15 +1,37 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::domain::error::CommonError;
-use crate::domain::models::todo::{CreateTodo, Todo};
+use crate::domain::models::todo::{CreateTodo, MoveTodo, Todo, TodoOperation, TodoSearchHit, UpdateTodo};
+use crate::domain::models::todo_item::{CreateTodoItem, TodoItem, UpdateTodoItem};
 use crate::domain::repositories::repository::ResultPaging;
 use crate::domain::repositories::todo::TodoQueryParams;
+use crate::domain::repositories::todo_search::TodoSearchParams;
 
 #[async_trait]
 pub trait TodoService: 'static + Sync + Send {
     async fn create(&self, todo: CreateTodo) -> Result<Todo, CommonError>;
     async fn list(&self, params: TodoQueryParams) -> Result<ResultPaging<Todo>, CommonError>;
     async fn get(&self, todo_id: i32) -> Result<Todo, CommonError>;
-    async fn delete(&self, todo_id: i32) -> Result<(), CommonError>;
+    async fn update(&self, todo_id: i32, todo: UpdateTodo, expected_version: Option<i32>) -> Result<Todo, CommonError>;
+    async fn delete(&self, todo_id: i32, expected_version: Option<i32>) -> Result<(), CommonError>;
+    async fn complete(&self, todo_id: i32, completed_by: Option<String>) -> Result<Todo, CommonError>;
+    async fn reopen(&self, todo_id: i32) -> Result<Todo, CommonError>;
+    // With `atomic` all operations succeed or fail together, otherwise each operation reports its own result
+    async fn bulk(&self, operations: Vec<TodoOperation>, atomic: bool) -> Result<Vec<Result<Option<Todo>, CommonError>>, CommonError>;
+    async fn restore(&self, todo_id: i32) -> Result<Todo, CommonError>;
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> Result<usize, CommonError>;
+    async fn due_reminders(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Todo>, CommonError>;
+    async fn mark_reminded(&self, todos: &[Todo]) -> Result<(), CommonError>;
+    async fn search(&self, params: TodoSearchParams) -> Result<ResultPaging<TodoSearchHit>, CommonError>;
+    async fn move_todo(&self, todo_id: i32, target: MoveTodo) -> Result<Todo, CommonError>;
+    // Due dates of the upcoming occurrences of a recurring todo, after its own
+    async fn occurrences(&self, todo_id: i32, limit: Option<usize>) -> Result<Vec<DateTime<Utc>>, CommonError>;
+    async fn list_items(&self, todo_id: i32) -> Result<Vec<TodoItem>, CommonError>;
+    async fn add_item(&self, todo_id: i32, item: CreateTodoItem) -> Result<TodoItem, CommonError>;
+    async fn update_item(&self, todo_id: i32, item_id: i32, item: UpdateTodoItem) -> Result<TodoItem, CommonError>;
+    async fn toggle_item(&self, todo_id: i32, item_id: i32) -> Result<TodoItem, CommonError>;
+    async fn delete_item(&self, todo_id: i32, item_id: i32) -> Result<(), CommonError>;
+    async fn reorder_items(&self, todo_id: i32, item_ids: Vec<i32>) -> Result<Vec<TodoItem>, CommonError>;
 }
 
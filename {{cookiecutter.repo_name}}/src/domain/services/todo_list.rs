@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+
+use crate::domain::error::CommonError;
+use crate::domain::models::share::{AccessibleTodoList, CreateTodoListShare, TodoListShare};
+use crate::domain::models::todo_list::{CreateTodoList, TodoList, UpdateTodoList};
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::repositories::todo_list::TodoListQueryParams;
+
+#[async_trait]
+pub trait TodoListService: 'static + Sync + Send {
+    async fn create(&self, list: CreateTodoList) -> Result<TodoList, CommonError>;
+    async fn list(&self, params: TodoListQueryParams) -> Result<ResultPaging<TodoList>, CommonError>;
+    async fn get(&self, list_id: i32) -> Result<TodoList, CommonError>;
+    async fn update(&self, list_id: i32, list: UpdateTodoList) -> Result<TodoList, CommonError>;
+    async fn delete(&self, list_id: i32, cascade: bool) -> Result<(), CommonError>;
+    async fn archive(&self, list_id: i32) -> Result<TodoList, CommonError>;
+    async fn unarchive(&self, list_id: i32) -> Result<TodoList, CommonError>;
+    async fn list_accessible(&self, params: QueryParamsImpl) -> Result<ResultPaging<AccessibleTodoList>, CommonError>;
+    async fn list_shares(&self, list_id: i32) -> Result<Vec<TodoListShare>, CommonError>;
+    async fn share(&self, list_id: i32, share: CreateTodoListShare) -> Result<TodoListShare, CommonError>;
+    async fn unshare(&self, list_id: i32, share_id: i32) -> Result<(), CommonError>;
+}
@@ -0,0 +1,9 @@
+use crate::domain::error::CommonError;
+use crate::domain::models::auth::TokenClaims;
+
+// Signs and verifies the tokens handed out at sign in
+pub trait TokenCodec: 'static + Sync + Send {
+    fn encode(&self, claims: &TokenClaims) -> Result<String, CommonError>;
+    // Fails for tokens that are malformed, tampered with or expired
+    fn decode(&self, token: &str) -> Result<TokenClaims, CommonError>;
+}
@@ -0,0 +1,14 @@
+use std::env;
+use std::str::FromStr;
+
+use dotenv::dotenv;
+
+// Reads an optional setting from the environment (or .env), falling back to `default`
+// when it is missing or cannot be parsed.
+pub fn env_or<T: FromStr>(key: &str, default: T) -> T {
+    dotenv().ok();
+    env::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
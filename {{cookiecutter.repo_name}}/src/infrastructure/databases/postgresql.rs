@@ -4,20 +4,75 @@ use diesel;
 use diesel::pg::PgConnection;
 use diesel::r2d2;
 use diesel::r2d2::ConnectionManager;
+use diesel::sql_types::Text;
+use diesel::RunQueryDsl;
 use dotenv::dotenv;
 
-use crate::domain::constants::POSTGRESQL_DB_URI;
+use crate::domain::constants::{POSTGRESQL_DB_URI, TENANT_ROW_LEVEL_SECURITY};
+use crate::domain::error::{RepositoryError, REPOSITORY_ERROR_CODE};
+use crate::domain::models::tenant::Tenant;
+use crate::infrastructure::config::env_or;
+use crate::infrastructure::error::DieselRepositoryError;
 
 pub type Pool<T> = r2d2::Pool<ConnectionManager<T>>;
 pub type PostgresPool = Pool<diesel::pg::PgConnection>;
-pub type DBConn = PostgresPool;
+pub type PooledConnection = r2d2::PooledConnection<ConnectionManager<PgConnection>>;
+
+pub struct DBConn {
+    pool: PostgresPool,
+    row_level_security: bool,
+}
+
+impl DBConn {
+    // The tenant connections are tagged with: the running request's tenant, whose rows alone the
+    // row-level security policies then let them see. Queries run on the blocking thread pool, where
+    // the request's tenant is not set, so it is captured before moving there.
+    pub fn tenant_tag() -> Option<String> {
+        Tenant::current().map(|tenant| tenant.id)
+    }
+
+    // Checks out a connection for the tenant captured with `tenant_tag`. Without a tenant it fails
+    // rather than handing out a connection seeing every tenant.
+    pub fn get_for(&self, tenant_tag: &Option<String>) -> Result<PooledConnection, DieselRepositoryError> {
+        match tenant_tag {
+            Some(tenant_id) => self.checkout(tenant_id.clone()),
+            None => Err(RepositoryError {
+                message: "No tenant to check out a connection for".to_string(),
+                code: REPOSITORY_ERROR_CODE,
+            }.into()),
+        }
+    }
+
+    // Checks out a connection for the running request, which must not be called on the blocking
+    // thread pool
+    pub fn get(&self) -> Result<PooledConnection, DieselRepositoryError> {
+        self.get_for(&Self::tenant_tag())
+    }
+
+    // A connection seeing every tenant. Only for lookups finding out which tenant a request belongs
+    // to in the first place, background jobs working across tenants and data kept outside of them.
+    pub fn get_across_tenants(&self) -> Result<PooledConnection, DieselRepositoryError> {
+        self.checkout(String::new())
+    }
+
+    fn checkout(&self, tenant_id: String) -> Result<PooledConnection, DieselRepositoryError> {
+        let mut conn = self.pool.get()?;
+        if self.row_level_security {
+            diesel::sql_query("SELECT set_config('app.tenant_id', $1, false)")
+                .bind::<Text, _>(tenant_id)
+                .execute(&mut conn)?;
+        }
+        Ok(conn)
+    }
+}
 
 pub fn db_pool() -> DBConn {
     dotenv().ok();
     let database_url = env::var(POSTGRESQL_DB_URI)
         .expect(&*format!("{value} must be set", value = POSTGRESQL_DB_URI));
     let manager = ConnectionManager::<PgConnection>::new(database_url);
-    Pool::builder()
+    let pool = Pool::builder()
         .build(manager)
-        .expect("Failed to create pool")
+        .expect("Failed to create pool");
+    DBConn { pool, row_level_security: env_or(TENANT_ROW_LEVEL_SECURITY, false) }
 }
@@ -1,6 +1,7 @@
 use diesel::r2d2;
 pub use actix_threadpool::{run, BlockingError};
-use crate::domain::error::RepositoryError;
+use diesel::result::DatabaseErrorKind;
+use crate::domain::error::{RepositoryError, CONFLICT_ERROR_CODE, NOT_FOUND_ERROR_CODE, REPOSITORY_ERROR_CODE};
 
 pub type AsyncPoolError <T> = BlockingError<T>;
 
@@ -13,26 +14,52 @@ impl DieselRepositoryError {
     }
 }
 
+impl From<RepositoryError> for DieselRepositoryError {
+    fn from(error: RepositoryError) -> DieselRepositoryError {
+        DieselRepositoryError(error)
+    }
+}
+
 impl From<r2d2::Error> for DieselRepositoryError {
     fn from(error: r2d2::Error) -> DieselRepositoryError {
         DieselRepositoryError(RepositoryError {
             message: error.to_string(),
+            code: REPOSITORY_ERROR_CODE,
         })
     }
 }
 
-impl From<diesel::result::Error> for DieselRepositoryError {
-    fn from(error: diesel::result::Error) -> DieselRepositoryError {
+impl From<r2d2::PoolError> for DieselRepositoryError {
+    fn from(error: r2d2::PoolError) -> DieselRepositoryError {
         DieselRepositoryError(RepositoryError {
             message: error.to_string(),
+            code: REPOSITORY_ERROR_CODE,
         })
     }
 }
 
-impl<T: std::fmt::Debug> From<AsyncPoolError<T>> for DieselRepositoryError {
-    fn from(error: AsyncPoolError<T>) -> DieselRepositoryError {
+impl From<diesel::result::Error> for DieselRepositoryError {
+    fn from(error: diesel::result::Error) -> DieselRepositoryError {
+        let code = match error {
+            diesel::result::Error::NotFound => NOT_FOUND_ERROR_CODE,
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => CONFLICT_ERROR_CODE,
+            _ => REPOSITORY_ERROR_CODE,
+        };
         DieselRepositoryError(RepositoryError {
             message: error.to_string(),
+            code,
         })
     }
 }
+
+impl<T: std::fmt::Debug + Into<DieselRepositoryError>> From<AsyncPoolError<T>> for DieselRepositoryError {
+    fn from(error: AsyncPoolError<T>) -> DieselRepositoryError {
+        match error {
+            BlockingError::Error(error) => error.into(),
+            BlockingError::Canceled => DieselRepositoryError(RepositoryError {
+                message: error.to_string(),
+                code: REPOSITORY_ERROR_CODE,
+            }),
+        }
+    }
+}
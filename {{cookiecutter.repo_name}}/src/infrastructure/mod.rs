@@ -4,3 +4,4 @@ pub mod repositories;
 pub mod schema;
 pub mod error;
 pub mod services;
+pub mod config;
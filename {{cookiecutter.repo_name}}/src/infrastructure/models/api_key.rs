@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::api_key::{ApiKey, ApiKeyCredentials, NewApiKey};
+use crate::domain::models::authorization::Permission;
+use crate::infrastructure::schema::api_keys;
+
+#[derive(Queryable)]
+pub struct ApiKeyDiesel {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub tenant_id: String,
+}
+
+fn api_key(k: ApiKeyDiesel) -> (ApiKey, String) {
+    let api_key = ApiKey {
+        id: k.id,
+        user_id: k.user_id,
+        name: k.name,
+        prefix: k.prefix,
+        // Scopes that are no longer known grant nothing
+        scopes: k.scopes.iter().filter_map(|scope| scope.parse().ok()).collect(),
+        expires_at: k.expires_at,
+        last_used_at: k.last_used_at,
+        revoked_at: k.revoked_at,
+        created_at: k.created_at,
+    };
+    (api_key, k.key_hash)
+}
+
+impl From<ApiKeyDiesel> for ApiKey {
+    fn from(k: ApiKeyDiesel) -> Self {
+        api_key(k).0
+    }
+}
+
+impl From<ApiKeyDiesel> for ApiKeyCredentials {
+    fn from(k: ApiKeyDiesel) -> Self {
+        let tenant_id = k.tenant_id.clone();
+        let (api_key, key_hash) = api_key(k);
+        ApiKeyCredentials { api_key, key_hash, tenant_id }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = api_keys)]
+pub struct NewApiKeyDiesel {
+    pub tenant_id: String,
+    pub user_id: i32,
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl NewApiKeyDiesel {
+    pub fn new(tenant_id: String, k: NewApiKey) -> Self {
+        NewApiKeyDiesel {
+            tenant_id,
+            user_id: k.user_id,
+            name: k.name,
+            prefix: k.prefix,
+            key_hash: k.key_hash,
+            scopes: k.scopes.iter().map(Permission::to_string).collect(),
+            expires_at: k.expires_at,
+        }
+    }
+}
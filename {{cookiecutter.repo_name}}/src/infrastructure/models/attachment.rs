@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::attachment::{Attachment, CreateAttachment};
+use crate::infrastructure::schema::attachments;
+
+#[derive(Queryable)]
+pub struct AttachmentDiesel {
+    pub id: i32,
+    pub todo_id: i32,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub checksum: String,
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+    pub tenant_id: String,
+}
+
+impl From<AttachmentDiesel> for Attachment {
+    fn from(a: AttachmentDiesel) -> Self {
+        Attachment {
+            id: a.id,
+            todo_id: a.todo_id,
+            file_name: a.file_name,
+            content_type: a.content_type,
+            size: a.size,
+            checksum: a.checksum,
+            storage_key: a.storage_key,
+            created_at: a.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = attachments)]
+pub struct CreateAttachmentDiesel {
+    pub todo_id: i32,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub checksum: String,
+    pub storage_key: String,
+}
+
+impl CreateAttachmentDiesel {
+    pub fn new(todo_id: i32, a: CreateAttachment) -> Self {
+        CreateAttachmentDiesel {
+            todo_id,
+            file_name: a.file_name,
+            content_type: a.content_type,
+            size: a.size,
+            checksum: a.checksum,
+            storage_key: a.storage_key,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::io::Write;
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Varchar;
+use serde_json::Value;
+use crate::domain::models::audit::{AuditAction, AuditEvent};
+use crate::infrastructure::schema::audit_events;
+
+// Audit actions are stored by name, so new actions need no migration
+#[derive(Debug, Clone, Copy, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Varchar)]
+pub struct AuditActionDiesel(pub AuditAction);
+
+impl ToSql<Varchar, Pg> for AuditActionDiesel {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.0.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Varchar, Pg> for AuditActionDiesel {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(AuditActionDiesel(std::str::from_utf8(bytes.as_bytes())?.parse()?))
+    }
+}
+
+#[derive(Queryable)]
+pub struct AuditEventDiesel {
+    pub id: i64,
+    pub todo_id: i32,
+    pub action: AuditActionDiesel,
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+    pub changes: Value,
+    pub created_at: DateTime<Utc>,
+    pub tenant_id: String,
+}
+
+impl From<AuditEventDiesel> for AuditEvent {
+    fn from(e: AuditEventDiesel) -> Self {
+        AuditEvent {
+            id: e.id,
+            todo_id: e.todo_id,
+            action: e.action.0,
+            actor: e.actor,
+            request_id: e.request_id,
+            changes: e.changes,
+            created_at: e.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = audit_events)]
+pub struct CreateAuditEventDiesel {
+    pub todo_id: i32,
+    pub action: AuditActionDiesel,
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+    pub changes: Value,
+}
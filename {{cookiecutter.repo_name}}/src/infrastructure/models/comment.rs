@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::comment::Comment;
+use crate::infrastructure::schema::comments;
+
+#[derive(Queryable)]
+pub struct CommentDiesel {
+    pub id: i32,
+    pub todo_id: i32,
+    pub author: Option<String>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub author_id: Option<i32>,
+    pub tenant_id: String,
+}
+
+impl From<CommentDiesel> for Comment {
+    fn from(c: CommentDiesel) -> Self {
+        Comment {
+            id: c.id,
+            todo_id: c.todo_id,
+            author: c.author,
+            author_id: c.author_id,
+            body: c.body,
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = comments)]
+pub struct CreateCommentDiesel {
+    pub todo_id: i32,
+    pub author: Option<String>,
+    pub author_id: Option<i32>,
+    pub body: String,
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::idempotency::{IdempotencyKey, StoredResponse};
+use crate::infrastructure::schema::idempotency_keys;
+
+#[derive(Queryable)]
+pub struct IdempotencyKeyDiesel {
+    pub key: String,
+    pub request_fingerprint: String,
+    pub response_status: Option<i16>,
+    pub response_content_type: Option<String>,
+    pub response_body: Option<Vec<u8>>,
+    pub response_headers: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub principal: String,
+    pub tenant_id: String,
+}
+
+impl From<IdempotencyKeyDiesel> for IdempotencyKey {
+    fn from(k: IdempotencyKeyDiesel) -> Self {
+        let response = match (k.response_status, k.response_body) {
+            (Some(status), Some(body)) => Some(StoredResponse {
+                status: status as u16,
+                content_type: k.response_content_type,
+                headers: k.response_headers
+                    .as_ref()
+                    .and_then(serde_json::Value::as_object)
+                    .map(|headers| headers.iter()
+                        .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_string())))
+                        .collect())
+                    .unwrap_or_default(),
+                body,
+            }),
+            _ => None,
+        };
+        IdempotencyKey {
+            key: k.key,
+            request_fingerprint: k.request_fingerprint,
+            response,
+            created_at: k.created_at,
+            expires_at: k.expires_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = idempotency_keys)]
+pub struct CreateIdempotencyKeyDiesel {
+    pub tenant_id: String,
+    pub principal: String,
+    pub key: String,
+    pub request_fingerprint: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = idempotency_keys)]
+pub struct StoredResponseDiesel {
+    pub response_status: i16,
+    pub response_content_type: Option<String>,
+    pub response_body: Vec<u8>,
+    pub response_headers: serde_json::Value,
+}
+
+impl From<StoredResponse> for StoredResponseDiesel {
+    fn from(r: StoredResponse) -> Self {
+        StoredResponseDiesel {
+            response_status: r.status as i16,
+            response_content_type: r.content_type,
+            response_body: r.body,
+            response_headers: r.headers.into_iter()
+                .map(|(name, value)| (name, serde_json::Value::String(value)))
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+        }
+    }
+}
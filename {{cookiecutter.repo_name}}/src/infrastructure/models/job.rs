@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::job::{CreateJob, Job, JobStatus};
+use crate::infrastructure::schema::jobs;
+
+#[derive(Queryable)]
+pub struct JobDiesel {
+    pub id: i64,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime<Utc>,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub dedup_key: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<JobDiesel> for Job {
+    fn from(j: JobDiesel) -> Self {
+        Job {
+            id: j.id,
+            kind: j.kind,
+            payload: j.payload,
+            status: j.status.parse().unwrap_or(JobStatus::Failed),
+            attempts: j.attempts,
+            max_attempts: j.max_attempts,
+            run_at: j.run_at,
+            locked_at: j.locked_at,
+            last_error: j.last_error,
+            created_at: j.created_at,
+            updated_at: j.updated_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = jobs)]
+pub struct CreateJobDiesel {
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub run_at: DateTime<Utc>,
+    pub max_attempts: i32,
+    pub dedup_key: Option<String>,
+}
+
+impl From<CreateJob> for CreateJobDiesel {
+    fn from(j: CreateJob) -> Self {
+        CreateJobDiesel {
+            kind: j.kind,
+            payload: j.payload,
+            run_at: j.run_at,
+            max_attempts: j.max_attempts,
+            dedup_key: j.dedup_key,
+        }
+    }
+}
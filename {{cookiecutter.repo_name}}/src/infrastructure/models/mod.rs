@@ -1,2 +1,14 @@
 pub mod todo;
-pub mod service_context;
\ No newline at end of file
+pub mod service_context;
+pub mod idempotency;
+pub mod job;
+pub mod tag;
+pub mod todo_list;
+pub mod todo_item;
+pub mod comment;
+pub mod attachment;
+pub mod audit;
+pub mod user;
+pub mod api_key;
+pub mod share;
+pub mod tenant;
@@ -1,30 +1,22 @@
 use diesel;
 use diesel::prelude::*;
 use crate::domain::models::service_context::ServiceContext;
-use crate::infrastructure::schema::service_contexts;
 
 
-#[derive(Queryable, Insertable, AsChangeset)]
-#[diesel(table_name = service_contexts)]
+#[derive(Queryable)]
 pub struct ServiceContextDiesel {
     pub id: i32,
     pub maintenance: bool,
+    // Empty for the global context
+    pub tenant_id: String,
 }
 
 impl From<ServiceContextDiesel> for ServiceContext {
     fn from(service_context: ServiceContextDiesel) -> Self {
         ServiceContext {
             id: service_context.id,
+            tenant_id: Some(service_context.tenant_id).filter(|tenant_id| !tenant_id.is_empty()),
             maintenance: service_context.maintenance
         }
     }
 }
-
-impl From<ServiceContext> for ServiceContextDiesel {
-    fn from(service_context: ServiceContext) -> Self {
-        ServiceContextDiesel {
-            id: service_context.id,
-            maintenance: service_context.maintenance
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::share::{CreateTodoListShare, SharePermission, TodoListShare};
+use crate::infrastructure::schema::todo_list_shares;
+
+#[derive(Queryable)]
+pub struct TodoListShareDiesel {
+    pub id: i32,
+    pub list_id: i32,
+    pub user_id: Option<i32>,
+    pub group_name: Option<String>,
+    pub permission: String,
+    pub created_at: DateTime<Utc>,
+    pub tenant_id: String,
+}
+
+impl From<TodoListShareDiesel> for TodoListShare {
+    fn from(s: TodoListShareDiesel) -> Self {
+        TodoListShare {
+            id: s.id,
+            list_id: s.list_id,
+            user_id: s.user_id,
+            group: s.group_name,
+            // Permissions that are no longer known only grant reading
+            permission: s.permission.parse().unwrap_or(SharePermission::Read),
+            created_at: s.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = todo_list_shares)]
+pub struct CreateTodoListShareDiesel {
+    pub tenant_id: String,
+    pub list_id: i32,
+    pub user_id: Option<i32>,
+    pub group_name: Option<String>,
+    pub permission: String,
+}
+
+impl CreateTodoListShareDiesel {
+    pub fn new(tenant_id: String, list_id: i32, s: CreateTodoListShare) -> Self {
+        CreateTodoListShareDiesel {
+            tenant_id,
+            list_id,
+            user_id: s.user_id,
+            group_name: s.group,
+            permission: s.permission.as_str().to_string(),
+        }
+    }
+}
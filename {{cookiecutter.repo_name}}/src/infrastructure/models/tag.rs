@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::tag::{CreateTag, Tag, UpdateTag};
+use crate::infrastructure::schema::tags;
+
+#[derive(Queryable)]
+pub struct TagDiesel {
+    pub id: i32,
+    pub name: String,
+    pub color: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub tenant_id: String,
+}
+
+impl From<TagDiesel> for Tag {
+    fn from(t: TagDiesel) -> Self {
+        Tag {
+            id: t.id,
+            name: t.name,
+            color: t.color,
+            created_at: t.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = tags)]
+pub struct CreateTagDiesel {
+    pub tenant_id: String,
+    pub name: String,
+    pub color: Option<String>,
+}
+
+impl CreateTagDiesel {
+    pub fn new(tenant_id: String, t: CreateTag) -> Self {
+        CreateTagDiesel {
+            tenant_id,
+            name: t.name,
+            color: t.color,
+        }
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = tags)]
+pub struct UpdateTagDiesel {
+    pub name: Option<String>,
+    pub color: Option<Option<String>>,
+}
+
+impl From<UpdateTag> for UpdateTagDiesel {
+    fn from(t: UpdateTag) -> Self {
+        UpdateTagDiesel {
+            name: t.name,
+            color: t.color,
+        }
+    }
+}
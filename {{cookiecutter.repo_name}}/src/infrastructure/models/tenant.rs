@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::tenant::{CreateTenant, RegisteredTenant, UpdateTenant};
+use crate::infrastructure::schema::tenants;
+
+#[derive(Queryable)]
+pub struct TenantDiesel {
+    pub id: String,
+    pub allow_anonymous: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TenantDiesel> for RegisteredTenant {
+    fn from(t: TenantDiesel) -> Self {
+        RegisteredTenant {
+            id: t.id,
+            allow_anonymous: t.allow_anonymous,
+            created_at: t.created_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = tenants)]
+pub struct CreateTenantDiesel {
+    pub id: String,
+    pub allow_anonymous: bool,
+}
+
+impl From<CreateTenant> for CreateTenantDiesel {
+    fn from(t: CreateTenant) -> Self {
+        CreateTenantDiesel {
+            id: t.id,
+            allow_anonymous: t.allow_anonymous,
+        }
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = tenants)]
+pub struct UpdateTenantDiesel {
+    pub allow_anonymous: Option<bool>,
+}
+
+impl From<UpdateTenant> for UpdateTenantDiesel {
+    fn from(t: UpdateTenant) -> Self {
+        UpdateTenantDiesel {
+            allow_anonymous: t.allow_anonymous,
+        }
+    }
+}
@@ -1,14 +1,95 @@
+use std::io::Write;
+use chrono::{DateTime, Utc};
 use diesel;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
 use diesel::prelude::*;
-use crate::domain::models::todo::{CreateTodo, Todo};
-use crate::infrastructure::schema::todos;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Jsonb;
+use crate::domain::models::recurrence::Recurrence;
+use crate::domain::models::tenant::Tenant;
+use crate::domain::models::todo::{CreateTodo, Todo, TodoPriority, TodoStatus, UpdateTodo};
+use crate::infrastructure::schema::{sql_types, todos};
 
-#[derive(Queryable)]
+// Wrappers mapping the domain enums onto the Postgres `todo_status` and `todo_priority` types
+#[derive(Debug, Clone, Copy, AsExpression, FromSqlRow)]
+#[diesel(sql_type = sql_types::TodoStatus)]
+pub struct TodoStatusDiesel(pub TodoStatus);
+
+#[derive(Debug, Clone, Copy, AsExpression, FromSqlRow)]
+#[diesel(sql_type = sql_types::TodoPriority)]
+pub struct TodoPriorityDiesel(pub TodoPriority);
+
+// Stores a recurrence as the JSON it was given in
+#[derive(Debug, Clone, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Jsonb)]
+pub struct RecurrenceDiesel(pub Recurrence);
+
+impl ToSql<sql_types::TodoStatus, Pg> for TodoStatusDiesel {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.0.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<sql_types::TodoStatus, Pg> for TodoStatusDiesel {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(TodoStatusDiesel(std::str::from_utf8(bytes.as_bytes())?.parse()?))
+    }
+}
+
+impl ToSql<sql_types::TodoPriority, Pg> for TodoPriorityDiesel {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.0.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<sql_types::TodoPriority, Pg> for TodoPriorityDiesel {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(TodoPriorityDiesel(std::str::from_utf8(bytes.as_bytes())?.parse()?))
+    }
+}
+
+impl ToSql<Jsonb, Pg> for RecurrenceDiesel {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        // Version of the binary jsonb format
+        out.write_all(&[1])?;
+        serde_json::to_writer(out, &self.0)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Jsonb, Pg> for RecurrenceDiesel {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <serde_json::Value as FromSql<Jsonb, Pg>>::from_sql(bytes)?;
+        Ok(RecurrenceDiesel(serde_json::from_value(value)?))
+    }
+}
+
+#[derive(Clone, Queryable, QueryableByName)]
+#[diesel(table_name = todos)]
 pub struct TodoDiesel {
     pub id: i32,
     pub title: String,
     pub description: String,
     pub completed: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub version: i32,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub completed_by: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub remind_at: Option<DateTime<Utc>>,
+    pub status: TodoStatusDiesel,
+    pub priority: TodoPriorityDiesel,
+    pub list_id: Option<i32>,
+    pub position: i64,
+    pub recurrence: Option<RecurrenceDiesel>,
+    pub owner_id: Option<i32>,
+    pub tenant_id: String,
 }
 
 // Factory method for creating a new TodoDiesel from a Todo
@@ -19,15 +100,39 @@ impl From<Todo> for TodoDiesel {
             title: t.title,
             description: t.description,
             completed: t.completed,
+            created_at: t.created_at,
+            updated_at: t.updated_at,
+            deleted_at: t.deleted_at,
+            version: t.version,
+            completed_at: t.completed_at,
+            completed_by: t.completed_by,
+            due_at: t.due_at,
+            remind_at: t.remind_at,
+            status: TodoStatusDiesel(t.status),
+            priority: TodoPriorityDiesel(t.priority),
+            list_id: t.list_id,
+            position: t.position,
+            recurrence: t.recurrence.map(RecurrenceDiesel),
+            owner_id: t.owner_id,
+            tenant_id: Tenant::current_id(),
         }
     }
 }
 
+// `completed` and `completed_at` are derived from `status` by the `sync_completion` trigger
 #[derive(Insertable)]
 #[diesel(table_name = todos)]
 pub struct CreateTodoDiesel {
+    pub tenant_id: String,
     pub title: String,
     pub description: String,
+    pub due_at: Option<DateTime<Utc>>,
+    pub remind_at: Option<DateTime<Utc>>,
+    pub status: TodoStatusDiesel,
+    pub priority: TodoPriorityDiesel,
+    pub list_id: Option<i32>,
+    pub recurrence: Option<RecurrenceDiesel>,
+    pub owner_id: Option<i32>,
 }
 
 // Factory method for creating a new Todo from a TodoDiesel
@@ -38,26 +143,100 @@ impl Into<Todo> for TodoDiesel {
             title: self.title,
             description: self.description,
             completed: self.completed,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            deleted_at: self.deleted_at,
+            version: self.version,
+            completed_at: self.completed_at,
+            completed_by: self.completed_by,
+            due_at: self.due_at,
+            remind_at: self.remind_at,
+            status: self.status.0,
+            priority: self.priority.0,
+            list_id: self.list_id,
+            position: self.position,
+            recurrence: self.recurrence.map(|recurrence| recurrence.0),
+            owner_id: self.owner_id,
+            tags: Vec::new(),
+            done_items: 0,
+            total_items: 0,
+            comment_count: 0,
         }
     }
 }
 
-impl From<CreateTodo> for CreateTodoDiesel {
-    fn from(t: CreateTodo) -> Self {
+impl CreateTodoDiesel {
+    pub fn new(tenant_id: String, t: CreateTodo) -> Self {
         CreateTodoDiesel {
+            tenant_id,
             title: t.title,
             description: t.description,
+            due_at: t.due_at,
+            remind_at: t.remind_at,
+            status: TodoStatusDiesel(t.status),
+            priority: TodoPriorityDiesel(t.priority),
+            list_id: t.list_id,
+            recurrence: t.recurrence.map(RecurrenceDiesel),
+            owner_id: t.owner_id,
         }
     }
 }
 
 impl Into<Todo> for CreateTodoDiesel {
     fn into(self) -> Todo {
+        let now = Utc::now();
+        let completed = matches!(self.status.0, TodoStatus::Done);
         Todo {
             id: 0,
             title: self.title,
             description: self.description,
-            completed: false,
+            completed,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            version: 1,
+            completed_at: if completed { Some(now) } else { None },
+            completed_by: None,
+            due_at: self.due_at,
+            remind_at: self.remind_at,
+            status: self.status.0,
+            priority: self.priority.0,
+            list_id: self.list_id,
+            position: 0,
+            recurrence: self.recurrence.map(|recurrence| recurrence.0),
+            owner_id: self.owner_id,
+            tags: Vec::new(),
+            done_items: 0,
+            total_items: 0,
+            comment_count: 0,
+        }
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = todos)]
+pub struct UpdateTodoDiesel {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    pub remind_at: Option<Option<DateTime<Utc>>>,
+    pub status: Option<TodoStatusDiesel>,
+    pub priority: Option<TodoPriorityDiesel>,
+    pub list_id: Option<Option<i32>>,
+    pub recurrence: Option<Option<RecurrenceDiesel>>,
+}
+
+impl From<UpdateTodo> for UpdateTodoDiesel {
+    fn from(t: UpdateTodo) -> Self {
+        UpdateTodoDiesel {
+            title: t.title,
+            description: t.description,
+            due_at: t.due_at,
+            remind_at: t.remind_at,
+            status: t.status.map(TodoStatusDiesel),
+            priority: t.priority.map(TodoPriorityDiesel),
+            list_id: t.list_id,
+            recurrence: t.recurrence.map(|recurrence| recurrence.map(RecurrenceDiesel)),
         }
     }
-}
\ No newline at end of file
+}
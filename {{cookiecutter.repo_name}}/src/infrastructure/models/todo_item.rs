@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::todo_item::{TodoItem, UpdateTodoItem};
+use crate::infrastructure::schema::todo_items;
+
+#[derive(Queryable)]
+pub struct TodoItemDiesel {
+    pub id: i32,
+    pub todo_id: i32,
+    pub title: String,
+    pub position: i32,
+    pub done: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub tenant_id: String,
+}
+
+impl From<TodoItemDiesel> for TodoItem {
+    fn from(t: TodoItemDiesel) -> Self {
+        TodoItem {
+            id: t.id,
+            todo_id: t.todo_id,
+            title: t.title,
+            position: t.position,
+            done: t.done,
+            created_at: t.created_at,
+            updated_at: t.updated_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = todo_items)]
+pub struct CreateTodoItemDiesel {
+    pub todo_id: i32,
+    pub title: String,
+    pub position: i32,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = todo_items)]
+pub struct UpdateTodoItemDiesel {
+    pub title: Option<String>,
+    pub done: Option<bool>,
+}
+
+impl From<UpdateTodoItem> for UpdateTodoItemDiesel {
+    fn from(t: UpdateTodoItem) -> Self {
+        UpdateTodoItemDiesel {
+            title: t.title,
+            done: t.done,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use crate::domain::models::todo_list::{CreateTodoList, TodoList, UpdateTodoList};
+use crate::infrastructure::schema::todo_lists;
+
+#[derive(Queryable)]
+pub struct TodoListDiesel {
+    pub id: i32,
+    pub name: String,
+    pub description: String,
+    pub owner: Option<String>,
+    pub archived: bool,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub tenant_id: String,
+    pub owner_id: Option<i32>,
+}
+
+impl From<TodoListDiesel> for TodoList {
+    fn from(l: TodoListDiesel) -> Self {
+        TodoList {
+            id: l.id,
+            name: l.name,
+            description: l.description,
+            owner: l.owner,
+            owner_id: l.owner_id,
+            archived: l.archived,
+            archived_at: l.archived_at,
+            created_at: l.created_at,
+            updated_at: l.updated_at,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = todo_lists)]
+pub struct CreateTodoListDiesel {
+    pub tenant_id: String,
+    pub name: String,
+    pub description: String,
+    pub owner: Option<String>,
+    pub owner_id: Option<i32>,
+}
+
+impl CreateTodoListDiesel {
+    pub fn new(tenant_id: String, l: CreateTodoList) -> Self {
+        CreateTodoListDiesel {
+            tenant_id,
+            name: l.name,
+            description: l.description,
+            owner: l.owner,
+            owner_id: l.owner_id,
+        }
+    }
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name = todo_lists)]
+pub struct UpdateTodoListDiesel {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub owner: Option<Option<String>>,
+}
+
+impl From<UpdateTodoList> for UpdateTodoListDiesel {
+    fn from(l: UpdateTodoList) -> Self {
+        UpdateTodoListDiesel {
+            name: l.name,
+            description: l.description,
+            owner: l.owner,
+        }
+    }
+}
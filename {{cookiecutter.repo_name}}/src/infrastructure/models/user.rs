@@ -0,0 +1,100 @@
+use std::io::Write;
+use chrono::{DateTime, Utc};
+use diesel;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Varchar;
+use crate::domain::models::authorization::Role;
+use crate::domain::models::user::{CreateUser, User, UserCredentials};
+use crate::infrastructure::schema::users;
+
+#[derive(Debug, Clone, Copy, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Varchar)]
+pub struct RoleDiesel(pub Role);
+
+impl ToSql<Varchar, Pg> for RoleDiesel {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.0.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Varchar, Pg> for RoleDiesel {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(RoleDiesel(std::str::from_utf8(bytes.as_bytes())?.parse()?))
+    }
+}
+
+#[derive(Queryable)]
+pub struct UserDiesel {
+    pub id: i32,
+    pub email: String,
+    pub name: Option<String>,
+    pub password_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub role: RoleDiesel,
+    pub external_id: Option<String>,
+    pub tenant_id: String,
+    pub groups: Vec<String>,
+}
+
+impl From<UserDiesel> for User {
+    fn from(u: UserDiesel) -> Self {
+        User {
+            id: u.id,
+            email: u.email,
+            name: u.name,
+            role: u.role.0,
+            tenant_id: u.tenant_id,
+            groups: u.groups,
+            created_at: u.created_at,
+            updated_at: u.updated_at,
+        }
+    }
+}
+
+impl From<UserDiesel> for UserCredentials {
+    fn from(u: UserDiesel) -> Self {
+        UserCredentials {
+            user: User {
+                id: u.id,
+                email: u.email,
+                name: u.name,
+                role: u.role.0,
+                tenant_id: u.tenant_id,
+                groups: u.groups,
+                created_at: u.created_at,
+                updated_at: u.updated_at,
+            },
+            password_hash: u.password_hash,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = users)]
+pub struct CreateUserDiesel {
+    pub tenant_id: String,
+    pub email: String,
+    pub name: Option<String>,
+    pub password_hash: Option<String>,
+    pub role: RoleDiesel,
+    pub external_id: Option<String>,
+}
+
+impl CreateUserDiesel {
+    pub fn new(tenant_id: String, u: CreateUser) -> Self {
+        CreateUserDiesel {
+            tenant_id,
+            email: u.email,
+            name: u.name,
+            password_hash: u.password_hash,
+            role: RoleDiesel(u.role),
+            external_id: u.external_id,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+use crate::domain::models::tenant::Tenant;
+use crate::domain::models::api_key::{ApiKey, ApiKeyCredentials, NewApiKey};
+use crate::domain::repositories::api_key::ApiKeyRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::api_key::{ApiKeyDiesel, NewApiKeyDiesel};
+
+pub struct ApiKeyDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl ApiKeyDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        ApiKeyDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for ApiKeyDieselRepository {
+    async fn create(&self, new_api_key: &NewApiKey) -> RepositoryResult<ApiKey> {
+        use crate::infrastructure::schema::api_keys::dsl::api_keys;
+        let new_api_key_diesel = NewApiKeyDiesel::new(Tenant::current_id(), new_api_key.clone());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::insert_into(api_keys).values(new_api_key_diesel)
+                .get_result::<ApiKeyDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> ApiKey { v.into() })
+    }
+
+    async fn list_for_user(&self, owner_id: i32) -> RepositoryResult<Vec<ApiKey>> {
+        use crate::infrastructure::schema::api_keys::dsl::{api_keys, id, tenant_id, user_id};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            api_keys.filter(user_id.eq(owner_id)).filter(tenant_id.eq(tenant)).order(id.asc()).load::<ApiKeyDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(ApiKey::from).collect())
+    }
+
+    async fn get(&self, api_key_id: i32) -> RepositoryResult<ApiKey> {
+        use crate::infrastructure::schema::api_keys::dsl::{api_keys, id, tenant_id};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            api_keys.filter(id.eq(api_key_id)).filter(tenant_id.eq(tenant)).first::<ApiKeyDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> ApiKey { v.into() })
+    }
+
+    async fn find_credentials(&self, key_prefix: &str) -> RepositoryResult<Option<ApiKeyCredentials>> {
+        use crate::infrastructure::schema::api_keys::dsl::{api_keys, prefix};
+        let key_prefix = key_prefix.to_string();
+        // Prefixes are unique across tenants, the key tells which tenant it belongs to
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            api_keys.filter(prefix.eq(key_prefix)).first::<ApiKeyDiesel>(&mut conn).optional()
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.map(ApiKeyCredentials::from))
+    }
+
+    async fn revoke(&self, api_key_id: i32, at: DateTime<Utc>) -> RepositoryResult<ApiKey> {
+        use crate::infrastructure::schema::api_keys::dsl::{api_keys, id, revoked_at, tenant_id};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                // Revoking twice keeps the original time
+                diesel::update(api_keys.filter(id.eq(api_key_id)).filter(tenant_id.eq(&tenant)).filter(revoked_at.is_null()))
+                    .set(revoked_at.eq(Some(at)))
+                    .execute(conn)?;
+                api_keys.filter(id.eq(api_key_id)).filter(tenant_id.eq(&tenant)).first::<ApiKeyDiesel>(conn)
+            }).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> ApiKey { v.into() })
+    }
+
+    async fn mark_used(&self, api_key_id: i32, at: DateTime<Utc>) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::api_keys::dsl::{api_keys, id, last_used_at, tenant_id};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::update(api_keys.filter(id.eq(api_key_id)).filter(tenant_id.eq(tenant)))
+                .set(last_used_at.eq(Some(at)))
+                .execute(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|_| ())
+    }
+}
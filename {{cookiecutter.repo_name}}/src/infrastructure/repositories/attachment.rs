@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use serde_json::json;
+
+use crate::domain::constants::{DEFAULT_JOB_MAX_ATTEMPTS, DELETE_BLOBS_JOB};
+use crate::domain::models::attachment::{Attachment, CreateAttachment};
+use crate::domain::models::authorization::Principal;
+use crate::domain::models::tenant::Tenant;
+use crate::domain::repositories::attachment::AttachmentRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::attachment::{AttachmentDiesel, CreateAttachmentDiesel};
+use crate::infrastructure::models::job::CreateJobDiesel;
+use crate::infrastructure::repositories::todo::ensure_todo_exists;
+
+pub struct AttachmentDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl AttachmentDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        AttachmentDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for AttachmentDieselRepository {
+    async fn create(&self, todo_id: i32, new_attachment: &CreateAttachment) -> RepositoryResult<Attachment> {
+        use crate::infrastructure::schema::attachments::dsl::attachments;
+        let new_attachment = CreateAttachmentDiesel::new(todo_id, new_attachment.clone());
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                diesel::insert_into(attachments).values(new_attachment)
+                    .get_result::<AttachmentDiesel>(conn)
+                    .map_err(DieselRepositoryError::from)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Attachment { v.into() })
+    }
+
+    async fn list(&self, todo_id: i32) -> RepositoryResult<Vec<Attachment>> {
+        use crate::infrastructure::schema::attachments::dsl::{attachments, id};
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            ensure_todo_exists(&mut conn, &tenant, &principal, todo_id)?;
+            attachments.filter(attachment_of(todo_id))
+                .order(id.asc())
+                .load::<AttachmentDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|attachment| attachment.into()).collect())
+    }
+
+    async fn get(&self, todo_id: i32, attachment_id: i32) -> RepositoryResult<Attachment> {
+        use crate::infrastructure::schema::attachments::dsl::{attachments, id};
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            ensure_todo_exists(&mut conn, &tenant, &principal, todo_id)?;
+            attachments.filter(attachment_of(todo_id)).filter(id.eq(attachment_id))
+                .first::<AttachmentDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Attachment { v.into() })
+    }
+
+    async fn delete(&self, todo_id: i32, attachment_id: i32) -> RepositoryResult<Attachment> {
+        use crate::infrastructure::schema::attachments::dsl::{attachments, id};
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                diesel::delete(attachments.filter(attachment_of(todo_id)).filter(id.eq(attachment_id)))
+                    .get_result::<AttachmentDiesel>(conn)
+                    .map_err(DieselRepositoryError::from)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Attachment { v.into() })
+    }
+}
+
+type AttachmentOf = diesel::dsl::Eq<crate::infrastructure::schema::attachments::todo_id, i32>;
+
+fn attachment_of(todo_id: i32) -> AttachmentOf {
+    crate::infrastructure::schema::attachments::todo_id.eq(todo_id)
+}
+
+// Enqueues the removal of the content of the attachments of `todo_ids` within the caller's
+// transaction, before their rows go away with the todos
+pub fn enqueue_blob_cleanup(conn: &mut PgConnection, todo_ids: &[i32]) -> QueryResult<()> {
+    use crate::infrastructure::schema::attachments::dsl::{attachments, storage_key, todo_id};
+    use crate::infrastructure::schema::jobs::dsl::jobs;
+    let storage_keys = attachments.filter(todo_id.eq_any(todo_ids))
+        .select(storage_key)
+        .load::<String>(conn)?;
+    if storage_keys.is_empty() {
+        return Ok(());
+    }
+    diesel::insert_into(jobs)
+        .values(CreateJobDiesel {
+            kind: DELETE_BLOBS_JOB.to_string(),
+            payload: json!({ "storage_keys": storage_keys }),
+            run_at: Utc::now(),
+            max_attempts: DEFAULT_JOB_MAX_ATTEMPTS,
+            dedup_key: None,
+        })
+        .execute(conn)?;
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use serde_json::{json, Map, Value};
+
+use crate::domain::models::audit::{audit_changes, AuditAction, AuditContext, AuditEvent};
+use crate::domain::models::authorization::Principal;
+use crate::domain::models::tenant::Tenant;
+use crate::domain::repositories::audit::{AuditQueryParams, AuditRepository};
+use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging, SortOrder};
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::audit::{AuditActionDiesel, AuditEventDiesel, CreateAuditEventDiesel};
+use crate::infrastructure::models::todo::TodoDiesel;
+use crate::infrastructure::models::todo_item::TodoItemDiesel;
+use crate::infrastructure::repositories::todo_list::visible_lists;
+use crate::infrastructure::schema::{audit_events, todos};
+
+pub struct AuditDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl AuditDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        AuditDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for AuditDieselRepository {
+    async fn list(&self, params: AuditQueryParams) -> RepositoryResult<ResultPaging<AuditEvent>> {
+        use crate::infrastructure::schema::audit_events::dsl::id;
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let total_query = filtered_events(&tenant, &principal, &params);
+        let query = match params.sort_order.unwrap_or_default() {
+            SortOrder::Asc => filtered_events(&tenant, &principal, &params).order(id.asc()),
+            SortOrder::Desc => filtered_events(&tenant, &principal, &params).order(id.desc()),
+        }
+            .limit(params.limit())
+            .offset(params.offset());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let (total, result) = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            let total = total_query.count().get_result::<i64>(&mut conn)?;
+            let result = query.load::<AuditEventDiesel>(&mut conn)?;
+            Ok::<_, DieselRepositoryError>((total, result))
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(ResultPaging {
+            total,
+            items: result.into_iter().map(|v| v.into()).collect()
+        })
+    }
+}
+
+fn filtered_events(tenant: &str, principal: &Principal, params: &AuditQueryParams) -> audit_events::BoxedQuery<'static, Pg> {
+    use crate::infrastructure::schema::audit_events::dsl::{action, actor, audit_events, created_at, request_id, tenant_id, todo_id};
+    let mut builder = audit_events.filter(tenant_id.eq(tenant.to_string())).into_boxed();
+    // The history of todos in lists the principal may not see, or that were deleted, is hidden along with them
+    let hidden = todos::table
+        .select(todos::id)
+        .filter(todos::tenant_id.eq(tenant.to_string()))
+        .filter(todos::list_id.is_not_null())
+        .filter(diesel::dsl::not(todos::list_id.eq_any(visible_lists(tenant, principal))));
+    builder = builder.filter(diesel::dsl::not(todo_id.eq_any(hidden)));
+    if let Some(audited_todo_id) = params.todo_id {
+        builder = builder.filter(todo_id.eq(audited_todo_id));
+    }
+    if let Some(audit_actor) = params.actor.clone() {
+        builder = builder.filter(actor.eq(audit_actor));
+    }
+    if let Some(audit_action) = params.action {
+        builder = builder.filter(action.eq(AuditActionDiesel(audit_action)));
+    }
+    if let Some(audit_request_id) = params.request_id.clone() {
+        builder = builder.filter(request_id.eq(audit_request_id));
+    }
+    if let Some(since) = params.since {
+        builder = builder.filter(created_at.ge(since));
+    }
+    if let Some(until) = params.until {
+        builder = builder.filter(created_at.lt(until));
+    }
+    builder
+}
+
+// Appends an audit event within the caller's transaction. A mutation that ends up
+// changing none of the audited fields is not recorded.
+pub fn record_event(
+    conn: &mut PgConnection, context: &AuditContext, todo_id: i32, action: AuditAction,
+    before: Option<Value>, after: Option<Value>,
+) -> QueryResult<()> {
+    use crate::infrastructure::schema::audit_events::dsl::audit_events;
+    let changes = audit_changes(before.as_ref(), after.as_ref());
+    if changes.is_empty() && before.is_some() && after.is_some() {
+        return Ok(());
+    }
+    diesel::insert_into(audit_events)
+        .values(CreateAuditEventDiesel {
+            todo_id,
+            action: AuditActionDiesel(action),
+            actor: context.actor.clone(),
+            request_id: context.request_id.clone(),
+            changes: Value::Object(changes),
+        })
+        .execute(conn)?;
+    Ok(())
+}
+
+// The audited fields of a todo, leaving out bookkeeping such as `version` and `updated_at`
+pub fn todo_snapshot(todo: &TodoDiesel) -> Value {
+    json!({
+        "title": todo.title,
+        "description": todo.description,
+        "status": todo.status.0,
+        "priority": todo.priority.0,
+        "completed_at": todo.completed_at,
+        "completed_by": todo.completed_by,
+        "due_at": todo.due_at,
+        "remind_at": todo.remind_at,
+        "list_id": todo.list_id,
+        "position": todo.position,
+        "recurrence": todo.recurrence.as_ref().map(|recurrence| &recurrence.0),
+        "deleted_at": todo.deleted_at,
+    })
+}
+
+// Items are keyed by id, so the changes of an event tell which item was affected
+pub fn item_snapshot(item: &TodoItemDiesel) -> Value {
+    let mut snapshot = Map::new();
+    snapshot.insert(format!("items.{}", item.id), json!({
+        "title": item.title,
+        "done": item.done,
+        "position": item.position,
+    }));
+    Value::Object(snapshot)
+}
+
+pub fn item_order_snapshot(items: &[TodoItemDiesel]) -> Value {
+    json!({ "item_order": items.iter().map(|item| item.id).collect::<Vec<_>>() })
+}
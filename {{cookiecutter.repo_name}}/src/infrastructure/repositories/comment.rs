@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+
+use crate::domain::models::comment::{Comment, CreateComment, UpdateComment};
+use crate::domain::models::authorization::Principal;
+use crate::domain::models::tenant::Tenant;
+use crate::domain::repositories::comment::CommentRepository;
+use crate::domain::repositories::repository::{QueryParams, QueryParamsImpl, RepositoryResult, ResultPaging};
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::comment::{CommentDiesel, CreateCommentDiesel};
+use crate::infrastructure::repositories::todo::{ensure_todo_exists, touch_todo};
+
+pub struct CommentDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl CommentDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        CommentDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl CommentRepository for CommentDieselRepository {
+    async fn create(&self, todo_id: i32, new_comment: &CreateComment) -> RepositoryResult<Comment> {
+        use crate::infrastructure::schema::comments::dsl::comments;
+        let new_comment = CreateCommentDiesel {
+            todo_id,
+            author: new_comment.author.clone(),
+            author_id: new_comment.author_id,
+            body: new_comment.body.clone(),
+        };
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                let created = diesel::insert_into(comments).values(new_comment)
+                    .get_result::<CommentDiesel>(conn)?;
+                // The comment count is part of the todo's representation
+                touch_todo(conn, todo_id)?;
+                Ok::<_, DieselRepositoryError>(created)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Comment { v.into() })
+    }
+
+    async fn list(&self, todo_id: i32, params: QueryParamsImpl) -> RepositoryResult<ResultPaging<Comment>> {
+        use crate::infrastructure::schema::comments::dsl::{comments, created_at, id};
+        let (limit, offset) = (params.limit(), params.offset());
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let (total, result) = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            ensure_todo_exists(&mut conn, &tenant, &principal, todo_id)?;
+            let total = comments.filter(comment_of(todo_id)).count().get_result::<i64>(&mut conn)?;
+            let result = comments.filter(comment_of(todo_id))
+                .order((created_at.asc(), id.asc()))
+                .limit(limit)
+                .offset(offset)
+                .load::<CommentDiesel>(&mut conn)?;
+            Ok::<_, DieselRepositoryError>((total, result))
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(ResultPaging {
+            total,
+            items: result.into_iter().map(|v| v.into()).collect()
+        })
+    }
+
+    async fn get(&self, todo_id: i32, comment_id: i32) -> RepositoryResult<Comment> {
+        use crate::infrastructure::schema::comments::dsl::{comments, id};
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            ensure_todo_exists(&mut conn, &tenant, &principal, todo_id)?;
+            comments.filter(comment_of(todo_id)).filter(id.eq(comment_id))
+                .first::<CommentDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Comment { v.into() })
+    }
+
+    async fn update(&self, todo_id: i32, comment_id: i32, comment: &UpdateComment) -> RepositoryResult<Comment> {
+        use crate::infrastructure::schema::comments::dsl::{body, comments, id};
+        let new_body = comment.body.clone();
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            ensure_todo_exists(&mut conn, &tenant, &principal, todo_id)?;
+            diesel::update(comments.filter(comment_of(todo_id)).filter(id.eq(comment_id)))
+                .set(body.eq(new_body))
+                .get_result::<CommentDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Comment { v.into() })
+    }
+
+    async fn delete(&self, todo_id: i32, comment_id: i32) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::comments::dsl::{comments, id};
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                let deleted = diesel::delete(comments.filter(comment_of(todo_id)).filter(id.eq(comment_id)))
+                    .execute(conn)?;
+                if deleted == 0 {
+                    return Err(diesel::result::Error::NotFound.into());
+                }
+                touch_todo(conn, todo_id)?;
+                Ok::<_, DieselRepositoryError>(())
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+
+    async fn count_for_todos(&self, todo_ids: &[i32]) -> RepositoryResult<HashMap<i32, i64>> {
+        use crate::infrastructure::schema::comments::dsl::{comments, tenant_id, todo_id};
+        let todo_ids = todo_ids.to_vec();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let rows = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            comments
+                .filter(todo_id.eq_any(todo_ids))
+                .filter(tenant_id.eq(tenant))
+                .group_by(todo_id)
+                .select((todo_id, count_star()))
+                .load::<(i32, i64)>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(rows.into_iter().collect())
+    }
+}
+
+type CommentOf = diesel::dsl::Eq<crate::infrastructure::schema::comments::todo_id, i32>;
+
+fn comment_of(todo_id: i32) -> CommentOf {
+    crate::infrastructure::schema::comments::todo_id.eq(todo_id)
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+use crate::domain::models::idempotency::{IdempotencyKey, StoredResponse};
+use crate::domain::models::tenant::Tenant;
+use crate::domain::repositories::idempotency::IdempotencyRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::idempotency::{CreateIdempotencyKeyDiesel, IdempotencyKeyDiesel, StoredResponseDiesel};
+
+pub struct IdempotencyDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl IdempotencyDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        IdempotencyDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepository for IdempotencyDieselRepository {
+    async fn claim(&self, principal: &str, key: &str, request_fingerprint: &str, expires_at: DateTime<Utc>) -> RepositoryResult<Option<IdempotencyKey>> {
+        use crate::infrastructure::schema::idempotency_keys::dsl;
+        let new_key = CreateIdempotencyKeyDiesel {
+            tenant_id: Tenant::current_id(),
+            principal: principal.to_string(),
+            key: key.to_string(),
+            request_fingerprint: request_fingerprint.to_string(),
+            expires_at,
+        };
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                diesel::delete(dsl::idempotency_keys)
+                    .filter(dsl::tenant_id.eq(&new_key.tenant_id))
+                    .filter(dsl::principal.eq(&new_key.principal))
+                    .filter(dsl::key.eq(&new_key.key))
+                    .filter(dsl::expires_at.lt(Utc::now()))
+                    .execute(conn)?;
+                let claimed = diesel::insert_into(dsl::idempotency_keys)
+                    .values(&new_key)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                if claimed == 1 {
+                    return Ok(None);
+                }
+                dsl::idempotency_keys
+                    .filter(dsl::tenant_id.eq(&new_key.tenant_id))
+                    .filter(dsl::principal.eq(&new_key.principal))
+                    .filter(dsl::key.eq(&new_key.key))
+                    .first::<IdempotencyKeyDiesel>(conn)
+                    .map(Some)
+            }).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.map(|k| -> IdempotencyKey { k.into() }))
+    }
+
+    async fn store_response(&self, principal: &str, key: &str, response: &StoredResponse) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::idempotency_keys::dsl;
+        let principal = principal.to_string();
+        let key = key.to_string();
+        let response_diesel = StoredResponseDiesel::from(response.clone());
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::update(dsl::idempotency_keys)
+                .filter(dsl::tenant_id.eq(tenant)).filter(dsl::principal.eq(principal)).filter(dsl::key.eq(key))
+                .set(response_diesel)
+                .execute(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+
+    async fn release(&self, principal: &str, key: &str) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::idempotency_keys::dsl;
+        let principal = principal.to_string();
+        let key = key.to_string();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::delete(dsl::idempotency_keys)
+                .filter(dsl::tenant_id.eq(tenant)).filter(dsl::principal.eq(principal)).filter(dsl::key.eq(key))
+                .execute(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> RepositoryResult<usize> {
+        use crate::infrastructure::schema::idempotency_keys::dsl;
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            diesel::delete(dsl::idempotency_keys).filter(dsl::expires_at.lt(now))
+                .execute(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+}
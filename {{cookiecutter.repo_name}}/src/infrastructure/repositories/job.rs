@@ -0,0 +1,142 @@
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+use crate::domain::models::job::{CreateJob, Job, JobStatus};
+use crate::domain::repositories::job::JobRepository;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::job::{CreateJobDiesel, JobDiesel};
+
+pub struct JobDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl JobDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        JobDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl JobRepository for JobDieselRepository {
+    async fn enqueue(&self, new_job: &CreateJob) -> RepositoryResult<Option<Job>> {
+        use crate::infrastructure::schema::jobs::dsl::jobs;
+        let new_job_diesel = CreateJobDiesel::from(new_job.clone());
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            diesel::insert_into(jobs).values(new_job_diesel)
+                .on_conflict_do_nothing()
+                .get_result::<JobDiesel>(&mut conn)
+                .optional()
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.map(|job| -> Job { job.into() }))
+    }
+
+    async fn claim_next(&self, kinds: &[String], stale_before: DateTime<Utc>) -> RepositoryResult<Option<Job>> {
+        use crate::infrastructure::schema::jobs::dsl::{attempts, id, jobs, kind, locked_at, run_at, status};
+        let kinds = kinds.to_vec();
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            conn.transaction(|conn| {
+                let now = Utc::now();
+                let next = jobs
+                    .filter(kind.eq_any(&kinds))
+                    .filter(
+                        status.eq(JobStatus::Pending.as_str()).and(run_at.le(now))
+                            .or(status.eq(JobStatus::Running.as_str()).and(locked_at.lt(stale_before)))
+                    )
+                    .order(run_at.asc())
+                    .for_update()
+                    .skip_locked()
+                    .first::<JobDiesel>(conn)
+                    .optional()?;
+                match next {
+                    Some(job) => diesel::update(jobs.filter(id.eq(job.id)))
+                        .set((status.eq(JobStatus::Running.as_str()), locked_at.eq(now), attempts.eq(attempts + 1)))
+                        .get_result::<JobDiesel>(conn)
+                        .map(Some),
+                    None => Ok(None),
+                }
+            }).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.map(|job| -> Job { job.into() }))
+    }
+
+    async fn complete(&self, job_id: i64) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::jobs::dsl::{id, jobs, locked_at, status};
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            diesel::update(jobs.filter(id.eq(job_id)))
+                .set((status.eq(JobStatus::Completed.as_str()), locked_at.eq(None::<DateTime<Utc>>)))
+                .execute(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+
+    async fn retry(&self, job_id: i64, error: &str, retry_at: DateTime<Utc>) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::jobs::dsl::{id, jobs, last_error, locked_at, run_at, status};
+        let error = error.to_string();
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            diesel::update(jobs.filter(id.eq(job_id)))
+                .set((
+                    status.eq(JobStatus::Pending.as_str()),
+                    locked_at.eq(None::<DateTime<Utc>>),
+                    last_error.eq(error),
+                    run_at.eq(retry_at),
+                ))
+                .execute(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+
+    async fn fail(&self, job_id: i64, error: &str) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::jobs::dsl::{id, jobs, last_error, locked_at, status};
+        let error = error.to_string();
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            diesel::update(jobs.filter(id.eq(job_id)))
+                .set((status.eq(JobStatus::Failed.as_str()), locked_at.eq(None::<DateTime<Utc>>), last_error.eq(error)))
+                .execute(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+
+    async fn purge_finished(&self, finished_before: DateTime<Utc>) -> RepositoryResult<usize> {
+        use crate::infrastructure::schema::jobs::dsl::{jobs, status, updated_at};
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            diesel::delete(jobs)
+                .filter(status.eq_any([JobStatus::Completed.as_str(), JobStatus::Failed.as_str()]))
+                .filter(updated_at.lt(finished_before))
+                .execute(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+}
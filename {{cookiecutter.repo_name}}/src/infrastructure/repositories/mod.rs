@@ -1 +1,13 @@
-pub mod todo;
\ No newline at end of file
+pub mod todo;
+pub mod idempotency;
+pub mod job;
+pub mod tag;
+pub mod todo_list;
+pub mod todo_item;
+pub mod todo_search;
+pub mod comment;
+pub mod attachment;
+pub mod audit;
+pub mod user;
+pub mod api_key;
+pub mod tenant;
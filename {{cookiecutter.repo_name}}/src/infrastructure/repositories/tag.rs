@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+
+use crate::domain::error::{RepositoryError, NOT_FOUND_ERROR_CODE};
+use crate::domain::models::tag::{CreateTag, Tag, UpdateTag};
+use crate::domain::models::authorization::Principal;
+use crate::domain::models::tenant::Tenant;
+use crate::domain::repositories::repository::{QueryParams, QueryParamsImpl, RepositoryResult, ResultPaging};
+use crate::domain::repositories::tag::TagRepository;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::tag::{CreateTagDiesel, TagDiesel, UpdateTagDiesel};
+use crate::infrastructure::repositories::todo::{ensure_todo_exists, touch_todo};
+
+pub struct TagDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl TagDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        TagDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl TagRepository for TagDieselRepository {
+    async fn create(&self, new_tag: &CreateTag) -> RepositoryResult<Tag> {
+        use crate::infrastructure::schema::tags::dsl::tags;
+        let new_tag_diesel = CreateTagDiesel::new(Tenant::current_id(), new_tag.clone());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::insert_into(tags).values(new_tag_diesel)
+                .get_result::<TagDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Tag { v.into() })
+    }
+
+    async fn list(&self, params: QueryParamsImpl) -> RepositoryResult<ResultPaging<Tag>> {
+        use crate::infrastructure::schema::tags::dsl::{name, tags, tenant_id};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let (limit, offset) = (params.limit(), params.offset());
+        let result = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            tags.filter(tenant_id.eq(tenant)).order(name.asc()).limit(limit).offset(offset)
+                .load::<TagDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(ResultPaging {
+            total: 0,
+            items: result.into_iter().map(|v| v.into()).collect()
+        })
+    }
+
+    async fn get(&self, tag_id: i32) -> RepositoryResult<Tag> {
+        use crate::infrastructure::schema::tags::dsl::{id, tags, tenant_id};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            tags.filter(id.eq(tag_id)).filter(tenant_id.eq(tenant)).first::<TagDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Tag { v.into() })
+    }
+
+    async fn update(&self, tag_id: i32, tag: &UpdateTag) -> RepositoryResult<Tag> {
+        use crate::infrastructure::schema::tags::dsl::{id, tags, tenant_id};
+        let changes = UpdateTagDiesel::from(tag.clone());
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                let updated = diesel::update(tags.filter(id.eq(tag_id)).filter(tenant_id.eq(&tenant))).set(&changes)
+                    .get_result::<TagDiesel>(conn)?;
+                touch_tagged_todos(conn, &tenant, tag_id)?;
+                Ok::<_, diesel::result::Error>(updated)
+            }).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Tag { v.into() })
+    }
+
+    async fn delete(&self, tag_id: i32) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::tags::dsl::{id, tags, tenant_id};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                touch_tagged_todos(conn, &tenant, tag_id)?;
+                diesel::delete(tags.filter(id.eq(tag_id)).filter(tenant_id.eq(&tenant))).execute(conn)
+            }).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+
+    async fn list_for_todo(&self, todo_id: i32) -> RepositoryResult<Vec<Tag>> {
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            ensure_todo_exists(&mut conn, &tenant, &principal, todo_id)?;
+            load_todo_tags(&mut conn, todo_id).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|tag| tag.into()).collect())
+    }
+
+    async fn list_for_todos(&self, todo_ids: &[i32]) -> RepositoryResult<HashMap<i32, Vec<Tag>>> {
+        use crate::infrastructure::schema::{tags, todo_tags};
+        let todo_ids = todo_ids.to_vec();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let rows = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            todo_tags::table.inner_join(tags::table)
+                .filter(todo_tags::todo_id.eq_any(todo_ids))
+                .filter(tags::tenant_id.eq(tenant))
+                .select((todo_tags::todo_id, tags::all_columns))
+                .order(tags::name.asc())
+                .load::<(i32, TagDiesel)>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        let mut todo_tags: HashMap<i32, Vec<Tag>> = HashMap::new();
+        for (todo_id, tag) in rows {
+            todo_tags.entry(todo_id).or_default().push(tag.into());
+        }
+        Ok(todo_tags)
+    }
+
+    async fn attach(&self, todo_id: i32, tag_ids: &[i32]) -> RepositoryResult<Vec<Tag>> {
+        use crate::infrastructure::schema::tags;
+        use crate::infrastructure::schema::todo_tags;
+        let mut tag_ids = tag_ids.to_vec();
+        tag_ids.sort_unstable();
+        tag_ids.dedup();
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                let existing = tags::table.filter(tags::id.eq_any(&tag_ids)).filter(tags::tenant_id.eq(&tenant))
+                    .select(tags::id)
+                    .load::<i32>(conn)?;
+                if let Some(missing) = tag_ids.iter().find(|tag_id| !existing.contains(tag_id)) {
+                    return Err(RepositoryError {
+                        message: format!("Tag {} not found", missing),
+                        code: NOT_FOUND_ERROR_CODE,
+                    }.into());
+                }
+                let rows = tag_ids.iter()
+                    .map(|tag_id| (todo_tags::todo_id.eq(todo_id), todo_tags::tag_id.eq(*tag_id)))
+                    .collect::<Vec<_>>();
+                let attached = diesel::insert_into(todo_tags::table).values(rows)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                if attached > 0 {
+                    touch_todo(conn, todo_id)?;
+                }
+                load_todo_tags(conn, todo_id).map_err(DieselRepositoryError::from)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|tag| tag.into()).collect())
+    }
+
+    async fn detach(&self, todo_id: i32, tag_id: i32) -> RepositoryResult<Vec<Tag>> {
+        use crate::infrastructure::schema::todo_tags;
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                let detached = diesel::delete(todo_tags::table)
+                    .filter(todo_tags::todo_id.eq(todo_id))
+                    .filter(todo_tags::tag_id.eq(tag_id))
+                    .execute(conn)?;
+                if detached > 0 {
+                    touch_todo(conn, todo_id)?;
+                }
+                load_todo_tags(conn, todo_id).map_err(DieselRepositoryError::from)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|tag| tag.into()).collect())
+    }
+}
+
+fn load_todo_tags(conn: &mut PgConnection, todo_id: i32) -> QueryResult<Vec<TagDiesel>> {
+    use crate::infrastructure::schema::{tags, todo_tags};
+    todo_tags::table.inner_join(tags::table)
+        .filter(todo_tags::todo_id.eq(todo_id))
+        .select(tags::all_columns)
+        .order(tags::name.asc())
+        .load::<TagDiesel>(conn)
+}
+
+// Renaming or deleting a tag changes the representation of every todo carrying it
+fn touch_tagged_todos(conn: &mut PgConnection, tenant: &str, tag_id: i32) -> QueryResult<usize> {
+    use crate::infrastructure::schema::todo_tags;
+    use crate::infrastructure::schema::todos::dsl::{id, tenant_id, todos, updated_at};
+    let tagged = todo_tags::table.filter(todo_tags::tag_id.eq(tag_id)).select(todo_tags::todo_id);
+    diesel::update(todos.filter(id.eq_any(tagged)).filter(tenant_id.eq(tenant)))
+        .set(updated_at.eq(Utc::now()))
+        .execute(conn)
+}
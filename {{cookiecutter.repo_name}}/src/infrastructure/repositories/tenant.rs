@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::domain::models::tenant::{CreateTenant, RegisteredTenant, UpdateTenant};
+use crate::domain::repositories::repository::{QueryParams, QueryParamsImpl, RepositoryResult, ResultPaging};
+use crate::domain::repositories::tenant::TenantRepository;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::tenant::{CreateTenantDiesel, TenantDiesel, UpdateTenantDiesel};
+
+pub struct TenantDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl TenantDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        TenantDieselRepository { pool: db }
+    }
+}
+
+// Tenants are looked up before requests run within one, so connections see every tenant
+#[async_trait]
+impl TenantRepository for TenantDieselRepository {
+    async fn create(&self, new_tenant: &CreateTenant) -> RepositoryResult<RegisteredTenant> {
+        use crate::infrastructure::schema::tenants::dsl::tenants;
+        let new_tenant_diesel = CreateTenantDiesel::from(new_tenant.clone());
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            diesel::insert_into(tenants).values(new_tenant_diesel)
+                .get_result::<TenantDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> RegisteredTenant { v.into() })
+    }
+
+    async fn list(&self, params: QueryParamsImpl) -> RepositoryResult<ResultPaging<RegisteredTenant>> {
+        use crate::infrastructure::schema::tenants::dsl::{id, tenants};
+        let pool = self.pool.clone();
+        let (limit, offset) = (params.limit(), params.offset());
+        let result = run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            tenants.order(id.asc()).limit(limit).offset(offset)
+                .load::<TenantDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(ResultPaging {
+            total: 0,
+            items: result.into_iter().map(|v| v.into()).collect()
+        })
+    }
+
+    async fn get(&self, tenant_id: &str) -> RepositoryResult<RegisteredTenant> {
+        use crate::infrastructure::schema::tenants::dsl::{id, tenants};
+        let tenant_id = tenant_id.to_string();
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            tenants.filter(id.eq(tenant_id)).first::<TenantDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> RegisteredTenant { v.into() })
+    }
+
+    async fn update(&self, tenant_id: &str, tenant: &UpdateTenant) -> RepositoryResult<RegisteredTenant> {
+        use crate::infrastructure::schema::tenants::dsl::{id, tenants};
+        let tenant_id = tenant_id.to_string();
+        let changes = UpdateTenantDiesel::from(tenant.clone());
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            diesel::update(tenants.filter(id.eq(tenant_id))).set(&changes)
+                .get_result::<TenantDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> RegisteredTenant { v.into() })
+    }
+}
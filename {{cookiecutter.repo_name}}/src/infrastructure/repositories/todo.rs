@@ -1,14 +1,27 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use actix_threadpool::run;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use diesel::prelude::*;
 
-use crate::domain::models::todo::{CreateTodo, Todo};
-use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
-use crate::domain::repositories::todo::{TodoQueryParams, TodoRepository};
+use crate::domain::constants::TODO_POSITION_GAP;
+use crate::domain::error::{
+    RepositoryError, CONFLICT_ERROR_CODE, NOT_FOUND_ERROR_CODE, PRECONDITION_FAILED_ERROR_CODE, REPOSITORY_ERROR_CODE, VALIDATION_ERROR_CODE,
+};
+use crate::domain::models::audit::{AuditAction, AuditContext};
+use crate::domain::models::authorization::Principal;
+use crate::domain::models::tenant::Tenant;
+use crate::domain::models::todo::{CreateTodo, Todo, TodoOperation, TodoOwnership, TodoPlacement, TodoStatus, UpdateTodo};
+use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging, SortOrder};
+use crate::domain::repositories::todo::{TagMatch, TodoQueryParams, TodoRepository, TodoSortField};
 use crate::infrastructure::error::DieselRepositoryError;
 use crate::infrastructure::databases::postgresql::DBConn;
-use crate::infrastructure::models::todo::{CreateTodoDiesel, TodoDiesel};
+use crate::infrastructure::schema::{todo_lists, todos};
+use crate::infrastructure::models::todo::{CreateTodoDiesel, TodoDiesel, TodoPriorityDiesel, TodoStatusDiesel, UpdateTodoDiesel};
+use crate::infrastructure::repositories::attachment::enqueue_blob_cleanup;
+use crate::infrastructure::repositories::audit::{record_event, todo_snapshot};
+use crate::infrastructure::repositories::todo_list::visible_lists;
 
 pub struct TodoDieselRepository {
     pub pool: Arc<DBConn>
@@ -24,23 +37,93 @@ impl TodoDieselRepository {
 impl TodoRepository for TodoDieselRepository {
 
     async fn create(&self, new_todo: &CreateTodo) -> RepositoryResult<Todo> {
-        use crate::infrastructure::schema::todos::dsl::todos;
-        let new_todo_diesel: CreateTodoDiesel = CreateTodoDiesel::from(new_todo.clone());
-        let mut conn = self.pool.get().unwrap();
-        let result: TodoDiesel = run(move || diesel::insert_into(todos).values(new_todo_diesel)
-            .get_result(&mut conn))
+        let new_todo_diesel = CreateTodoDiesel::new(Tenant::current_id(), new_todo.clone());
+        let context = AuditContext::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let mut result = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| insert_todos(conn, &context, &[new_todo_diesel]))
+                .map_err(DieselRepositoryError::from)
+        })
             .await
             .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
-        Ok(result.into())
+        Ok(result.remove(0).into())
     }
 
     async fn list(&self, params: TodoQueryParams) -> RepositoryResult<ResultPaging<Todo>> {
-        use crate::infrastructure::schema::todos::dsl::todos;
+        use crate::infrastructure::schema::todos::dsl::{completed, created_at, deleted_at, due_at, id, list_id, owner_id, position, priority, status, tenant_id, todos, updated_at};
+        let tenant = Tenant::current_id();
+        let mut builder = todos.filter(tenant_id.eq(tenant.clone()))
+            .filter(list_id.is_null().or(list_id.eq_any(visible_lists(&tenant, &Principal::current()))))
+            .into_boxed();
+        if !params.include_deleted.unwrap_or(false) {
+            builder = builder.filter(deleted_at.is_null());
+        }
+        if let Some(after) = params.created_after {
+            builder = builder.filter(created_at.gt(after));
+        }
+        if let Some(since) = params.updated_since {
+            builder = builder.filter(updated_at.ge(since));
+        }
+        if params.overdue.unwrap_or(false) {
+            builder = builder.filter(due_at.lt(Utc::now())).filter(completed.eq(false));
+        }
+        if let Some(before) = params.due_before {
+            builder = builder.filter(due_at.lt(before));
+        }
+        if let Some(after) = params.due_after {
+            builder = builder.filter(due_at.gt(after));
+        }
+        if let Some(todo_status) = params.status {
+            builder = builder.filter(status.eq(TodoStatusDiesel(todo_status)));
+        }
+        if let Some(todo_priority) = params.priority {
+            builder = builder.filter(priority.eq(TodoPriorityDiesel(todo_priority)));
+        }
+        if let Some(todo_list_id) = params.list_id {
+            builder = builder.filter(list_id.eq(todo_list_id));
+        }
+        if let Some(todo_owner_id) = params.owner_id {
+            builder = builder.filter(owner_id.eq(todo_owner_id));
+        }
+        if !params.include_archived.unwrap_or(false) {
+            builder = builder.filter(diesel::dsl::not(in_archived_list()));
+        }
+        let tag_names = params.tag_names();
+        if !tag_names.is_empty() {
+            use crate::infrastructure::schema::{tags, todo_tags};
+            let tagged = |names: Vec<String>| diesel::dsl::exists(
+                todo_tags::table.inner_join(tags::table)
+                    .filter(todo_tags::todo_id.eq(id))
+                    .filter(tags::name.eq_any(names))
+            );
+            builder = match params.tags_match.unwrap_or_default() {
+                TagMatch::Any => builder.filter(tagged(tag_names)),
+                TagMatch::All => tag_names.into_iter()
+                    .fold(builder, |builder, name| builder.filter(tagged(vec![name]))),
+            };
+        }
+        builder = match (params.sort_by.unwrap_or_default(), params.sort_order.unwrap_or_default()) {
+            (TodoSortField::Id, SortOrder::Asc) => builder.order(id.asc()),
+            (TodoSortField::Id, SortOrder::Desc) => builder.order(id.desc()),
+            (TodoSortField::CreatedAt, SortOrder::Asc) => builder.order((created_at.asc(), id.asc())),
+            (TodoSortField::CreatedAt, SortOrder::Desc) => builder.order((created_at.desc(), id.desc())),
+            (TodoSortField::UpdatedAt, SortOrder::Asc) => builder.order((updated_at.asc(), id.asc())),
+            (TodoSortField::UpdatedAt, SortOrder::Desc) => builder.order((updated_at.desc(), id.desc())),
+            (TodoSortField::DueAt, SortOrder::Asc) => builder.order((due_at.asc().nulls_last(), id.asc())),
+            (TodoSortField::DueAt, SortOrder::Desc) => builder.order((due_at.desc().nulls_last(), id.desc())),
+            (TodoSortField::Priority, SortOrder::Asc) => builder.order((priority.asc(), id.asc())),
+            (TodoSortField::Priority, SortOrder::Desc) => builder.order((priority.desc(), id.desc())),
+            (TodoSortField::Position, SortOrder::Asc) => builder.order((position.asc(), id.asc())),
+            (TodoSortField::Position, SortOrder::Desc) => builder.order((position.desc(), id.desc())),
+        };
+        let builder = builder.limit(params.limit()).offset(params.offset());
         let pool = self.pool.clone();
-        let builder = todos.limit(params.limit()).offset(params.offset());
+        let tenant_tag = DBConn::tenant_tag();
         let result = run(move || {
-            let mut conn = pool.get().unwrap();
-            builder.load::<TodoDiesel>(&mut conn)
+            let mut conn = pool.get_for(&tenant_tag)?;
+            builder.load::<TodoDiesel>(&mut conn).map_err(DieselRepositoryError::from)
         })
             .await
             .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
@@ -51,21 +134,650 @@ impl TodoRepository for TodoDieselRepository {
     }
 
     async fn get(&self, todo_id: i32) -> RepositoryResult<Todo> {
-        use crate::infrastructure::schema::todos::dsl::{id, todos};
-        let mut conn = self.pool.get().unwrap();
-        run(move || todos.filter(id.eq(todo_id)).first::<TodoDiesel>(&mut conn))
+        use crate::infrastructure::schema::todos::dsl::{deleted_at, id, list_id, tenant_id, todos};
+        let tenant = Tenant::current_id();
+        let visible = visible_lists(&tenant, &Principal::current());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            todos.filter(id.eq(todo_id)).filter(tenant_id.eq(tenant)).filter(deleted_at.is_null())
+                .filter(list_id.is_null().or(list_id.eq_any(visible)))
+                .first::<TodoDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Todo { v.into() })
+    }
+
+    async fn ownership(&self, todo_id: i32) -> RepositoryResult<TodoOwnership> {
+        use crate::infrastructure::schema::todos::dsl::{id, list_id, owner_id, tenant_id, todos};
+        let tenant = Tenant::current_id();
+        let visible = visible_lists(&tenant, &Principal::current());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            todos.filter(id.eq(todo_id)).filter(tenant_id.eq(tenant))
+                .filter(list_id.is_null().or(list_id.eq_any(visible)))
+                .select((owner_id, list_id))
+                .first::<(Option<i32>, Option<i32>)>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|(todo_owner_id, todo_list_id)| TodoOwnership { owner_id: todo_owner_id, list_id: todo_list_id })
+    }
+
+    async fn update(&self, todo_id: i32, todo: &UpdateTodo, expected_version: Option<i32>) -> RepositoryResult<Todo> {
+        let changes = UpdateTodoDiesel::from(todo.clone());
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| update_audited(conn, &context, &tenant, todo_id, &changes, expected_version))
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Todo { v.into() })
+    }
+
+    async fn delete(&self, todo_id: i32, expected_version: Option<i32>) -> RepositoryResult<()> {
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| delete_todo(conn, &context, &tenant, todo_id, expected_version))
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+
+    async fn create_many(&self, new_todos: &[CreateTodo]) -> RepositoryResult<Vec<Todo>> {
+        let tenant = Tenant::current_id();
+        let new_todos_diesel: Vec<CreateTodoDiesel> = new_todos.iter()
+            .map(|new_todo| CreateTodoDiesel::new(tenant.clone(), new_todo.clone()))
+            .collect();
+        let context = AuditContext::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| insert_todos(conn, &context, &new_todos_diesel))
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|todo| todo.into()).collect())
+    }
+
+    async fn delete_many(&self, todo_ids: &[i32]) -> RepositoryResult<usize> {
+        let todo_ids = todo_ids.to_vec();
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| soft_delete_todos(conn, &context, &tenant, &todo_ids))
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+
+    async fn complete_many(&self, todo_ids: &[i32], completed_by: Option<String>) -> RepositoryResult<Vec<Todo>> {
+        let todo_ids = todo_ids.to_vec();
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| complete_todos(conn, &context, &tenant, &todo_ids, completed_by))
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|todo| todo.into()).collect())
+    }
+
+    async fn reopen(&self, todo_id: i32) -> RepositoryResult<Todo> {
+        use crate::infrastructure::schema::todos::dsl::{deleted_at, id, status, tenant_id, todos};
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| audited(conn, &context, &tenant, todo_id, AuditAction::Reopened, |conn| {
+                diesel::update(todos).filter(id.eq(todo_id)).filter(tenant_id.eq(&tenant)).filter(deleted_at.is_null())
+                    .set(status.eq(TodoStatusDiesel(TodoStatus::Todo)))
+                    .get_result::<TodoDiesel>(conn)
+                    .map_err(DieselRepositoryError::from)
+            }))
+        })
             .await
             .map_err(|v| DieselRepositoryError::from(v).into_inner())
             .map(|v| -> Todo { v.into() })
     }
 
-    async fn delete(&self, todo_id: i32) -> RepositoryResult<()> {
-        use crate::infrastructure::schema::todos::dsl::{id, todos};
-        let mut conn = self.pool.get().unwrap();
-        run(move || diesel::delete(todos).filter(id.eq(todo_id))
-            .execute(&mut conn))
+    async fn execute_batch(&self, operations: &[TodoOperation]) -> RepositoryResult<Vec<Option<Todo>>> {
+        let operations = operations.to_vec();
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| execute_operations(conn, &context, &tenant, &operations))
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|todo| todo.map(|todo| todo.into())).collect())
+    }
+
+    async fn restore(&self, todo_id: i32) -> RepositoryResult<Todo> {
+        use crate::infrastructure::schema::todos::dsl::{deleted_at, id, tenant_id, todos};
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                let before = lock_todos(conn, &tenant, &[todo_id])?
+                    .remove(&todo_id)
+                    .ok_or(diesel::result::Error::NotFound)?;
+                if before.deleted_at.is_none() {
+                    return Err(DieselRepositoryError::from(RepositoryError {
+                        message: format!("Todo {} is not deleted", todo_id),
+                        code: CONFLICT_ERROR_CODE,
+                    }));
+                }
+                // Todos of a deleted list go with it, there is no list to restore them into
+                if let Some(todo_list_id) = before.list_id {
+                    let list_deleted = todo_lists::table.filter(todo_lists::id.eq(todo_list_id))
+                        .select(todo_lists::deleted_at.is_not_null())
+                        .first::<bool>(conn)?;
+                    if list_deleted {
+                        return Err(DieselRepositoryError::from(RepositoryError {
+                            message: format!("List {} of todo {} is deleted", todo_list_id, todo_id),
+                            code: CONFLICT_ERROR_CODE,
+                        }));
+                    }
+                }
+                let after = diesel::update(todos).filter(id.eq(todo_id)).filter(tenant_id.eq(&tenant))
+                    .set(deleted_at.eq(None::<DateTime<Utc>>))
+                    .get_result::<TodoDiesel>(conn)?;
+                let (before, after_snapshot) = (todo_snapshot(&before), todo_snapshot(&after));
+                record_event(conn, &context, todo_id, AuditAction::Restored, Some(before), Some(after_snapshot))?;
+                Ok(after)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Todo { v.into() })
+    }
+
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> RepositoryResult<usize> {
+        use crate::infrastructure::schema::todos::dsl::{deleted_at, id, list_id, todos};
+        let context = AuditContext::current();
+        let pool = self.pool.clone();
+        // Purges todos of every tenant, then the lists deleted with them. Events are recorded first,
+        // they take the tenant of their todo.
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            conn.transaction(|conn| {
+                let purged_lists = todo_lists::table.filter(todo_lists::deleted_at.lt(deleted_before))
+                    .select(todo_lists::id.nullable())
+                    .for_update()
+                    .load::<Option<i32>>(conn)?;
+                let purged = todos.filter(deleted_at.lt(deleted_before).or(list_id.eq_any(&purged_lists)))
+                    .for_update()
+                    .load::<TodoDiesel>(conn)?;
+                for todo in &purged {
+                    record_event(conn, &context, todo.id, AuditAction::Purged, Some(todo_snapshot(todo)), None)?;
+                }
+                let purged_ids: Vec<i32> = purged.iter().map(|todo| todo.id).collect();
+                enqueue_blob_cleanup(conn, &purged_ids)?;
+                diesel::delete(todos.filter(id.eq_any(purged_ids))).execute(conn)?;
+                diesel::delete(todo_lists::table.filter(todo_lists::id.nullable().eq_any(purged_lists))).execute(conn)?;
+                Ok::<_, DieselRepositoryError>(purged.len())
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+
+    async fn list_due_reminders(&self, now: DateTime<Utc>, limit: i64) -> RepositoryResult<Vec<Todo>> {
+        use crate::infrastructure::schema::todo_reminders;
+        use crate::infrastructure::schema::todos::dsl::{completed, deleted_at, id, remind_at, todos};
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            todos
+                .filter(remind_at.le(now))
+                .filter(completed.eq(false))
+                .filter(deleted_at.is_null())
+                .filter(diesel::dsl::not(in_archived_list()))
+                .filter(diesel::dsl::not(diesel::dsl::exists(
+                    todo_reminders::table
+                        .filter(todo_reminders::todo_id.eq(id))
+                        .filter(todo_reminders::remind_at.nullable().eq(remind_at))
+                )))
+                .order(remind_at.asc())
+                .limit(limit)
+                .load::<TodoDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|todo| -> Todo { todo.into() }).collect())
+    }
+
+    async fn mark_reminded(&self, reminders: &[(i32, DateTime<Utc>)]) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::todo_reminders::dsl::{remind_at, todo_id, todo_reminders};
+        let rows = reminders.iter()
+            .map(|(reminded_todo_id, reminded_at)| (todo_id.eq(*reminded_todo_id), remind_at.eq(*reminded_at)))
+            .collect::<Vec<_>>();
+        let pool = self.pool.clone();
+        run(move || {
+            let mut conn = pool.get_across_tenants()?;
+            diesel::insert_into(todo_reminders).values(rows)
+                .on_conflict_do_nothing()
+                .execute(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
             .await
             .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
         Ok(())
     }
-}
\ No newline at end of file
+
+    async fn move_todo(&self, todo_id: i32, placement: TodoPlacement) -> RepositoryResult<Todo> {
+        use crate::infrastructure::schema::todos::dsl::{id, list_id, position, todos, version};
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| audited(conn, &context, &tenant, todo_id, AuditAction::Moved, |conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                let todo_list_id = todos.filter(id.eq(todo_id)).select(list_id).first::<Option<i32>>(conn)?;
+                lock_positions(conn, &tenant, todo_list_id)?;
+                let new_position = match free_position(conn, &tenant, &principal, todo_id, todo_list_id, placement)? {
+                    Some(new_position) => new_position,
+                    None => {
+                        renormalize_positions(conn, &tenant, todo_list_id)?;
+                        // Renormalized positions are `TODO_POSITION_GAP` apart, so this always finds room
+                        free_position(conn, &tenant, &principal, todo_id, todo_list_id, placement)?.ok_or_else(|| RepositoryError {
+                            message: format!("No room to move todo {}", todo_id),
+                            code: REPOSITORY_ERROR_CODE,
+                        })?
+                    }
+                };
+                // Only changing the position keeps the version, see `renormalize_positions`
+                diesel::update(todos.filter(id.eq(todo_id)))
+                    .set((position.eq(new_position), version.eq(version + 1)))
+                    .get_result::<TodoDiesel>(conn)
+                    .map_err(DieselRepositoryError::from)
+            }))
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> Todo { v.into() })
+    }
+}
+
+// Todos of lists the principal may not see do not exist as far as they are concerned
+pub fn ensure_todo_exists(conn: &mut PgConnection, tenant: &str, principal: &Principal, todo_id: i32) -> Result<(), DieselRepositoryError> {
+    use crate::infrastructure::schema::todos::dsl::{deleted_at, id, list_id, tenant_id, todos};
+    todos.filter(id.eq(todo_id)).filter(tenant_id.eq(tenant)).filter(deleted_at.is_null())
+        .filter(list_id.is_null().or(list_id.eq_any(visible_lists(tenant, principal))))
+        .select(id)
+        .first::<i32>(conn)?;
+    Ok(())
+}
+
+// Tags and checklist items are part of a todo's representation, so changing them bumps the
+// todo's version and `updated_at` to keep ETags and `updated_since` queries accurate.
+pub fn touch_todo(conn: &mut PgConnection, todo_id: i32) -> QueryResult<usize> {
+    use crate::infrastructure::schema::todos::dsl::{id, todos, updated_at};
+    diesel::update(todos.filter(id.eq(todo_id)))
+        .set(updated_at.eq(Utc::now()))
+        .execute(conn)
+}
+
+type ArchivedListOf = diesel::dsl::Filter<
+    diesel::dsl::Filter<todo_lists::table, diesel::dsl::Eq<diesel::dsl::Nullable<todo_lists::id>, todos::list_id>>,
+    diesel::dsl::Eq<todo_lists::archived, bool>,
+>;
+
+// Whether a todo belongs to an archived list, whose todos are hidden from listings and reminders
+pub fn in_archived_list() -> diesel::dsl::exists<ArchivedListOf> {
+    diesel::dsl::exists(
+        todo_lists::table
+            .filter(todo_lists::id.nullable().eq(todos::list_id))
+            .filter(todo_lists::archived.eq(true))
+    )
+}
+
+// A position strictly between the anchor and its neighbour on the requested side,
+// or `None` when there is no room left and positions have to be renormalized.
+// Positions order the todos of a list, so the anchor has to be in the same list and visible
+// to the principal. Deleted todos keep their place, so they are taken into account as well.
+fn free_position(
+    conn: &mut PgConnection, tenant: &str, principal: &Principal, todo_id: i32, todo_list_id: Option<i32>, placement: TodoPlacement,
+) -> Result<Option<i64>, DieselRepositoryError> {
+    use crate::infrastructure::schema::todos::dsl::{deleted_at, id, list_id, position, tenant_id, todos};
+    use diesel::dsl::{max, min};
+    use diesel::PgExpressionMethods;
+    let anchor_id = placement.anchor_id();
+    let (anchor_list_id, anchor) = todos.filter(id.eq(anchor_id)).filter(tenant_id.eq(tenant)).filter(deleted_at.is_null())
+        .filter(list_id.is_null().or(list_id.eq_any(visible_lists(tenant, principal))))
+        .select((list_id, position))
+        .first::<(Option<i32>, i64)>(conn)
+        .optional()?
+        .ok_or_else(|| RepositoryError {
+            message: format!("Todo {} not found", anchor_id),
+            code: NOT_FOUND_ERROR_CODE,
+        })?;
+    if anchor_list_id != todo_list_id {
+        return Err(RepositoryError {
+            message: format!("Todo {} is not in the same list as todo {}", anchor_id, todo_id),
+            code: VALIDATION_ERROR_CODE,
+        }.into());
+    }
+    let others = todos.filter(tenant_id.eq(tenant)).filter(list_id.is_not_distinct_from(todo_list_id))
+        .filter(id.ne(todo_id))
+        .filter(id.ne(anchor_id));
+    // Todos sharing the anchor's position leave no room on either side of it
+    let tied = others.filter(position.eq(anchor)).count().get_result::<i64>(conn)?;
+    if tied > 0 {
+        return Ok(None);
+    }
+    let neighbour = match placement {
+        TodoPlacement::Before(_) => others.filter(position.lt(anchor)).select(max(position)).first::<Option<i64>>(conn)?,
+        TodoPlacement::After(_) => others.filter(position.gt(anchor)).select(min(position)).first::<Option<i64>>(conn)?,
+    };
+    Ok(match (placement, neighbour) {
+        (TodoPlacement::Before(_), None) => Some(anchor - TODO_POSITION_GAP),
+        (TodoPlacement::After(_), None) => Some(anchor + TODO_POSITION_GAP),
+        (_, Some(neighbour)) if (anchor - neighbour).abs() > 1 => Some(anchor + (neighbour - anchor) / 2),
+        _ => None,
+    })
+}
+
+// Spreads the todos of a list `TODO_POSITION_GAP` apart again, keeping their order. Only rows
+// whose position actually changes are written, and they keep their version since only their
+// position changes. This is bookkeeping rather than a change anyone asked for, so it is not audited.
+fn renormalize_positions(conn: &mut PgConnection, tenant: &str, todo_list_id: Option<i32>) -> QueryResult<usize> {
+    diesel::sql_query(
+        "UPDATE todos SET position = ordered.rank * $1 \
+         FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY position, id) AS rank FROM todos \
+               WHERE tenant_id = $2 AND list_id IS NOT DISTINCT FROM $3) AS ordered \
+         WHERE todos.id = ordered.id AND todos.tenant_id = $2 AND todos.position <> ordered.rank * $1"
+    )
+        .bind::<diesel::sql_types::BigInt, _>(TODO_POSITION_GAP)
+        .bind::<diesel::sql_types::Text, _>(tenant)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(todo_list_id)
+        .execute(conn)
+}
+
+// Serializes position changes within a list until the end of the transaction, like the
+// `assign_position` trigger does for new todos
+fn lock_positions(conn: &mut PgConnection, tenant: &str, todo_list_id: Option<i32>) -> QueryResult<()> {
+    diesel::sql_query("SELECT todos_lock_positions($1, $2)")
+        .bind::<diesel::sql_types::Text, _>(tenant)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(todo_list_id)
+        .execute(conn)
+        .map(|_| ())
+}
+
+// The todos as they are before a mutation, locked until the end of the transaction
+fn lock_todos(conn: &mut PgConnection, tenant: &str, todo_ids: &[i32]) -> QueryResult<HashMap<i32, TodoDiesel>> {
+    use crate::infrastructure::schema::todos::dsl::{id, tenant_id, todos};
+    Ok(todos.filter(id.eq_any(todo_ids)).filter(tenant_id.eq(tenant))
+        .for_update()
+        .load::<TodoDiesel>(conn)?
+        .into_iter()
+        .map(|todo| (todo.id, todo))
+        .collect())
+}
+
+// Runs a mutation of a single todo, recording how it changed the todo
+fn audited<F>(
+    conn: &mut PgConnection, context: &AuditContext, tenant: &str, todo_id: i32, action: AuditAction, mutate: F,
+) -> Result<TodoDiesel, DieselRepositoryError>
+where
+    F: FnOnce(&mut PgConnection) -> Result<TodoDiesel, DieselRepositoryError>,
+{
+    let before = lock_todos(conn, tenant, &[todo_id])?.remove(&todo_id);
+    let after = mutate(conn)?;
+    record_event(conn, context, todo_id, action, before.as_ref().map(todo_snapshot), Some(todo_snapshot(&after)))?;
+    Ok(after)
+}
+
+fn record_changes(
+    conn: &mut PgConnection, context: &AuditContext, action: AuditAction,
+    before: &HashMap<i32, TodoDiesel>, after: &[TodoDiesel],
+) -> QueryResult<()> {
+    for todo in after {
+        record_event(conn, context, todo.id, action, before.get(&todo.id).map(todo_snapshot), Some(todo_snapshot(todo)))?;
+    }
+    Ok(())
+}
+
+// Called when a conditional mutation matched no rows: the todo either does not exist
+// (or is deleted) or its version moved on since the client last read it.
+fn version_mismatch_error(conn: &mut PgConnection, tenant: &str, todo_id: i32) -> DieselRepositoryError {
+    use crate::infrastructure::schema::todos::dsl::{deleted_at, id, tenant_id, todos};
+    let exists = todos.filter(id.eq(todo_id)).filter(tenant_id.eq(tenant)).filter(deleted_at.is_null())
+        .count()
+        .get_result::<i64>(conn);
+    match exists {
+        Ok(0) => DieselRepositoryError::from(diesel::result::Error::NotFound),
+        Ok(_) => DieselRepositoryError::from(RepositoryError {
+            message: format!("Todo {} has been modified", todo_id),
+            code: PRECONDITION_FAILED_ERROR_CODE,
+        }),
+        Err(e) => DieselRepositoryError::from(e),
+    }
+}
+
+fn update_todo(
+    conn: &mut PgConnection, tenant: &str, todo_id: i32, changes: &UpdateTodoDiesel, expected_version: Option<i32>,
+) -> Result<TodoDiesel, DieselRepositoryError> {
+    use crate::infrastructure::schema::todos::dsl::{deleted_at, id, tenant_id, todos, version};
+    let target = todos.filter(id.eq(todo_id)).filter(tenant_id.eq(tenant)).filter(deleted_at.is_null());
+    let updated = match expected_version {
+        Some(expected) => diesel::update(target.filter(version.eq(expected))).set(changes)
+            .get_result::<TodoDiesel>(conn).optional()?,
+        None => diesel::update(target).set(changes)
+            .get_result::<TodoDiesel>(conn).optional()?,
+    };
+    updated.ok_or_else(|| version_mismatch_error(conn, tenant, todo_id))
+}
+
+fn delete_todo(
+    conn: &mut PgConnection, context: &AuditContext, tenant: &str, todo_id: i32, expected_version: Option<i32>,
+) -> Result<(), DieselRepositoryError> {
+    use crate::infrastructure::schema::todos::dsl::{deleted_at, id, tenant_id, todos, version};
+    let before = lock_todos(conn, tenant, &[todo_id])?;
+    let target = todos.filter(id.eq(todo_id)).filter(tenant_id.eq(tenant)).filter(deleted_at.is_null());
+    let deleted = match expected_version {
+        Some(expected) => diesel::update(target.filter(version.eq(expected)))
+            .set(deleted_at.eq(Utc::now()))
+            .get_results::<TodoDiesel>(conn)?,
+        None => diesel::update(target)
+            .set(deleted_at.eq(Utc::now()))
+            .get_results::<TodoDiesel>(conn)?,
+    };
+    if deleted.is_empty() && expected_version.is_some() {
+        return Err(version_mismatch_error(conn, tenant, todo_id));
+    }
+    record_changes(conn, context, AuditAction::Deleted, &before, &deleted)?;
+    Ok(())
+}
+
+// Updates a todo, creating its next occurrence when the update completes it
+fn update_audited(
+    conn: &mut PgConnection, context: &AuditContext, tenant: &str, todo_id: i32, changes: &UpdateTodoDiesel,
+    expected_version: Option<i32>,
+) -> Result<TodoDiesel, DieselRepositoryError> {
+    let was_done = lock_todos(conn, tenant, &[todo_id])?
+        .get(&todo_id)
+        .is_some_and(|todo| todo.status.0 == TodoStatus::Done);
+    let todo = audited(conn, context, tenant, todo_id, AuditAction::Updated, |conn| {
+        update_todo(conn, tenant, todo_id, changes, expected_version)
+    })?;
+    if !was_done && todo.status.0 == TodoStatus::Done {
+        spawn_next_occurrences(conn, context, tenant, std::slice::from_ref(&todo))?;
+    }
+    Ok(todo)
+}
+
+// Creates the next occurrences of recurring todos that have just been completed, within the
+// transaction completing them so that a todo is never completed without its successor. The
+// successors keep the tags.
+fn spawn_next_occurrences(
+    conn: &mut PgConnection, context: &AuditContext, tenant: &str, completed: &[TodoDiesel],
+) -> Result<(), DieselRepositoryError> {
+    use crate::infrastructure::schema::todo_tags;
+    for todo in completed {
+        let todo: Todo = todo.clone().into();
+        let next = todo.next_occurrence().map_err(|message| RepositoryError {
+            message,
+            code: VALIDATION_ERROR_CODE,
+        })?;
+        let Some(next) = next else {
+            continue;
+        };
+        let next = insert_todos(conn, context, &[CreateTodoDiesel::new(tenant.to_string(), next)])?.remove(0);
+        diesel::insert_into(todo_tags::table)
+            .values(todo_tags::table.filter(todo_tags::todo_id.eq(todo.id)).select((next.id.into_sql::<diesel::sql_types::Integer>(), todo_tags::tag_id)))
+            .into_columns((todo_tags::todo_id, todo_tags::tag_id))
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+fn insert_todos(conn: &mut PgConnection, context: &AuditContext, new_todos: &[CreateTodoDiesel]) -> QueryResult<Vec<TodoDiesel>> {
+    use crate::infrastructure::schema::todos::dsl::todos;
+    if new_todos.is_empty() {
+        return Ok(Vec::new());
+    }
+    let created = diesel::insert_into(todos).values(new_todos).get_results(conn)?;
+    record_changes(conn, context, AuditAction::Created, &HashMap::new(), &created)?;
+    Ok(created)
+}
+
+// Deleting an already deleted or missing todo is not an error, mirroring `delete_todo`
+pub fn soft_delete_todos(conn: &mut PgConnection, context: &AuditContext, tenant: &str, todo_ids: &[i32]) -> QueryResult<usize> {
+    use crate::infrastructure::schema::todos::dsl::{deleted_at, id, tenant_id, todos};
+    let before = lock_todos(conn, tenant, todo_ids)?;
+    let deleted = diesel::update(todos.filter(id.eq_any(todo_ids)).filter(tenant_id.eq(tenant)).filter(deleted_at.is_null()))
+        .set(deleted_at.eq(Utc::now()))
+        .get_results::<TodoDiesel>(conn)?;
+    record_changes(conn, context, AuditAction::Deleted, &before, &deleted)?;
+    Ok(deleted.len())
+}
+
+// Returns the completed todos in the order of `todo_ids`, failing if any of them does not exist.
+// Todos that were already completed keep their original completion time and author.
+fn complete_todos(
+    conn: &mut PgConnection, context: &AuditContext, tenant: &str, todo_ids: &[i32], completed_by: Option<String>,
+) -> Result<Vec<TodoDiesel>, DieselRepositoryError> {
+    use crate::infrastructure::schema::todos::dsl;
+    use crate::infrastructure::schema::todos::dsl::{deleted_at, id, status, tenant_id, todos};
+    let before = lock_todos(conn, tenant, todo_ids)?;
+    let completed = diesel::update(todos.filter(id.eq_any(todo_ids)).filter(tenant_id.eq(tenant)).filter(deleted_at.is_null()).filter(status.ne(TodoStatusDiesel(TodoStatus::Done))))
+        .set((status.eq(TodoStatusDiesel(TodoStatus::Done)), dsl::completed_by.eq(completed_by)))
+        .get_results::<TodoDiesel>(conn)?;
+    record_changes(conn, context, AuditAction::Completed, &before, &completed)?;
+    spawn_next_occurrences(conn, context, tenant, &completed)?;
+    let mut completed_todos: HashMap<i32, TodoDiesel> = todos.filter(id.eq_any(todo_ids)).filter(tenant_id.eq(tenant)).filter(deleted_at.is_null())
+        .load::<TodoDiesel>(conn)?
+        .into_iter()
+        .map(|todo| (todo.id, todo))
+        .collect();
+    todo_ids.iter()
+        .map(|todo_id| completed_todos.remove(todo_id).ok_or_else(|| RepositoryError {
+            message: format!("Todo {} not found", todo_id),
+            code: NOT_FOUND_ERROR_CODE,
+        }.into()))
+        .collect()
+}
+
+// Consecutive operations of the same kind are sent as a single multi-row statement
+fn batches_with(operation: &TodoOperation, other: &TodoOperation) -> bool {
+    match (operation, other) {
+        (TodoOperation::Create(_), TodoOperation::Create(_))
+            | (TodoOperation::Delete { expected_version: None, .. }, TodoOperation::Delete { expected_version: None, .. }) => true,
+        (TodoOperation::Complete { completed_by, .. }, TodoOperation::Complete { completed_by: other_completed_by, .. }) => {
+            completed_by == other_completed_by
+        }
+        _ => false,
+    }
+}
+
+fn execute_operations(
+    conn: &mut PgConnection, context: &AuditContext, tenant: &str, operations: &[TodoOperation],
+) -> Result<Vec<Option<TodoDiesel>>, DieselRepositoryError> {
+    let mut results = Vec::with_capacity(operations.len());
+    let mut start = 0;
+    while start < operations.len() {
+        let end = start + operations[start..].iter().take_while(|op| batches_with(&operations[start], op)).count().max(1);
+        let batch = &operations[start..end];
+        let outcome = match &operations[start] {
+            TodoOperation::Create(_) => {
+                let new_todos: Vec<CreateTodoDiesel> = batch.iter().filter_map(|op| match op {
+                    TodoOperation::Create(new_todo) => Some(CreateTodoDiesel::new(tenant.to_string(), new_todo.clone())),
+                    _ => None,
+                }).collect();
+                insert_todos(conn, context, &new_todos)
+                    .map(|todos| results.extend(todos.into_iter().map(Some)))
+                    .map_err(DieselRepositoryError::from)
+            }
+            TodoOperation::Delete { todo_id, expected_version: Some(expected) } => {
+                delete_todo(conn, context, tenant, *todo_id, Some(*expected)).map(|_| results.push(None))
+            }
+            TodoOperation::Delete { expected_version: None, .. } => {
+                let todo_ids: Vec<i32> = batch.iter().filter_map(|op| match op {
+                    TodoOperation::Delete { todo_id, .. } => Some(*todo_id),
+                    _ => None,
+                }).collect();
+                soft_delete_todos(conn, context, tenant, &todo_ids)
+                    .map(|_| results.extend(todo_ids.iter().map(|_| None)))
+                    .map_err(DieselRepositoryError::from)
+            }
+            TodoOperation::Complete { completed_by, .. } => {
+                let todo_ids: Vec<i32> = batch.iter().filter_map(|op| match op {
+                    TodoOperation::Complete { todo_id, .. } => Some(*todo_id),
+                    _ => None,
+                }).collect();
+                complete_todos(conn, context, tenant, &todo_ids, completed_by.clone()).map(|todos| results.extend(todos.into_iter().map(Some)))
+            }
+            TodoOperation::Update { todo_id, todo, expected_version } => {
+                let changes = UpdateTodoDiesel::from(todo.clone());
+                update_audited(conn, context, tenant, *todo_id, &changes, *expected_version)
+                    .map(|todo| results.push(Some(todo)))
+            }
+        };
+        if let Err(e) = outcome {
+            let error = e.into_inner();
+            return Err(RepositoryError {
+                message: format!("Operation {} failed: {}", start, error.message),
+                code: error.code,
+            }.into());
+        }
+        start = end;
+    }
+    Ok(results)
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use diesel::dsl::{count_star, not};
+use diesel::prelude::*;
+
+use crate::domain::error::{RepositoryError, VALIDATION_ERROR_CODE};
+use crate::domain::models::audit::{AuditAction, AuditContext};
+use crate::domain::models::todo_item::{CreateTodoItem, TodoItem, TodoProgress, UpdateTodoItem};
+use crate::domain::models::authorization::Principal;
+use crate::domain::models::tenant::Tenant;
+use crate::domain::repositories::repository::RepositoryResult;
+use crate::domain::repositories::todo_item::TodoItemRepository;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::todo_item::{CreateTodoItemDiesel, TodoItemDiesel, UpdateTodoItemDiesel};
+use crate::infrastructure::repositories::audit::{item_order_snapshot, item_snapshot, record_event};
+use crate::infrastructure::repositories::todo::{ensure_todo_exists, touch_todo};
+
+pub struct TodoItemDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl TodoItemDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        TodoItemDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl TodoItemRepository for TodoItemDieselRepository {
+    async fn list(&self, todo_id: i32) -> RepositoryResult<Vec<TodoItem>> {
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            ensure_todo_exists(&mut conn, &tenant, &principal, todo_id)?;
+            load_todo_items(&mut conn, todo_id).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|item| item.into()).collect())
+    }
+
+    async fn create(&self, todo_id: i32, item: &CreateTodoItem) -> RepositoryResult<TodoItem> {
+        use crate::infrastructure::schema::todo_items::dsl::{position, todo_items};
+        let item = item.clone();
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                let count = count_items(conn, todo_id)?;
+                // Positions stay contiguous, so anything past the end is an append
+                let new_position = item.position.map_or(count, |p| p.clamp(0, count));
+                diesel::update(todo_items.filter(item_of(todo_id)).filter(position.ge(new_position)))
+                    .set(position.eq(position + 1))
+                    .execute(conn)?;
+                let created = diesel::insert_into(todo_items)
+                    .values(CreateTodoItemDiesel { todo_id, title: item.title, position: new_position })
+                    .get_result::<TodoItemDiesel>(conn)?;
+                touch_todo(conn, todo_id)?;
+                record_event(conn, &context, todo_id, AuditAction::ItemAdded, None, Some(item_snapshot(&created)))?;
+                Ok::<_, DieselRepositoryError>(created)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> TodoItem { v.into() })
+    }
+
+    async fn update(&self, todo_id: i32, item_id: i32, item: &UpdateTodoItem) -> RepositoryResult<TodoItem> {
+        use crate::infrastructure::schema::todo_items::dsl::{id, todo_items};
+        let changes = UpdateTodoItemDiesel::from(item.clone());
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                let before = lock_item(conn, todo_id, item_id)?;
+                let updated = diesel::update(todo_items.filter(item_of(todo_id)).filter(id.eq(item_id)))
+                    .set(&changes)
+                    .get_result::<TodoItemDiesel>(conn)?;
+                touch_todo(conn, todo_id)?;
+                record_event(conn, &context, todo_id, AuditAction::ItemUpdated, Some(item_snapshot(&before)), Some(item_snapshot(&updated)))?;
+                Ok::<_, DieselRepositoryError>(updated)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> TodoItem { v.into() })
+    }
+
+    async fn toggle(&self, todo_id: i32, item_id: i32) -> RepositoryResult<TodoItem> {
+        use crate::infrastructure::schema::todo_items::dsl::{done, id, todo_items};
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                let before = lock_item(conn, todo_id, item_id)?;
+                let toggled = diesel::update(todo_items.filter(item_of(todo_id)).filter(id.eq(item_id)))
+                    .set(done.eq(not(done)))
+                    .get_result::<TodoItemDiesel>(conn)?;
+                touch_todo(conn, todo_id)?;
+                record_event(conn, &context, todo_id, AuditAction::ItemUpdated, Some(item_snapshot(&before)), Some(item_snapshot(&toggled)))?;
+                Ok::<_, DieselRepositoryError>(toggled)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> TodoItem { v.into() })
+    }
+
+    async fn delete(&self, todo_id: i32, item_id: i32) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::todo_items::dsl::{id, position, todo_items};
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                let deleted = diesel::delete(todo_items.filter(item_of(todo_id)).filter(id.eq(item_id)))
+                    .get_result::<TodoItemDiesel>(conn)?;
+                // Close the gap left by the deleted item
+                diesel::update(todo_items.filter(item_of(todo_id)).filter(position.gt(deleted.position)))
+                    .set(position.eq(position - 1))
+                    .execute(conn)?;
+                touch_todo(conn, todo_id)?;
+                record_event(conn, &context, todo_id, AuditAction::ItemDeleted, Some(item_snapshot(&deleted)), None)?;
+                Ok::<_, DieselRepositoryError>(())
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+
+    async fn reorder(&self, todo_id: i32, item_ids: &[i32]) -> RepositoryResult<Vec<TodoItem>> {
+        use crate::infrastructure::schema::todo_items::dsl::{id, position, todo_items};
+        let item_ids = item_ids.to_vec();
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                ensure_todo_exists(conn, &tenant, &principal, todo_id)?;
+                let before = load_todo_items(conn, todo_id)?;
+                let mut existing = before.iter().map(|item| item.id).collect::<Vec<_>>();
+                let mut requested = item_ids.clone();
+                existing.sort_unstable();
+                requested.sort_unstable();
+                if existing != requested {
+                    return Err(RepositoryError {
+                        message: format!("Reordering must list each item of todo {} exactly once", todo_id),
+                        code: VALIDATION_ERROR_CODE,
+                    }.into());
+                }
+                for (index, item_id) in item_ids.iter().enumerate() {
+                    diesel::update(todo_items.filter(id.eq(item_id)))
+                        .set(position.eq(index as i32))
+                        .execute(conn)?;
+                }
+                touch_todo(conn, todo_id)?;
+                let after = load_todo_items(conn, todo_id)?;
+                record_event(conn, &context, todo_id, AuditAction::ItemsReordered, Some(item_order_snapshot(&before)), Some(item_order_snapshot(&after)))?;
+                Ok::<_, DieselRepositoryError>(after)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|item| item.into()).collect())
+    }
+
+    async fn progress(&self, todo_ids: &[i32]) -> RepositoryResult<HashMap<i32, TodoProgress>> {
+        use crate::infrastructure::schema::todo_items::dsl::{done, tenant_id, todo_id, todo_items};
+        let todo_ids = todo_ids.to_vec();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let rows = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            todo_items
+                .filter(todo_id.eq_any(todo_ids))
+                .filter(tenant_id.eq(tenant))
+                .group_by((todo_id, done))
+                .select((todo_id, done, count_star()))
+                .load::<(i32, bool, i64)>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        let mut progress: HashMap<i32, TodoProgress> = HashMap::new();
+        for (id, is_done, count) in rows {
+            let entry = progress.entry(id).or_default();
+            entry.total_items += count;
+            if is_done {
+                entry.done_items += count;
+            }
+        }
+        Ok(progress)
+    }
+}
+
+type ItemOf = diesel::dsl::Eq<crate::infrastructure::schema::todo_items::todo_id, i32>;
+
+fn item_of(todo_id: i32) -> ItemOf {
+    crate::infrastructure::schema::todo_items::todo_id.eq(todo_id)
+}
+
+fn count_items(conn: &mut PgConnection, todo_id: i32) -> QueryResult<i32> {
+    use crate::infrastructure::schema::todo_items::dsl::todo_items;
+    todo_items.filter(item_of(todo_id)).count().get_result::<i64>(conn).map(|count| count as i32)
+}
+
+fn lock_item(conn: &mut PgConnection, todo_id: i32, item_id: i32) -> QueryResult<TodoItemDiesel> {
+    use crate::infrastructure::schema::todo_items::dsl::{id, todo_items};
+    todo_items.filter(item_of(todo_id)).filter(id.eq(item_id))
+        .for_update()
+        .first::<TodoItemDiesel>(conn)
+}
+
+fn load_todo_items(conn: &mut PgConnection, todo_id: i32) -> QueryResult<Vec<TodoItemDiesel>> {
+    use crate::infrastructure::schema::todo_items::dsl::{position, todo_items};
+    todo_items.filter(item_of(todo_id))
+        .order(position.asc())
+        .load::<TodoItemDiesel>(conn)
+}
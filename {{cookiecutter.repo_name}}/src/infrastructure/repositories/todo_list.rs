@@ -0,0 +1,357 @@
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::{Integer, Nullable};
+
+use crate::domain::error::{RepositoryError, CONFLICT_ERROR_CODE, NOT_FOUND_ERROR_CODE};
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::authorization::{Permission, Principal};
+use crate::domain::models::share::{AccessibleTodoList, CreateTodoListShare, ListAccess, SharePermission, TodoListShare};
+use crate::domain::models::tenant::Tenant;
+use crate::domain::models::todo_list::{CreateTodoList, TodoList, UpdateTodoList};
+use crate::domain::repositories::repository::{QueryParams, QueryParamsImpl, RepositoryResult, ResultPaging};
+use crate::domain::repositories::todo_list::{TodoListQueryParams, TodoListRepository};
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::share::{CreateTodoListShareDiesel, TodoListShareDiesel};
+use crate::infrastructure::models::todo_list::{CreateTodoListDiesel, TodoListDiesel, UpdateTodoListDiesel};
+use crate::infrastructure::repositories::todo::soft_delete_todos;
+use crate::infrastructure::schema::{todo_list_shares, todo_lists};
+
+pub struct TodoListDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl TodoListDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        TodoListDieselRepository { pool: db }
+    }
+}
+
+// The ids of the lists shared with the principal, directly or through one of their groups
+fn shared_lists(principal: &Principal) -> todo_list_shares::BoxedQuery<'static, Pg, Integer> {
+    todo_list_shares::table
+        .select(todo_list_shares::list_id)
+        .filter(todo_list_shares::user_id.eq(principal.user_id)
+            .or(todo_list_shares::group_name.eq_any(principal.groups.clone())))
+        .into_boxed()
+}
+
+// The ids of the lists of the tenant the principal may see: those nobody owns, their own and
+// those shared with them. Admins see every list. Deleted lists are seen by nobody. Todos of
+// lists they may not see are hidden as well, so this is meant to be compared with `todos.list_id`.
+pub fn visible_lists(tenant: &str, principal: &Principal) -> todo_lists::BoxedQuery<'static, Pg, Nullable<Integer>> {
+    let lists = todo_lists::table
+        .select(todo_lists::id.nullable())
+        .filter(todo_lists::tenant_id.eq(tenant.to_string()))
+        .filter(todo_lists::deleted_at.is_null())
+        .into_boxed();
+    if principal.can(Permission::ModifyAnyTodo) {
+        return lists;
+    }
+    lists.filter(todo_lists::owner_id.is_null()
+        .or(todo_lists::owner_id.eq(principal.user_id))
+        .or(todo_lists::id.eq_any(shared_lists(principal))))
+}
+
+// The strongest permission the principal has been granted on a list
+fn shared_permission(conn: &mut PgConnection, list_id: i32, principal: &Principal) -> QueryResult<Option<SharePermission>> {
+    let permissions = shared_lists(principal)
+        .filter(todo_list_shares::list_id.eq(list_id))
+        .select(todo_list_shares::permission)
+        .load::<String>(conn)?;
+    Ok(permissions.iter().filter_map(|permission| permission.parse().ok()).max())
+}
+
+#[async_trait]
+impl TodoListRepository for TodoListDieselRepository {
+    async fn create(&self, new_list: &CreateTodoList) -> RepositoryResult<TodoList> {
+        use crate::infrastructure::schema::todo_lists::dsl::todo_lists;
+        let new_list_diesel = CreateTodoListDiesel::new(Tenant::current_id(), new_list.clone());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::insert_into(todo_lists).values(new_list_diesel)
+                .get_result::<TodoListDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> TodoList { v.into() })
+    }
+
+    async fn list(&self, params: TodoListQueryParams) -> RepositoryResult<ResultPaging<TodoList>> {
+        use crate::infrastructure::schema::todo_lists::dsl::{archived, id, owner, tenant_id, todo_lists};
+        let tenant = Tenant::current_id();
+        let mut builder = todo_lists.filter(tenant_id.eq(tenant.clone()))
+            .filter(id.nullable().eq_any(visible_lists(&tenant, &Principal::current())))
+            .into_boxed();
+        if !params.include_archived.unwrap_or(false) {
+            builder = builder.filter(archived.eq(false));
+        }
+        if let Some(list_owner) = params.owner.clone() {
+            builder = builder.filter(owner.eq(list_owner));
+        }
+        let builder = builder.order(id.asc()).limit(params.limit()).offset(params.offset());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let result = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            builder.load::<TodoListDiesel>(&mut conn).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(ResultPaging {
+            total: 0,
+            items: result.into_iter().map(|v| v.into()).collect()
+        })
+    }
+
+    async fn list_accessible(&self, params: QueryParamsImpl) -> RepositoryResult<ResultPaging<AccessibleTodoList>> {
+        use crate::infrastructure::schema::todo_lists::dsl::{deleted_at, id, owner_id, tenant_id, todo_lists};
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let result = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            let lists = todo_lists.filter(tenant_id.eq(&tenant)).filter(deleted_at.is_null())
+                .filter(owner_id.eq(principal.user_id).or(id.eq_any(shared_lists(&principal))))
+                .order(id.asc())
+                .limit(params.limit())
+                .offset(params.offset())
+                .load::<TodoListDiesel>(&mut conn)?;
+            let list_ids = lists.iter().map(|list| list.id).collect::<Vec<_>>();
+            let shares = shared_lists(&principal)
+                .filter(todo_list_shares::list_id.eq_any(list_ids))
+                .select((todo_list_shares::list_id, todo_list_shares::permission))
+                .load::<(i32, String)>(&mut conn)?;
+            Ok::<_, DieselRepositoryError>(lists.into_iter().map(|list| {
+                let shared = if list.owner_id.is_some() && list.owner_id == principal.user_id {
+                    None
+                } else {
+                    shares.iter()
+                        .filter(|(list_id, _)| *list_id == list.id)
+                        .filter_map(|(_, permission)| permission.parse().ok())
+                        .max()
+                };
+                AccessibleTodoList { list: list.into(), shared }
+            }).collect::<Vec<_>>())
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(ResultPaging {
+            total: 0,
+            items: result,
+        })
+    }
+
+    async fn get(&self, list_id: i32) -> RepositoryResult<TodoList> {
+        use crate::infrastructure::schema::todo_lists::dsl::{id, tenant_id, todo_lists};
+        let tenant = Tenant::current_id();
+        let visible = visible_lists(&tenant, &Principal::current());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            todo_lists.filter(id.eq(list_id)).filter(tenant_id.eq(tenant)).filter(id.nullable().eq_any(visible))
+                .first::<TodoListDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> TodoList { v.into() })
+    }
+
+    async fn access(&self, list_id: i32) -> RepositoryResult<ListAccess> {
+        use crate::infrastructure::schema::todo_lists::dsl::{id, owner_id, tenant_id, todo_lists};
+        let tenant = Tenant::current_id();
+        let principal = Principal::current();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            let list_owner = todo_lists.filter(id.eq(list_id)).filter(tenant_id.eq(&tenant))
+                .filter(id.nullable().eq_any(visible_lists(&tenant, &principal)))
+                .select(owner_id)
+                .first::<Option<i32>>(&mut conn)?;
+            let shared = shared_permission(&mut conn, list_id, &principal)?;
+            Ok::<_, DieselRepositoryError>(ListAccess { owner_id: list_owner, shared })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+    }
+
+    async fn update(&self, list_id: i32, list: &UpdateTodoList) -> RepositoryResult<TodoList> {
+        use crate::infrastructure::schema::todo_lists::dsl::{deleted_at, id, tenant_id, todo_lists};
+        let changes = UpdateTodoListDiesel::from(list.clone());
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::update(todo_lists.filter(id.eq(list_id)).filter(tenant_id.eq(tenant)).filter(deleted_at.is_null())).set(&changes)
+                .get_result::<TodoListDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> TodoList { v.into() })
+    }
+
+    async fn delete(&self, list_id: i32, cascade: bool) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::todo_lists::dsl::{deleted_at, id, tenant_id, todo_lists};
+        use crate::infrastructure::schema::todos;
+        let context = AuditContext::current();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                let deleted_list = todo_lists.filter(id.eq(list_id)).filter(tenant_id.eq(&tenant)).filter(deleted_at.is_null())
+                    .select(id)
+                    .for_update()
+                    .first::<i32>(conn)?;
+                if !cascade {
+                    let remaining = todos::table
+                        .filter(todos::list_id.eq(deleted_list))
+                        .filter(todos::deleted_at.is_null())
+                        .count()
+                        .get_result::<i64>(conn)?;
+                    if remaining > 0 {
+                        return Err(RepositoryError {
+                            message: format!("List {} still has {} todos", list_id, remaining),
+                            code: CONFLICT_ERROR_CODE,
+                        }.into());
+                    }
+                }
+                // The list is soft-deleted along with its todos, which stay in it and are hidden with it
+                // until they are purged together
+                let list_todo_ids = todos::table
+                    .filter(todos::list_id.eq(deleted_list))
+                    .select(todos::id)
+                    .load::<i32>(conn)?;
+                soft_delete_todos(conn, &context, &tenant, &list_todo_ids)?;
+                diesel::update(todo_lists.filter(id.eq(deleted_list)))
+                    .set(deleted_at.eq(Utc::now()))
+                    .execute(conn)
+                    .map_err(DieselRepositoryError::from)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(())
+    }
+
+    async fn set_archived(&self, list_id: i32, is_archived: bool) -> RepositoryResult<TodoList> {
+        use crate::infrastructure::schema::todo_lists::dsl::{archived, archived_at, deleted_at, id, tenant_id, todo_lists};
+        let archived_time: Option<DateTime<Utc>> = if is_archived { Some(Utc::now()) } else { None };
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                // Keep the original archive time when archiving twice
+                let updated = diesel::update(todo_lists.filter(id.eq(list_id)).filter(tenant_id.eq(&tenant)).filter(deleted_at.is_null())
+                    .filter(archived.ne(is_archived)))
+                    .set((archived.eq(is_archived), archived_at.eq(archived_time)))
+                    .get_result::<TodoListDiesel>(conn)
+                    .optional()?;
+                match updated {
+                    Some(list) => Ok(list),
+                    None => todo_lists.filter(id.eq(list_id)).filter(tenant_id.eq(&tenant)).filter(deleted_at.is_null())
+                        .first::<TodoListDiesel>(conn),
+                }
+            }).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> TodoList { v.into() })
+    }
+
+    async fn list_shares(&self, list_id: i32) -> RepositoryResult<Vec<TodoListShare>> {
+        use crate::infrastructure::schema::todo_list_shares::dsl::{id, list_id as share_list_id, tenant_id, todo_list_shares};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            todo_list_shares.filter(share_list_id.eq(list_id)).filter(tenant_id.eq(tenant))
+                .order(id.asc())
+                .load::<TodoListShareDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.into_iter().map(|share| share.into()).collect())
+    }
+
+    async fn share(&self, list_id: i32, share: &CreateTodoListShare) -> RepositoryResult<TodoListShare> {
+        use crate::infrastructure::schema::todo_list_shares::dsl::{group_name, list_id as share_list_id, permission, todo_list_shares, user_id};
+        use crate::infrastructure::schema::users;
+        let new_share = CreateTodoListShareDiesel::new(Tenant::current_id(), list_id, share.clone());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                // Users of other tenants are as good as unknown
+                if let Some(shared_with) = new_share.user_id {
+                    users::table.filter(users::id.eq(shared_with)).filter(users::tenant_id.eq(&new_share.tenant_id))
+                        .select(users::id)
+                        .first::<i32>(conn)
+                        .optional()?
+                        .ok_or_else(|| RepositoryError {
+                            message: format!("User {} not found", shared_with),
+                            code: NOT_FOUND_ERROR_CODE,
+                        })?;
+                }
+                let existing = todo_list_shares.filter(share_list_id.eq(list_id))
+                    .filter(user_id.eq(new_share.user_id))
+                    .or_filter(share_list_id.eq(list_id).and(group_name.eq(&new_share.group_name)))
+                    .for_update()
+                    .first::<TodoListShareDiesel>(conn)
+                    .optional()?;
+                let saved = match existing {
+                    Some(existing) => diesel::update(todo_list_shares.find(existing.id))
+                        .set(permission.eq(&new_share.permission))
+                        .get_result::<TodoListShareDiesel>(conn)?,
+                    None => diesel::insert_into(todo_list_shares).values(&new_share)
+                        .get_result::<TodoListShareDiesel>(conn)?,
+                };
+                Ok::<_, DieselRepositoryError>(saved)
+            })
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> TodoListShare { v.into() })
+    }
+
+    async fn unshare(&self, list_id: i32, share_id: i32) -> RepositoryResult<()> {
+        use crate::infrastructure::schema::todo_list_shares::dsl::{id, list_id as share_list_id, tenant_id, todo_list_shares};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let deleted = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::delete(todo_list_shares.filter(id.eq(share_id)).filter(share_list_id.eq(list_id)).filter(tenant_id.eq(tenant)))
+                .execute(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        if deleted == 0 {
+            return Err(RepositoryError {
+                message: format!("Share {} not found", share_id),
+                code: NOT_FOUND_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+}
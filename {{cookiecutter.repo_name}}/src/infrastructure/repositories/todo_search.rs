@@ -0,0 +1,170 @@
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, BigInt, Float4, Nullable, Integer, Text};
+
+use crate::domain::constants::{SEARCH_HIGHLIGHT_END, SEARCH_HIGHLIGHT_START};
+use crate::domain::models::authorization::Principal;
+use crate::domain::models::tenant::Tenant;
+use crate::domain::models::todo::TodoSearchHit;
+use crate::domain::repositories::repository::{QueryParams, RepositoryResult, ResultPaging};
+use crate::domain::repositories::todo_search::{TodoSearchParams, TodoSearchRepository};
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::todo::TodoDiesel;
+use crate::infrastructure::repositories::todo::in_archived_list;
+use crate::infrastructure::repositories::todo_list::visible_lists;
+
+// Characters of context kept on each side of a match by `LikeTodoSearchRepository`
+const SNIPPET_CONTEXT: usize = 60;
+
+#[derive(QueryableByName)]
+struct TodoSearchRow {
+    #[diesel(embed)]
+    todo: TodoDiesel,
+    #[diesel(sql_type = Float4)]
+    rank: f32,
+    #[diesel(sql_type = Text)]
+    snippet: String,
+}
+
+// Ranks matches of the generated `search_vector` column with `ts_rank` and highlights them
+// with `ts_headline`. Queries use the `websearch_to_tsquery` syntax (quoted phrases, `or`, `-`).
+pub struct FullTextTodoSearchRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl FullTextTodoSearchRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        FullTextTodoSearchRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl TodoSearchRepository for FullTextTodoSearchRepository {
+    async fn search(&self, params: TodoSearchParams) -> RepositoryResult<ResultPaging<TodoSearchHit>> {
+        let tenant = Tenant::current_id();
+        let visible = visible_lists(&tenant, &Principal::current());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let options = format!("StartSel={}, StopSel={}, MaxFragments=2", SEARCH_HIGHLIGHT_START, SEARCH_HIGHLIGHT_END);
+        let (limit, offset) = (params.limit(), params.offset());
+        let rows = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            let visible_list_ids = visible.load::<Option<i32>>(&mut conn)?.into_iter().flatten().collect::<Vec<_>>();
+            diesel::sql_query(
+                "SELECT todos.*, ts_rank(todos.search_vector, query) AS rank, \
+                     ts_headline('english', todos.title || ' ' || todos.description, query, $2) AS snippet \
+                 FROM todos, websearch_to_tsquery('english', $1) AS query \
+                 WHERE todos.search_vector @@ query \
+                     AND todos.tenant_id = $6 \
+                     AND todos.deleted_at IS NULL \
+                     AND ($3 IS NULL OR todos.list_id = $3) \
+                     AND (todos.list_id IS NULL OR todos.list_id = ANY($7)) \
+                     AND NOT EXISTS (SELECT 1 FROM todo_lists WHERE todo_lists.id = todos.list_id AND todo_lists.archived) \
+                 ORDER BY rank DESC, todos.id ASC \
+                 LIMIT $4 OFFSET $5"
+            )
+                .bind::<Text, _>(params.q)
+                .bind::<Text, _>(options)
+                .bind::<Nullable<Integer>, _>(params.list_id)
+                .bind::<BigInt, _>(limit)
+                .bind::<BigInt, _>(offset)
+                .bind::<Text, _>(tenant)
+                .bind::<Array<Integer>, _>(visible_list_ids)
+                .load::<TodoSearchRow>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(ResultPaging {
+            total: 0,
+            items: rows.into_iter()
+                .map(|row| TodoSearchHit { todo: row.todo.into(), rank: row.rank, snippet: row.snippet })
+                .collect(),
+        })
+    }
+}
+
+// Fallback for databases without full-text search: a case-insensitive substring match of the
+// whole query, ranking title matches above description matches.
+pub struct LikeTodoSearchRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl LikeTodoSearchRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        LikeTodoSearchRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl TodoSearchRepository for LikeTodoSearchRepository {
+    async fn search(&self, params: TodoSearchParams) -> RepositoryResult<ResultPaging<TodoSearchHit>> {
+        use crate::infrastructure::schema::todos::dsl::{deleted_at, description, id, list_id, tenant_id, title, todos};
+        let pattern = format!("%{}%", escape_like(&params.q));
+        let tenant = Tenant::current_id();
+        let mut builder = todos.into_boxed()
+            .filter(tenant_id.eq(tenant.clone()))
+            .filter(list_id.is_null().or(list_id.eq_any(visible_lists(&tenant, &Principal::current()))))
+            .filter(deleted_at.is_null())
+            .filter(diesel::dsl::not(in_archived_list()))
+            .filter(title.ilike(pattern.clone()).or(description.ilike(pattern.clone())));
+        if let Some(todo_list_id) = params.list_id {
+            builder = builder.filter(list_id.eq(todo_list_id));
+        }
+        let builder = builder
+            .order((title.ilike(pattern).desc(), id.asc()))
+            .limit(params.limit())
+            .offset(params.offset());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let result = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            builder.load::<TodoDiesel>(&mut conn).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(ResultPaging {
+            total: 0,
+            items: result.into_iter()
+                .map(|todo| {
+                    let (rank, snippet) = match highlight(&todo.title, &params.q) {
+                        Some(snippet) => (1.0, snippet),
+                        None => (0.5, highlight(&todo.description, &params.q).unwrap_or_default()),
+                    };
+                    TodoSearchHit { todo: todo.into(), rank, snippet }
+                })
+                .collect(),
+        })
+    }
+}
+
+fn escape_like(query: &str) -> String {
+    query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+// Cuts the text around the first case-insensitive occurrence of `query` and highlights it
+fn highlight(text: &str, query: &str) -> Option<String> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let folded = chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect::<Vec<_>>();
+    let needle = query.chars().map(|c| c.to_lowercase().next().unwrap_or(c)).collect::<Vec<_>>();
+    if needle.is_empty() || needle.len() > folded.len() {
+        return None;
+    }
+    let start = folded.windows(needle.len()).position(|window| window == needle.as_slice())?;
+    let end = start + needle.len();
+    let from = start.saturating_sub(SNIPPET_CONTEXT);
+    let to = (end + SNIPPET_CONTEXT).min(chars.len());
+    Some(format!(
+        "{}{}{}{}{}{}{}",
+        if from > 0 { "..." } else { "" },
+        chars[from..start].iter().collect::<String>(),
+        SEARCH_HIGHLIGHT_START,
+        chars[start..end].iter().collect::<String>(),
+        SEARCH_HIGHLIGHT_END,
+        chars[end..to].iter().collect::<String>(),
+        if to < chars.len() { "..." } else { "" },
+    ))
+}
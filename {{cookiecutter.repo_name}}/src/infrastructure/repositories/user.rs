@@ -0,0 +1,161 @@
+use std::sync::Arc;
+use actix_threadpool::run;
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::domain::models::authorization::Role;
+use crate::domain::models::tenant::Tenant;
+use crate::domain::models::user::{CreateUser, User, UserCredentials};
+use crate::domain::repositories::repository::{QueryParams, QueryParamsImpl, RepositoryResult, ResultPaging};
+use crate::domain::repositories::user::UserRepository;
+use crate::infrastructure::error::DieselRepositoryError;
+use crate::infrastructure::databases::postgresql::DBConn;
+use crate::infrastructure::models::user::{CreateUserDiesel, RoleDiesel, UserDiesel};
+
+pub struct UserDieselRepository {
+    pub pool: Arc<DBConn>
+}
+
+impl UserDieselRepository {
+    pub fn new(db: Arc<DBConn>) -> Self {
+        UserDieselRepository { pool: db }
+    }
+}
+
+#[async_trait]
+impl UserRepository for UserDieselRepository {
+    async fn create(&self, new_user: &CreateUser) -> RepositoryResult<User> {
+        use crate::infrastructure::schema::users::dsl::users;
+        let new_user_diesel = CreateUserDiesel::new(Tenant::current_id(), new_user.clone());
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::insert_into(users).values(new_user_diesel)
+                .get_result::<UserDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> User { v.into() })
+    }
+
+    async fn list(&self, params: QueryParamsImpl) -> RepositoryResult<ResultPaging<User>> {
+        use crate::infrastructure::schema::users::dsl::{id, tenant_id, users};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        let (limit, offset) = (params.limit(), params.offset());
+        let (total, result) = run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            conn.transaction(|conn| {
+                let total = users.filter(tenant_id.eq(&tenant)).count().get_result::<i64>(conn)?;
+                let page = users.filter(tenant_id.eq(&tenant)).order(id.asc()).limit(limit).offset(offset).load::<UserDiesel>(conn)?;
+                Ok::<_, diesel::result::Error>((total, page))
+            }).map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())?;
+        Ok(ResultPaging {
+            total,
+            items: result.into_iter().map(|v| v.into()).collect()
+        })
+    }
+
+    async fn get(&self, user_id: i32) -> RepositoryResult<User> {
+        use crate::infrastructure::schema::users::dsl::{id, tenant_id, users};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            users.filter(id.eq(user_id)).filter(tenant_id.eq(tenant)).first::<UserDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> User { v.into() })
+    }
+
+    async fn set_role(&self, user_id: i32, user_role: Role) -> RepositoryResult<User> {
+        use crate::infrastructure::schema::users::dsl::{id, role, tenant_id, users};
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::update(users.filter(id.eq(user_id)).filter(tenant_id.eq(tenant))).set(role.eq(RoleDiesel(user_role)))
+                .get_result::<UserDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> User { v.into() })
+    }
+
+    async fn set_groups(&self, user_id: i32, user_groups: &[String]) -> RepositoryResult<User> {
+        use crate::infrastructure::schema::users::dsl::{groups, id, tenant_id, users};
+        let user_groups = user_groups.to_vec();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::update(users.filter(id.eq(user_id)).filter(tenant_id.eq(tenant))).set(groups.eq(user_groups))
+                .get_result::<UserDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> User { v.into() })
+    }
+
+    async fn find_credentials(&self, user_email: &str) -> RepositoryResult<Option<UserCredentials>> {
+        use crate::infrastructure::schema::users::dsl::{email, tenant_id, users};
+        let user_email = user_email.to_string();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            users.filter(email.eq(user_email)).filter(tenant_id.eq(tenant)).first::<UserDiesel>(&mut conn).optional()
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.map(UserCredentials::from))
+    }
+
+    async fn find_external(&self, user_external_id: &str) -> RepositoryResult<Option<User>> {
+        use crate::infrastructure::schema::users::dsl::{external_id, tenant_id, users};
+        let user_external_id = user_external_id.to_string();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            users.filter(external_id.eq(user_external_id)).filter(tenant_id.eq(tenant)).first::<UserDiesel>(&mut conn).optional()
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| v.map(User::from))
+    }
+
+    async fn link_external(&self, user_id: i32, user_external_id: &str) -> RepositoryResult<User> {
+        use crate::infrastructure::schema::users::dsl::{external_id, id, tenant_id, users};
+        let user_external_id = user_external_id.to_string();
+        let tenant = Tenant::current_id();
+        let pool = self.pool.clone();
+        let tenant_tag = DBConn::tenant_tag();
+        run(move || {
+            let mut conn = pool.get_for(&tenant_tag)?;
+            diesel::update(users.filter(id.eq(user_id)).filter(tenant_id.eq(tenant))).set(external_id.eq(Some(user_external_id)))
+                .get_result::<UserDiesel>(&mut conn)
+                .map_err(DieselRepositoryError::from)
+        })
+            .await
+            .map_err(|v| DieselRepositoryError::from(v).into_inner())
+            .map(|v| -> User { v.into() })
+    }
+}
@@ -1,22 +1,257 @@
 // @generated automatically by Diesel CLI.
 
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "todo_priority"))]
+    pub struct TodoPriority;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "todo_status"))]
+    pub struct TodoStatus;
+}
+
+diesel::table! {
+    api_keys (id) {
+        id -> Int4,
+        user_id -> Int4,
+        name -> Varchar,
+        prefix -> Varchar,
+        key_hash -> Varchar,
+        scopes -> Array<Text>,
+        expires_at -> Nullable<Timestamptz>,
+        last_used_at -> Nullable<Timestamptz>,
+        revoked_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        tenant_id -> Varchar,
+    }
+}
+
+diesel::table! {
+    attachments (id) {
+        id -> Int4,
+        todo_id -> Int4,
+        file_name -> Varchar,
+        content_type -> Varchar,
+        size -> Int8,
+        checksum -> Varchar,
+        storage_key -> Varchar,
+        created_at -> Timestamptz,
+        tenant_id -> Varchar,
+    }
+}
+
+diesel::table! {
+    audit_events (id) {
+        id -> Int8,
+        todo_id -> Int4,
+        action -> Varchar,
+        actor -> Nullable<Varchar>,
+        request_id -> Nullable<Varchar>,
+        changes -> Jsonb,
+        created_at -> Timestamptz,
+        tenant_id -> Varchar,
+    }
+}
+
+diesel::table! {
+    comments (id) {
+        id -> Int4,
+        todo_id -> Int4,
+        author -> Nullable<Varchar>,
+        body -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        author_id -> Nullable<Int4>,
+        tenant_id -> Varchar,
+    }
+}
+
+diesel::table! {
+    idempotency_keys (tenant_id, principal, key) {
+        key -> Varchar,
+        request_fingerprint -> Varchar,
+        response_status -> Nullable<Int2>,
+        response_content_type -> Nullable<Varchar>,
+        response_body -> Nullable<Bytea>,
+        response_headers -> Nullable<Jsonb>,
+        created_at -> Timestamptz,
+        expires_at -> Timestamptz,
+        principal -> Varchar,
+        tenant_id -> Varchar,
+    }
+}
+
+diesel::table! {
+    jobs (id) {
+        id -> Int8,
+        kind -> Varchar,
+        payload -> Jsonb,
+        status -> Varchar,
+        attempts -> Int4,
+        max_attempts -> Int4,
+        run_at -> Timestamptz,
+        locked_at -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+        dedup_key -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     service_contexts (id) {
         id -> Int4,
         maintenance -> Bool,
+        tenant_id -> Varchar,
     }
 }
 
 diesel::table! {
+    tags (id) {
+        id -> Int4,
+        name -> Varchar,
+        color -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        tenant_id -> Varchar,
+    }
+}
+
+diesel::table! {
+    tenants (id) {
+        id -> Varchar,
+        allow_anonymous -> Bool,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    todo_items (id) {
+        id -> Int4,
+        todo_id -> Int4,
+        title -> Varchar,
+        position -> Int4,
+        done -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        tenant_id -> Varchar,
+    }
+}
+
+diesel::table! {
+    todo_list_shares (id) {
+        id -> Int4,
+        list_id -> Int4,
+        user_id -> Nullable<Int4>,
+        group_name -> Nullable<Varchar>,
+        permission -> Varchar,
+        created_at -> Timestamptz,
+        tenant_id -> Varchar,
+    }
+}
+
+diesel::table! {
+    todo_lists (id) {
+        id -> Int4,
+        name -> Varchar,
+        description -> Text,
+        owner -> Nullable<Varchar>,
+        archived -> Bool,
+        archived_at -> Nullable<Timestamptz>,
+        deleted_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        tenant_id -> Varchar,
+        owner_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    todo_reminders (todo_id, remind_at) {
+        todo_id -> Int4,
+        remind_at -> Timestamptz,
+        sent_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    todo_tags (todo_id, tag_id) {
+        todo_id -> Int4,
+        tag_id -> Int4,
+    }
+}
+
+// `search_vector` (a generated tsvector) is left out, it is only used through raw SQL
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::TodoStatus;
+    use super::sql_types::TodoPriority;
+
     todos (id) {
         id -> Int4,
         title -> Varchar,
         description -> Text,
         completed -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        deleted_at -> Nullable<Timestamptz>,
+        version -> Int4,
+        completed_at -> Nullable<Timestamptz>,
+        completed_by -> Nullable<Varchar>,
+        due_at -> Nullable<Timestamptz>,
+        remind_at -> Nullable<Timestamptz>,
+        status -> TodoStatus,
+        priority -> TodoPriority,
+        list_id -> Nullable<Int4>,
+        position -> Int8,
+        recurrence -> Nullable<Jsonb>,
+        owner_id -> Nullable<Int4>,
+        tenant_id -> Varchar,
+    }
+}
+
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        email -> Varchar,
+        name -> Nullable<Varchar>,
+        password_hash -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        role -> Varchar,
+        external_id -> Nullable<Varchar>,
+        tenant_id -> Varchar,
+        groups -> Array<Text>,
     }
 }
 
+diesel::joinable!(api_keys -> users (user_id));
+diesel::joinable!(attachments -> todos (todo_id));
+diesel::joinable!(comments -> todos (todo_id));
+diesel::joinable!(todo_items -> todos (todo_id));
+diesel::joinable!(todo_list_shares -> todo_lists (list_id));
+diesel::joinable!(todo_list_shares -> users (user_id));
+diesel::joinable!(todo_lists -> users (owner_id));
+diesel::joinable!(todo_reminders -> todos (todo_id));
+diesel::joinable!(todo_tags -> tags (tag_id));
+diesel::joinable!(todo_tags -> todos (todo_id));
+diesel::joinable!(todos -> todo_lists (list_id));
+diesel::joinable!(todos -> users (owner_id));
+
 diesel::allow_tables_to_appear_in_same_query!(
+    api_keys,
+    attachments,
+    audit_events,
+    comments,
+    idempotency_keys,
+    jobs,
     service_contexts,
+    tags,
+    tenants,
+    todo_items,
+    todo_list_shares,
+    todo_lists,
+    todo_reminders,
+    todo_tags,
     todos,
+    users,
 );
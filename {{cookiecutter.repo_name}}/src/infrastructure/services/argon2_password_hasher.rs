@@ -0,0 +1,42 @@
+use actix_threadpool::run;
+use argon2::{Argon2, PasswordHash, PasswordHasher as _, PasswordVerifier};
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::SaltString;
+use async_trait::async_trait;
+
+use crate::domain::error::{CommonError, REPOSITORY_ERROR_CODE};
+use crate::domain::services::password_hasher::PasswordHasher;
+
+// Argon2id with the crate's default parameters, hashes are stored in the PHC string format
+#[derive(Clone, Default)]
+pub struct Argon2PasswordHasher;
+
+fn hashing_error(error: impl std::fmt::Display) -> CommonError {
+    CommonError {
+        message: format!("Password hashing failed: {}", error),
+        code: REPOSITORY_ERROR_CODE,
+    }
+}
+
+#[async_trait]
+impl PasswordHasher for Argon2PasswordHasher {
+    async fn hash(&self, password: String) -> Result<String, CommonError> {
+        run(move || {
+            let salt = SaltString::generate(&mut OsRng);
+            Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map(|hash| hash.to_string())
+        })
+            .await
+            .map_err(hashing_error)
+    }
+
+    async fn verify(&self, password: String, password_hash: String) -> Result<bool, CommonError> {
+        run(move || {
+            let parsed = PasswordHash::new(&password_hash)?;
+            Ok::<_, argon2::password_hash::Error>(Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+        })
+            .await
+            .map_err(hashing_error)
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use jsonwebtoken::jwk::{AlgorithmParameters, JwkSet};
+use log::{error, warn};
+use reqwest::Client;
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use crate::domain::error::{CommonError, UNAUTHORIZED_ERROR_CODE};
+use crate::domain::models::auth::ExternalIdentity;
+use crate::domain::models::authorization::Role;
+use crate::domain::services::identity_provider::IdentityProvider;
+
+// Only public key signatures, so a provider's published keys can never be used to sign tokens
+const ALGORITHMS: [Algorithm; 9] = [
+    Algorithm::RS256, Algorithm::RS384, Algorithm::RS512,
+    Algorithm::PS256, Algorithm::PS384, Algorithm::PS512,
+    Algorithm::ES256, Algorithm::ES384, Algorithm::EdDSA,
+];
+
+#[derive(Clone)]
+pub enum JwksSource {
+    // e.g. `https://sso.example.com/.well-known/jwks.json`
+    Url(String),
+    File(PathBuf),
+}
+
+#[derive(Clone)]
+pub struct OidcConfig {
+    pub jwks: JwksSource,
+    // Checked against the `iss` and `aud` claims when set
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
+    pub email_claim: String,
+    // A role name or a list of them, the most privileged known role wins
+    pub roles_claim: String,
+    // Without it, the roles claim is ignored
+    pub trust_roles: bool,
+    pub tenant_claim: Option<String>,
+    // The tenant of users whose tokens carry no tenant claim
+    pub tenant: String,
+    // How long fetched keys are trusted before they are fetched again
+    pub cache_ttl: Duration,
+    // Tokens signed with an unknown key trigger a refetch, at most once per interval
+    pub min_refresh_interval: Duration,
+}
+
+struct CachedKeys {
+    // By key id, keys without one are stored under ""
+    keys: HashMap<String, DecodingKey>,
+    fetched_at: Option<Instant>,
+}
+
+// Verifies tokens against the JSON Web Key Set of an OpenID Connect provider. The keys are
+// cached and fetched again once they expire or a token refers to a key that is not known yet,
+// which is how providers roll their keys over.
+pub struct JwksIdentityProvider {
+    client: Client,
+    config: OidcConfig,
+    cache: RwLock<CachedKeys>,
+}
+
+fn invalid_token(error: impl std::fmt::Display) -> CommonError {
+    CommonError {
+        message: format!("Invalid token: {}", error),
+        code: UNAUTHORIZED_ERROR_CODE,
+    }
+}
+
+fn rank(role: Role) -> u8 {
+    match role {
+        Role::Admin => 2,
+        Role::Member => 1,
+        Role::Viewer => 0,
+    }
+}
+
+impl JwksIdentityProvider {
+    pub fn new(config: OidcConfig) -> Self {
+        JwksIdentityProvider {
+            client: Client::new(),
+            config,
+            cache: RwLock::new(CachedKeys { keys: HashMap::new(), fetched_at: None }),
+        }
+    }
+
+    async fn fetch(&self) -> Result<HashMap<String, DecodingKey>, String> {
+        let body = match &self.config.jwks {
+            JwksSource::Url(url) => self.client.get(url).send().await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| e.to_string())?
+                .bytes().await
+                .map_err(|e| e.to_string())?
+                .to_vec(),
+            JwksSource::File(path) => tokio::fs::read(path).await.map_err(|e| e.to_string())?,
+        };
+        let jwks: JwkSet = serde_json::from_slice(&body).map_err(|e| e.to_string())?;
+        Ok(jwks.keys.iter()
+            .filter(|jwk| !matches!(jwk.algorithm, AlgorithmParameters::OctetKey(_)))
+            .filter_map(|jwk| {
+                let key = DecodingKey::from_jwk(jwk).ok()?;
+                Some((jwk.common.key_id.clone().unwrap_or_default(), key))
+            })
+            .collect())
+    }
+
+    async fn key(&self, key_id: &str) -> Result<DecodingKey, CommonError> {
+        let unknown_key = || invalid_token(format!("unknown signing key {}", key_id));
+        {
+            let cache = self.cache.read().await;
+            let age = cache.fetched_at.map(|fetched_at| fetched_at.elapsed());
+            if age.is_some_and(|age| age < self.config.cache_ttl) {
+                match cache.keys.get(key_id) {
+                    Some(key) => return Ok(key.clone()),
+                    None if age.is_some_and(|age| age < self.config.min_refresh_interval) => return Err(unknown_key()),
+                    None => {}
+                }
+            }
+        }
+
+        let mut cache = self.cache.write().await;
+        // Another request may have fetched the keys in the meantime
+        let recently_fetched = cache.fetched_at.is_some_and(|fetched_at| fetched_at.elapsed() < self.config.min_refresh_interval);
+        if !recently_fetched {
+            match self.fetch().await {
+                Ok(keys) => {
+                    cache.keys = keys;
+                    cache.fetched_at = Some(Instant::now());
+                }
+                // Keep using the keys we have while the provider cannot be reached
+                Err(e) if !cache.keys.is_empty() => warn!("Could not refresh the signing keys: {}", e),
+                Err(e) => {
+                    error!("Could not load the signing keys: {}", e);
+                    return Err(invalid_token("signing keys are unavailable"));
+                }
+            }
+        }
+        cache.keys.get(key_id).cloned().ok_or_else(unknown_key)
+    }
+
+    fn role(&self, claims: &Value) -> Option<Role> {
+        if !self.config.trust_roles {
+            return None;
+        }
+        let names = match claims.get(&self.config.roles_claim)? {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => return None,
+        };
+        names.into_iter()
+            .filter_map(|name| name.parse::<Role>().ok())
+            .max_by_key(|role| rank(*role))
+    }
+}
+
+#[async_trait]
+impl IdentityProvider for JwksIdentityProvider {
+    async fn verify(&self, token: &str) -> Result<ExternalIdentity, CommonError> {
+        let header = decode_header(token).map_err(invalid_token)?;
+        if !ALGORITHMS.contains(&header.alg) {
+            return Err(invalid_token(format!("unsupported algorithm {:?}", header.alg)));
+        }
+        let key = self.key(header.kid.as_deref().unwrap_or_default()).await?;
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = 0;
+        if let Some(issuer) = &self.config.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match &self.config.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        let claims = decode::<Value>(token, &key, &validation).map_err(invalid_token)?.claims;
+        let claim = |name: &str| claims.get(name).and_then(Value::as_str).map(str::to_string);
+        Ok(ExternalIdentity {
+            subject: claim("sub").ok_or_else(|| invalid_token("missing sub claim"))?,
+            email: claim(&self.config.email_claim)
+                .ok_or_else(|| invalid_token(format!("missing {} claim", self.config.email_claim)))?,
+            // Some providers send the flag as a string
+            email_verified: matches!(claims.get("email_verified"), Some(Value::Bool(true)))
+                || claim("email_verified").as_deref() == Some("true"),
+            name: claim("name"),
+            role: self.role(&claims),
+            tenant: self.config.tenant_claim.as_deref().and_then(claim).unwrap_or_else(|| self.config.tenant.clone()),
+        })
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::{TimeZone, Utc};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::constants::DEFAULT_TENANT;
+use crate::domain::error::{CommonError, REPOSITORY_ERROR_CODE, UNAUTHORIZED_ERROR_CODE};
+use crate::domain::models::auth::{TokenClaims, TokenKind};
+use crate::domain::services::token_codec::TokenCodec;
+
+#[derive(Serialize, Deserialize)]
+struct JwtClaims {
+    sub: String,
+    // Tokens issued before tenants existed belong to the default one
+    #[serde(default)]
+    tenant: Option<String>,
+    kind: TokenKind,
+    iat: i64,
+    exp: i64,
+}
+
+// HS256 signed JWTs
+pub struct JwtTokenCodec {
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    validation: Validation,
+}
+
+impl JwtTokenCodec {
+    pub fn new(secret: &[u8]) -> Self {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+        JwtTokenCodec {
+            encoding_key: EncodingKey::from_secret(secret),
+            decoding_key: DecodingKey::from_secret(secret),
+            validation,
+        }
+    }
+}
+
+fn invalid_token(error: impl std::fmt::Display) -> CommonError {
+    CommonError {
+        message: format!("Invalid token: {}", error),
+        code: UNAUTHORIZED_ERROR_CODE,
+    }
+}
+
+impl TokenCodec for JwtTokenCodec {
+    fn encode(&self, claims: &TokenClaims) -> Result<String, CommonError> {
+        let claims = JwtClaims {
+            sub: claims.user_id.to_string(),
+            tenant: Some(claims.tenant.clone()),
+            kind: claims.kind,
+            iat: claims.issued_at.timestamp(),
+            exp: claims.expires_at.timestamp(),
+        };
+        encode(&Header::new(Algorithm::HS256), &claims, &self.encoding_key).map_err(|e| CommonError {
+            message: format!("Could not sign token: {}", e),
+            code: REPOSITORY_ERROR_CODE,
+        })
+    }
+
+    fn decode(&self, token: &str) -> Result<TokenClaims, CommonError> {
+        let claims = decode::<JwtClaims>(token, &self.decoding_key, &self.validation)
+            .map_err(invalid_token)?
+            .claims;
+        let timestamp = |seconds: i64| Utc.timestamp_opt(seconds, 0).single().ok_or_else(|| invalid_token("bad timestamp"));
+        Ok(TokenClaims {
+            user_id: claims.sub.parse().map_err(invalid_token)?,
+            tenant: claims.tenant.unwrap_or_else(|| DEFAULT_TENANT.to_string()),
+            kind: claims.kind,
+            issued_at: timestamp(claims.iat)?,
+            expires_at: timestamp(claims.exp)?,
+        })
+    }
+}
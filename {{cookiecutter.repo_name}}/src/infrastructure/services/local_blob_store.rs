@@ -0,0 +1,75 @@
+use std::io::{ErrorKind, SeekFrom};
+use std::path::{Component, Path, PathBuf};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::{StreamExt, TryStreamExt};
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+use crate::domain::error::{CommonError, NOT_FOUND_ERROR_CODE, REPOSITORY_ERROR_CODE, VALIDATION_ERROR_CODE};
+use crate::domain::services::blob_store::{BlobStore, BlobStream};
+
+// Keeps blobs as files below `root`, keys map to relative paths
+#[derive(Clone)]
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        LocalBlobStore { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, CommonError> {
+        let relative = Path::new(key);
+        if !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+            return Err(CommonError {
+                message: format!("Invalid blob key {}", key),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+fn storage_error(key: &str, error: std::io::Error) -> CommonError {
+    CommonError {
+        message: format!("Blob {}: {}", key, error),
+        code: if error.kind() == ErrorKind::NotFound { NOT_FOUND_ERROR_CODE } else { REPOSITORY_ERROR_CODE },
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, _content_type: &str, content: Bytes) -> Result<(), CommonError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| storage_error(key, e))?;
+        }
+        // Write next to the target and rename, so readers never see a partial file
+        let partial = path.with_extension("partial");
+        fs::write(&partial, &content).await.map_err(|e| storage_error(key, e))?;
+        fs::rename(&partial, &path).await.map_err(|e| storage_error(key, e))
+    }
+
+    async fn get(&self, key: &str, range: Option<(u64, u64)>) -> Result<BlobStream, CommonError> {
+        let mut file = fs::File::open(self.path(key)?).await.map_err(|e| storage_error(key, e))?;
+        let owned_key = key.to_string();
+        let stream = match range {
+            Some((start, end)) => {
+                file.seek(SeekFrom::Start(start)).await.map_err(|e| storage_error(key, e))?;
+                ReaderStream::new(file.take(end - start + 1)).map_err(move |e| storage_error(&owned_key, e)).boxed()
+            }
+            None => ReaderStream::new(file).map_err(move |e| storage_error(&owned_key, e)).boxed(),
+        };
+        Ok(stream)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CommonError> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(storage_error(key, e)),
+            _ => Ok(()),
+        }
+    }
+}
@@ -1 +1,7 @@
-pub mod service_context;
\ No newline at end of file
+pub mod service_context;
+pub mod reminder_notifier;
+pub mod local_blob_store;
+pub mod s3_blob_store;
+pub mod argon2_password_hasher;
+pub mod jwt_token_codec;
+pub mod jwks_identity_provider;
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use log::info;
+use crate::domain::error::CommonError;
+use crate::domain::models::todo::Todo;
+use crate::domain::services::reminder::ReminderNotifier;
+
+// Default notifier that only logs reminders, replace it with e-mail, push, ... delivery
+#[derive(Clone, Default)]
+pub struct LogReminderNotifier;
+
+#[async_trait]
+impl ReminderNotifier for LogReminderNotifier {
+    async fn notify(&self, todo: &Todo) -> Result<(), CommonError> {
+        info!("Reminder for todo {}: {}", todo.id, todo.title);
+        Ok(())
+    }
+}
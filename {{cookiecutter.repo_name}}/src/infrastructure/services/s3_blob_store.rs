@@ -0,0 +1,141 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::Utc;
+use futures_util::{StreamExt, TryStreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode, Url};
+use sha2::{Digest, Sha256};
+
+use crate::domain::error::{CommonError, NOT_FOUND_ERROR_CODE, REPOSITORY_ERROR_CODE};
+use crate::domain::services::blob_store::{BlobStore, BlobStream};
+
+#[derive(Clone)]
+pub struct S3Config {
+    // e.g. `https://s3.eu-west-1.amazonaws.com` or `http://127.0.0.1:9000` for MinIO
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+// Talks to any S3-compatible service with path-style URLs and Signature Version 4.
+// Keys are expected to be URL-safe (`[A-Za-z0-9/_.-]`), which holds for generated attachment keys.
+#[derive(Clone)]
+pub struct S3BlobStore {
+    client: Client,
+    config: S3Config,
+}
+
+impl S3BlobStore {
+    pub fn new(config: S3Config) -> Self {
+        S3BlobStore { client: Client::new(), config }
+    }
+
+    // Creates the bucket, an already existing bucket is fine
+    pub async fn create_bucket(&self) -> Result<(), CommonError> {
+        let path = format!("/{}", self.config.bucket);
+        let response = self.send(Method::PUT, &path, Bytes::new(), |request| request).await?;
+        match response.status() {
+            status if status.is_success() || status == StatusCode::CONFLICT => Ok(()),
+            status => Err(s3_error(&path, status)),
+        }
+    }
+
+    fn object_path(&self, key: &str) -> String {
+        format!("/{}/{}", self.config.bucket, key.trim_start_matches('/'))
+    }
+
+    async fn send(
+        &self, method: Method, path: &str, body: Bytes, extra: impl FnOnce(RequestBuilder) -> RequestBuilder,
+    ) -> Result<Response, CommonError> {
+        let url = Url::parse(&format!("{}{}", self.config.endpoint.trim_end_matches('/'), path))
+            .map_err(|e| CommonError { message: format!("Invalid S3 URL: {}", e), code: REPOSITORY_ERROR_CODE })?;
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, url.path(), host, payload_hash, amz_date, signed_headers, payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.config.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date, scope, hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signing_key = [date.as_str(), self.config.region.as_str(), "s3", "aws4_request"].iter()
+            .fold(format!("AWS4{}", self.config.secret_access_key).into_bytes(), |key, part| hmac_sha256(&key, part));
+        let signature = hex::encode(hmac_sha256(&signing_key, &string_to_sign));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.config.access_key_id, scope, signed_headers, signature
+        );
+        let request = self.client.request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("authorization", authorization)
+            .body(body);
+        extra(request).send().await.map_err(|e| CommonError {
+            message: format!("S3 request to {} failed: {}", path, e),
+            code: REPOSITORY_ERROR_CODE,
+        })
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn s3_error(path: &str, status: StatusCode) -> CommonError {
+    CommonError {
+        message: format!("S3 request to {} failed with {}", path, status),
+        code: if status == StatusCode::NOT_FOUND { NOT_FOUND_ERROR_CODE } else { REPOSITORY_ERROR_CODE },
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, content_type: &str, content: Bytes) -> Result<(), CommonError> {
+        let path = self.object_path(key);
+        let content_type = content_type.to_string();
+        let response = self.send(Method::PUT, &path, content, |request| request.header("content-type", content_type)).await?;
+        if !response.status().is_success() {
+            return Err(s3_error(&path, response.status()));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str, range: Option<(u64, u64)>) -> Result<BlobStream, CommonError> {
+        let path = self.object_path(key);
+        let response = self.send(Method::GET, &path, Bytes::new(), |request| match range {
+            Some((start, end)) => request.header("range", format!("bytes={}-{}", start, end)),
+            None => request,
+        }).await?;
+        if !response.status().is_success() {
+            return Err(s3_error(&path, response.status()));
+        }
+        Ok(response.bytes_stream()
+            .map_err(move |e| CommonError {
+                message: format!("Reading {} from S3 failed: {}", path, e),
+                code: REPOSITORY_ERROR_CODE,
+            })
+            .boxed())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CommonError> {
+        let path = self.object_path(key);
+        let response = self.send(Method::DELETE, &path, Bytes::new(), |request| request).await?;
+        match response.status() {
+            status if status.is_success() || status == StatusCode::NOT_FOUND => Ok(()),
+            status => Err(s3_error(&path, status)),
+        }
+    }
+}
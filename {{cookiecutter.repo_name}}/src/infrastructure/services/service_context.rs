@@ -1,13 +1,18 @@
 use std::sync::Arc;
-use diesel::{insert_into, update};
+use diesel::insert_into;
 use diesel::prelude::*;
 use diesel::result::Error;
-use log::{info};
+use log::{error, info};
+use crate::domain::error::{CommonError, SERVICE_UNAVAILABLE_ERROR_CODE};
 use crate::domain::models::service_context::ServiceContext;
+use crate::domain::models::tenant::Tenant;
 use crate::domain::services::service_context::ServiceContextService;
 use crate::infrastructure::databases::postgresql::DBConn;
 use crate::infrastructure::models::service_context::ServiceContextDiesel;
 
+// The global context is stored with an empty tenant id
+const GLOBAL_TENANT_ID: &str = "";
+
 #[derive(Clone)]
 pub struct ServiceContextServiceImpl {
     pub pool: Arc<DBConn>
@@ -20,10 +25,16 @@ impl ServiceContextServiceImpl {
         }
     }
 
-    fn get_service_context(&self) -> ServiceContext {
-        use crate::infrastructure::schema::service_contexts::dsl::{id, service_contexts};
-        let mut conn = self.pool.get().unwrap();
-        let result: Result<ServiceContextDiesel, Error> = service_contexts.filter(id.eq(1)).first::<ServiceContextDiesel>(&mut conn);
+    fn get_service_context(&self, tenant: Option<&Tenant>) -> ServiceContext {
+        use crate::infrastructure::schema::service_contexts::dsl::{service_contexts, tenant_id};
+        let mut conn = self.pool.get_across_tenants().unwrap();
+        if let Some(tenant) = tenant {
+            let result: Result<ServiceContextDiesel, Error> = service_contexts.filter(tenant_id.eq(&tenant.id)).first::<ServiceContextDiesel>(&mut conn);
+            if let Ok(service_context) = result {
+                return service_context.into();
+            }
+        }
+        let result: Result<ServiceContextDiesel, Error> = service_contexts.filter(tenant_id.eq(GLOBAL_TENANT_ID)).first::<ServiceContextDiesel>(&mut conn);
 
         if result.is_err() {
             info!("Service context does not exist, creating a service context...");
@@ -34,9 +45,10 @@ impl ServiceContextServiceImpl {
     }
 
     fn create_service_context(&self) -> ServiceContext {
-        use crate::infrastructure::schema::service_contexts::dsl::service_contexts;
-        let mut conn = self.pool.get().unwrap();
-        let result: Result<ServiceContextDiesel, Error> = insert_into(service_contexts).values(ServiceContextDiesel { id: 1, maintenance: false }).get_result(&mut conn);
+        use crate::infrastructure::schema::service_contexts::dsl::{maintenance, service_contexts, tenant_id};
+        let mut conn = self.pool.get_across_tenants().unwrap();
+        let result: Result<ServiceContextDiesel, Error> = insert_into(service_contexts)
+            .values((tenant_id.eq(GLOBAL_TENANT_ID), maintenance.eq(false))).get_result(&mut conn);
 
         if result.is_err() {
             panic!("Could not create service context");
@@ -46,16 +58,18 @@ impl ServiceContextServiceImpl {
 }
 
 impl ServiceContextService for ServiceContextServiceImpl {
-    fn get_service_context(&self) -> ServiceContext {
-        self.get_service_context()
+    fn get_service_context(&self, tenant: Option<&Tenant>) -> ServiceContext {
+        self.get_service_context(tenant)
     }
 
-    fn update(&self, service_context: ServiceContext) -> ServiceContext {
-        let service_context_diesel: ServiceContextDiesel = ServiceContextDiesel::from(service_context);
-        let mut conn = self.pool.get().unwrap();
-        use crate::infrastructure::schema::service_contexts::dsl::{service_contexts, id};
-        let result: Result<ServiceContextDiesel, Error> = update(service_contexts)
-            .filter(id.eq(1)).set(service_context_diesel).get_result(&mut conn);
+    fn update(&self, tenant: Option<&Tenant>, maintenance_active: bool) -> ServiceContext {
+        let mut conn = self.pool.get_across_tenants().unwrap();
+        use crate::infrastructure::schema::service_contexts::dsl::{maintenance, service_contexts, tenant_id};
+        let key = tenant.map_or(GLOBAL_TENANT_ID, |tenant| tenant.id.as_str());
+        let result: Result<ServiceContextDiesel, Error> = insert_into(service_contexts)
+            .values((tenant_id.eq(key), maintenance.eq(maintenance_active)))
+            .on_conflict(tenant_id).do_update().set(maintenance.eq(maintenance_active))
+            .get_result(&mut conn);
 
         if result.is_err() {
             panic!("Could not update service context");
@@ -63,7 +77,19 @@ impl ServiceContextService for ServiceContextServiceImpl {
         result.unwrap().into()
     }
 
-    fn is_maintenance_active(&self) -> bool {
-        self.get_service_context().maintenance
+    fn is_maintenance_active(&self, tenant: &Tenant) -> Result<bool, CommonError> {
+        use crate::infrastructure::schema::service_contexts::dsl::{maintenance, service_contexts, tenant_id};
+        let unknown = |e: &dyn std::fmt::Display| {
+            error!("Could not load service contexts: {}", e);
+            CommonError { message: "Could not check for maintenance".to_string(), code: SERVICE_UNAVAILABLE_ERROR_CODE }
+        };
+        let mut conn = self.pool.get_across_tenants().map_err(|e| unknown(&e.into_inner().message))?;
+        let result: Result<Vec<bool>, Error> = service_contexts
+            .filter(tenant_id.eq_any([GLOBAL_TENANT_ID, tenant.id.as_str()]))
+            .select(maintenance)
+            .load(&mut conn);
+        result
+            .map(|contexts| contexts.into_iter().any(|active| active))
+            .map_err(|e| unknown(&e))
     }
 }
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use log::info;
+use crate::domain::constants::DELETE_BLOBS_JOB;
+use crate::domain::error::{CommonError, VALIDATION_ERROR_CODE};
+use crate::domain::models::job::Job;
+use crate::domain::services::blob_store::BlobStore;
+use crate::jobs::handler::JobHandler;
+
+pub const KIND: &str = DELETE_BLOBS_JOB;
+
+// Removes the content of attachments whose rows were deleted along with their todos
+pub struct DeleteBlobsJob {
+    pub blob_store: Arc<dyn BlobStore>,
+}
+
+#[async_trait]
+impl JobHandler for DeleteBlobsJob {
+    async fn run(&self, job: &Job) -> Result<(), CommonError> {
+        let storage_keys: Vec<String> = serde_json::from_value(job.payload["storage_keys"].clone())
+            .map_err(|e| CommonError {
+                message: format!("Invalid payload for job {}: {}", job.id, e),
+                code: VALIDATION_ERROR_CODE,
+            })?;
+        // Deleting missing content succeeds, so a retry starts over from the first key
+        for storage_key in &storage_keys {
+            self.blob_store.delete(storage_key).await?;
+        }
+        info!("Deleted {} blobs", storage_keys.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use crate::domain::error::CommonError;
+use crate::domain::models::job::Job;
+
+// Executes jobs of one kind. Returning an error schedules a retry until the job runs out of attempts,
+// so handlers must be safe to run more than once.
+#[async_trait]
+pub trait JobHandler: 'static + Sync + Send {
+    async fn run(&self, job: &Job) -> Result<(), CommonError>;
+}
@@ -0,0 +1,7 @@
+pub mod handler;
+pub mod runner;
+pub mod delete_blobs;
+pub mod purge_deleted_todos;
+pub mod purge_finished_jobs;
+pub mod purge_idempotency_keys;
+pub mod todo_reminders;
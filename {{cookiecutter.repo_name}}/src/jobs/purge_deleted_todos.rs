@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::info;
+use crate::domain::constants::{DEFAULT_TODO_RETENTION_DAYS, TODO_RETENTION_DAYS};
+use crate::domain::error::CommonError;
+use crate::domain::models::job::Job;
+use crate::domain::services::todo::TodoService;
+use crate::infrastructure::config::env_or;
+use crate::jobs::handler::JobHandler;
+
+pub const KIND: &str = "purge_deleted_todos";
+
+// How long soft-deleted todos are kept before being hard-deleted, refusing to start with an invalid setting
+pub fn retention() -> chrono::Duration {
+    let days = match env_or(TODO_RETENTION_DAYS, String::new()).as_str() {
+        "" => DEFAULT_TODO_RETENTION_DAYS,
+        days => days.parse::<u32>()
+            .map(i64::from)
+            .unwrap_or_else(|e| panic!("Invalid {} {}: {}", TODO_RETENTION_DAYS, days, e)),
+    };
+    chrono::Duration::days(days)
+}
+
+pub struct PurgeDeletedTodosJob {
+    pub todo_service: Arc<dyn TodoService>,
+    pub retention: chrono::Duration,
+}
+
+#[async_trait]
+impl JobHandler for PurgeDeletedTodosJob {
+    async fn run(&self, _job: &Job) -> Result<(), CommonError> {
+        let count = self.todo_service.purge_deleted(Utc::now() - self.retention).await?;
+        info!("Purged {} deleted todos", count);
+        Ok(())
+    }
+}
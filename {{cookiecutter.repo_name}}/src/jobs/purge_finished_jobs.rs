@@ -0,0 +1,23 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use log::info;
+use crate::domain::constants::FINISHED_JOB_RETENTION_DAYS;
+use crate::domain::error::CommonError;
+use crate::domain::models::job::Job;
+use crate::domain::services::job::JobService;
+use crate::jobs::handler::JobHandler;
+
+pub const KIND: &str = "purge_finished_jobs";
+
+pub struct PurgeFinishedJobsJob {
+    pub job_service: Arc<dyn JobService>,
+}
+
+#[async_trait]
+impl JobHandler for PurgeFinishedJobsJob {
+    async fn run(&self, _job: &Job) -> Result<(), CommonError> {
+        let count = self.job_service.purge_finished(chrono::Duration::days(FINISHED_JOB_RETENTION_DAYS)).await?;
+        info!("Purged {} finished jobs", count);
+        Ok(())
+    }
+}
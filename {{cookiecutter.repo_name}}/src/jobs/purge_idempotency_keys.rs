@@ -0,0 +1,22 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use log::info;
+use crate::domain::error::CommonError;
+use crate::domain::models::job::Job;
+use crate::domain::services::idempotency::IdempotencyService;
+use crate::jobs::handler::JobHandler;
+
+pub const KIND: &str = "purge_idempotency_keys";
+
+pub struct PurgeIdempotencyKeysJob {
+    pub idempotency_service: Arc<dyn IdempotencyService>,
+}
+
+#[async_trait]
+impl JobHandler for PurgeIdempotencyKeysJob {
+    async fn run(&self, _job: &Job) -> Result<(), CommonError> {
+        let count = self.idempotency_service.purge_expired().await?;
+        info!("Purged {} expired idempotency keys", count);
+        Ok(())
+    }
+}
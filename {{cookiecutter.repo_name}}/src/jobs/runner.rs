@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use actix_web::rt;
+use actix_web::rt::task::JoinHandle;
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use log::{error, info, warn};
+use serde_json::json;
+use tokio::sync::watch;
+use crate::domain::constants::{
+    DEFAULT_JOB_MAX_ATTEMPTS, DEFAULT_JOB_POLL_INTERVAL_MS, DEFAULT_JOB_WORKERS, JOB_POLL_INTERVAL_MS, JOB_WORKERS,
+};
+use crate::domain::models::authorization::Principal;
+use crate::domain::models::job::{CreateJob, Job};
+use crate::domain::services::job::JobService;
+use crate::infrastructure::config::env_or;
+use crate::jobs::handler::JobHandler;
+
+// How long the scheduler sleeps when no cron schedule has an upcoming fire time
+const IDLE_SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+
+pub struct JobRunnerConfig {
+    pub workers: usize,
+    pub poll_interval: Duration,
+}
+
+impl JobRunnerConfig {
+    pub fn from_env() -> Self {
+        JobRunnerConfig {
+            workers: env_or(JOB_WORKERS, DEFAULT_JOB_WORKERS),
+            poll_interval: Duration::from_millis(env_or(JOB_POLL_INTERVAL_MS, DEFAULT_JOB_POLL_INTERVAL_MS)),
+        }
+    }
+}
+
+// Parses a cron expression read from the environment, panicking on misconfiguration like `db_pool` does
+pub fn schedule_from_env(key: &str, default: &str) -> Schedule {
+    let expression: String = env_or(key, default.to_string());
+    Schedule::from_str(&expression)
+        .unwrap_or_else(|e| panic!("Invalid cron expression {} for {}: {}", expression, key, e))
+}
+
+pub struct JobRunner {
+    job_service: Arc<dyn JobService>,
+    handlers: HashMap<String, Arc<dyn JobHandler>>,
+    schedules: Vec<(String, Schedule)>,
+    config: JobRunnerConfig,
+}
+
+impl JobRunner {
+    pub fn new(job_service: Arc<dyn JobService>, config: JobRunnerConfig) -> Self {
+        JobRunner {
+            job_service,
+            handlers: HashMap::new(),
+            schedules: Vec::new(),
+            config,
+        }
+    }
+
+    pub fn register(mut self, kind: &str, handler: Arc<dyn JobHandler>) -> Self {
+        self.handlers.insert(kind.to_string(), handler);
+        self
+    }
+
+    // Enqueues a job of `kind` every time the cron schedule fires
+    pub fn schedule(mut self, kind: &str, schedule: Schedule) -> Self {
+        self.schedules.push((kind.to_string(), schedule));
+        self
+    }
+
+    pub fn start(self) -> JobRunnerHandle {
+        let (shutdown, shutdown_signal) = watch::channel(false);
+        let handlers = Arc::new(self.handlers);
+        let mut tasks = Vec::new();
+        for _ in 0..self.config.workers {
+            tasks.push(rt::spawn(work(
+                self.job_service.clone(),
+                handlers.clone(),
+                self.config.poll_interval,
+                shutdown_signal.clone(),
+            )));
+        }
+        if !self.schedules.is_empty() {
+            tasks.push(rt::spawn(enqueue_scheduled(self.job_service.clone(), self.schedules, shutdown_signal)));
+        }
+        info!("Started {} job workers", self.config.workers);
+        JobRunnerHandle { shutdown, tasks }
+    }
+}
+
+pub struct JobRunnerHandle {
+    shutdown: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl JobRunnerHandle {
+    // Stops polling for new jobs and waits for running jobs to finish
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        for task in self.tasks {
+            let _ = task.await;
+        }
+        info!("Job workers stopped");
+    }
+}
+
+// Resolves once shutdown was requested or the runner handle was dropped
+async fn shutdown_requested(shutdown: &mut watch::Receiver<bool>) {
+    while !*shutdown.borrow() {
+        if shutdown.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn work(
+    job_service: Arc<dyn JobService>,
+    handlers: Arc<HashMap<String, Arc<dyn JobHandler>>>,
+    poll_interval: Duration,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let kinds = handlers.keys().cloned().collect::<Vec<_>>();
+    while !*shutdown.borrow() {
+        match job_service.claim_next(&kinds).await {
+            Ok(Some(job)) => {
+                run_job(job_service.as_ref(), handlers.as_ref(), job).await;
+                continue;
+            }
+            Ok(None) => {}
+            Err(e) => error!("Could not claim job: {}", e),
+        }
+        tokio::select! {
+            _ = rt::time::sleep(poll_interval) => {}
+            _ = shutdown_requested(&mut shutdown) => {}
+        }
+    }
+}
+
+async fn run_job(job_service: &dyn JobService, handlers: &HashMap<String, Arc<dyn JobHandler>>, job: Job) {
+    let handler = match handlers.get(&job.kind) {
+        Some(handler) => handler,
+        None => return,
+    };
+    // Jobs work across users and tenants
+    let result = match Principal::system().scope(handler.run(&job)).await {
+        Ok(()) => job_service.complete(&job).await,
+        Err(e) => {
+            warn!("Job {} ({}) failed on attempt {}/{}: {}", job.id, job.kind, job.attempts, job.max_attempts, e);
+            job_service.fail(&job, &e).await
+        }
+    };
+    if let Err(e) = result {
+        error!("Could not record outcome of job {}: {}", job.id, e);
+    }
+}
+
+async fn enqueue_scheduled(
+    job_service: Arc<dyn JobService>,
+    schedules: Vec<(String, Schedule)>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut next_runs: Vec<Option<DateTime<Utc>>> = schedules.iter()
+        .map(|(_, schedule)| schedule.upcoming(Utc).next())
+        .collect();
+    loop {
+        let now = Utc::now();
+        for ((kind, schedule), next_run) in schedules.iter().zip(next_runs.iter_mut()) {
+            let Some(run_at) = *next_run else { continue };
+            if run_at > now {
+                continue;
+            }
+            // The dedup key makes every fire time enqueue a single job across all application instances
+            let job = CreateJob {
+                kind: kind.clone(),
+                payload: json!({}),
+                run_at,
+                max_attempts: DEFAULT_JOB_MAX_ATTEMPTS,
+                dedup_key: Some(format!("{}@{}", kind, run_at.to_rfc3339())),
+            };
+            if let Err(e) = job_service.enqueue(job).await {
+                error!("Could not enqueue scheduled job {}: {}", kind, e);
+            }
+            *next_run = schedule.after(&now).next();
+        }
+        let wait = next_runs.iter().flatten().min()
+            .map(|run_at| (*run_at - Utc::now()).to_std().unwrap_or_default())
+            .unwrap_or(IDLE_SCHEDULER_INTERVAL);
+        tokio::select! {
+            _ = rt::time::sleep(wait) => {}
+            _ = shutdown_requested(&mut shutdown) => break,
+        }
+    }
+}
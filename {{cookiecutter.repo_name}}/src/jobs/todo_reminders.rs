@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::{error, info};
+use crate::domain::error::CommonError;
+use crate::domain::models::job::Job;
+use crate::domain::services::reminder::ReminderNotifier;
+use crate::domain::services::todo::TodoService;
+use crate::jobs::handler::JobHandler;
+
+pub const KIND: &str = "todo_reminders";
+pub const BATCH_SIZE: i64 = 100;
+
+pub struct TodoRemindersJob {
+    pub todo_service: Arc<dyn TodoService>,
+    pub notifier: Arc<dyn ReminderNotifier>,
+}
+
+#[async_trait]
+impl JobHandler for TodoRemindersJob {
+    async fn run(&self, _job: &Job) -> Result<(), CommonError> {
+        let due = self.todo_service.due_reminders(Utc::now(), BATCH_SIZE).await?;
+        let mut sent = Vec::with_capacity(due.len());
+        let mut failure = None;
+        for todo in due {
+            match self.notifier.notify(&todo).await {
+                Ok(()) => sent.push(todo),
+                Err(e) => {
+                    error!("Could not send reminder for todo {}: {}", todo.id, e);
+                    failure = Some(e);
+                }
+            }
+        }
+        // Reminders that were sent are recorded even if others failed, so a retry only resends the failures
+        self.todo_service.mark_reminded(&sent).await?;
+        info!("Sent {} todo reminders", sent.len());
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
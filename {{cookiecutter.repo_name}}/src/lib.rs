@@ -6,3 +6,5 @@ pub mod services;
 pub mod infrastructure;
 pub mod api;
 pub mod create_app;
+pub mod create_job_runner;
+pub mod jobs;
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use actix_web::{HttpServer};
 use actix_clean_architecture::{container::Container, create_app::create_app};
+use actix_clean_architecture::create_job_runner::create_job_runner;
 
 
 #[cfg(test)]
@@ -9,8 +10,11 @@ mod tests;
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     let container = Arc::new(Container::new());
+    container.auth_service.seed_admins().await.expect("Failed to seed the admins");
+    let job_runner = create_job_runner(container.clone()).start();
     let server = HttpServer::new(move || { create_app(container.clone()) })
     .bind(("127.0.0.1", 8080))?;
-    server.run().await
+    let result = server.run().await;
+    job_runner.shutdown().await;
+    result
 }
-
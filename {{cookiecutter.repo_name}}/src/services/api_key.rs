@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::domain::constants::{API_KEY_LAST_USED_INTERVAL_SECS, API_KEY_PREFIX, MAX_API_KEY_NAME_LENGTH};
+use crate::domain::error::{CommonError, NOT_FOUND_ERROR_CODE, UNAUTHORIZED_ERROR_CODE, VALIDATION_ERROR_CODE};
+use crate::domain::models::api_key::{ApiKey, CreateApiKey, IssuedApiKey, NewApiKey};
+use crate::domain::models::authorization::{Permission, Principal};
+use crate::domain::models::tenant::Tenant;
+use crate::domain::models::user::User;
+use crate::domain::repositories::api_key::ApiKeyRepository;
+use crate::domain::repositories::user::UserRepository;
+use crate::domain::services::api_key::ApiKeyService;
+
+#[derive(Clone)]
+pub struct ApiKeyServiceImpl {
+    pub repository: Arc<dyn ApiKeyRepository>,
+    pub user_repository: Arc<dyn UserRepository>,
+}
+
+fn invalid_api_key() -> CommonError {
+    CommonError {
+        message: "Invalid or expired API key".to_string(),
+        code: UNAUTHORIZED_ERROR_CODE,
+    }
+}
+
+fn not_found(api_key_id: i32) -> CommonError {
+    CommonError {
+        message: format!("API key {} not found", api_key_id),
+        code: NOT_FOUND_ERROR_CODE,
+    }
+}
+
+// Keys are random enough that a plain hash is as good as a password hash, and much cheaper
+// to check on every request
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+impl ApiKeyServiceImpl {
+    pub fn new(repository: Arc<dyn ApiKeyRepository>, user_repository: Arc<dyn UserRepository>) -> Self {
+        ApiKeyServiceImpl {
+            repository,
+            user_repository,
+        }
+    }
+
+    fn validate(api_key: &CreateApiKey) -> Result<(), CommonError> {
+        let name = api_key.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_API_KEY_NAME_LENGTH {
+            return Err(CommonError {
+                message: format!("API key names must be between 1 and {} characters long", MAX_API_KEY_NAME_LENGTH),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        if api_key.scopes.is_empty() {
+            return Err(CommonError {
+                message: "API keys need at least one scope".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        if api_key.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+            return Err(CommonError {
+                message: "API keys must expire in the future".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+
+    // Users may manage their own keys, admins those of everyone
+    async fn find_managed(&self, user: &User, api_key_id: i32) -> Result<ApiKey, CommonError> {
+        let api_key = self.repository
+            .get(api_key_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if api_key.user_id != user.id && !Principal::current().can(Permission::ManageUsers) {
+            return Err(not_found(api_key_id));
+        }
+        Ok(api_key)
+    }
+}
+
+#[async_trait]
+impl ApiKeyService for ApiKeyServiceImpl {
+    async fn create(&self, user: &User, api_key: CreateApiKey) -> Result<IssuedApiKey, CommonError> {
+        Self::validate(&api_key)?;
+        // Keys cannot do more than whoever creates them
+        let principal = Principal::current();
+        for scope in &api_key.scopes {
+            principal.authorize(*scope)?;
+        }
+        let mut scopes = api_key.scopes.clone();
+        scopes.sort_by_key(Permission::as_str);
+        scopes.dedup();
+        let prefix = Uuid::new_v4().simple().to_string()[..12].to_string();
+        let key = format!("{}{}_{}", API_KEY_PREFIX, prefix, Uuid::new_v4().simple());
+        let created = self.repository
+            .create(&NewApiKey {
+                user_id: user.id,
+                name: api_key.name.trim().to_string(),
+                prefix,
+                key_hash: hash_key(&key),
+                scopes,
+                expires_at: api_key.expires_at,
+            })
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        Ok(IssuedApiKey { api_key: created, key })
+    }
+
+    async fn list(&self, user: &User) -> Result<Vec<ApiKey>, CommonError> {
+        self.repository
+            .list_for_user(user.id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn revoke(&self, user: &User, api_key_id: i32) -> Result<ApiKey, CommonError> {
+        self.find_managed(user, api_key_id).await?;
+        self.repository
+            .revoke(api_key_id, Utc::now())
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn authenticate(&self, key: &str) -> Result<(User, ApiKey), CommonError> {
+        let prefix = key.strip_prefix(API_KEY_PREFIX)
+            .and_then(|rest| rest.split_once('_'))
+            .map(|(prefix, _)| prefix)
+            .ok_or_else(invalid_api_key)?;
+        let credentials = self.repository
+            .find_credentials(prefix)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?
+            .ok_or_else(invalid_api_key)?;
+        let now = Utc::now();
+        if credentials.key_hash != hash_key(key) || !credentials.api_key.is_active(now) {
+            return Err(invalid_api_key());
+        }
+        // Keys work in the tenant they were created in
+        let tenant = Tenant::new(credentials.tenant_id);
+        let mut api_key = credentials.api_key;
+        let user = tenant.clone()
+            .scope(self.user_repository.get(api_key.user_id))
+            .await
+            .map_err(|e| match e.code {
+                NOT_FOUND_ERROR_CODE => invalid_api_key(),
+                _ => e.into(),
+            })?;
+        let interval = Duration::seconds(API_KEY_LAST_USED_INTERVAL_SECS);
+        if api_key.last_used_at.is_none_or(|last_used_at| now - last_used_at >= interval) {
+            tenant
+                .scope(self.repository.mark_used(api_key.id, now))
+                .await
+                .map_err(|e| -> CommonError { e.into() })?;
+            api_key.last_used_at = Some(now);
+        }
+        Ok((user, api_key))
+    }
+}
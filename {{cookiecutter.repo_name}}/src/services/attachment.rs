@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures_util::StreamExt;
+use log::warn;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::domain::error::{CommonError, PAYLOAD_TOO_LARGE_ERROR_CODE, UNSUPPORTED_MEDIA_TYPE_ERROR_CODE, VALIDATION_ERROR_CODE};
+use crate::domain::models::attachment::{Attachment, ByteRange, CreateAttachment, UploadAttachment};
+use crate::domain::repositories::attachment::AttachmentRepository;
+use crate::domain::repositories::todo::TodoRepository;
+use crate::domain::repositories::todo_list::TodoListRepository;
+use crate::domain::services::attachment::AttachmentService;
+use crate::domain::services::blob_store::{BlobStore, BlobStream};
+use crate::services::todo::check_todo_owner;
+
+#[derive(Clone)]
+pub struct AttachmentLimits {
+    pub max_size: u64,
+    // Allowed content types, `type/*` allows every subtype
+    pub content_types: Vec<String>,
+}
+
+impl AttachmentLimits {
+    fn allows(&self, content_type: &str) -> bool {
+        let essence = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        self.content_types.iter().any(|allowed| match allowed.strip_suffix("/*") {
+            Some(prefix) => essence.split('/').next() == Some(prefix),
+            None => *allowed == essence,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct AttachmentServiceImpl {
+    pub repository: Arc<dyn AttachmentRepository>,
+    pub todo_repository: Arc<dyn TodoRepository>,
+    pub list_repository: Arc<dyn TodoListRepository>,
+    pub blob_store: Arc<dyn BlobStore>,
+    pub limits: AttachmentLimits,
+}
+
+impl AttachmentServiceImpl {
+    pub fn new(
+        repository: Arc<dyn AttachmentRepository>,
+        todo_repository: Arc<dyn TodoRepository>,
+        list_repository: Arc<dyn TodoListRepository>,
+        blob_store: Arc<dyn BlobStore>,
+        limits: AttachmentLimits,
+    ) -> Self {
+        AttachmentServiceImpl {
+            repository,
+            todo_repository,
+            list_repository,
+            blob_store,
+            limits,
+        }
+    }
+
+    // Attachments are changed by whoever may change their todo
+    async fn check_owner(&self, todo_id: i32) -> Result<(), CommonError> {
+        check_todo_owner(self.todo_repository.as_ref(), self.list_repository.as_ref(), todo_id).await
+    }
+
+    // Reads the upload into memory, giving up as soon as it grows past the size limit
+    async fn read_content(&self, mut content: BlobStream) -> Result<BytesMut, CommonError> {
+        let mut buffer = BytesMut::new();
+        while let Some(chunk) = content.next().await {
+            let chunk = chunk?;
+            if (buffer.len() + chunk.len()) as u64 > self.limits.max_size {
+                return Err(CommonError {
+                    message: format!("Attachments must not exceed {} bytes", self.limits.max_size),
+                    code: PAYLOAD_TOO_LARGE_ERROR_CODE,
+                });
+            }
+            buffer.extend_from_slice(&chunk);
+        }
+        Ok(buffer)
+    }
+}
+
+#[async_trait]
+impl AttachmentService for AttachmentServiceImpl {
+    async fn upload(&self, todo_id: i32, upload: UploadAttachment) -> Result<Attachment, CommonError> {
+        let file_name = upload.file_name.trim().to_string();
+        if file_name.is_empty() {
+            return Err(CommonError {
+                message: "File name must not be empty".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        if !self.limits.allows(&upload.content_type) {
+            return Err(CommonError {
+                message: format!("Content type {} is not allowed", upload.content_type),
+                code: UNSUPPORTED_MEDIA_TYPE_ERROR_CODE,
+            });
+        }
+        self.check_owner(todo_id).await?;
+        let content = self.read_content(upload.content).await?.freeze();
+        let storage_key = format!("todos/{}/{}", todo_id, Uuid::new_v4().simple());
+        let new_attachment = CreateAttachment {
+            file_name,
+            content_type: upload.content_type.clone(),
+            size: content.len() as i64,
+            checksum: hex::encode(Sha256::digest(&content)),
+            storage_key: storage_key.clone(),
+        };
+        self.blob_store.put(&storage_key, &upload.content_type, content).await?;
+        let created = self.repository.create(todo_id, &new_attachment).await;
+        if created.is_err() {
+            // Do not leave content behind that no attachment refers to
+            if let Err(e) = self.blob_store.delete(&storage_key).await {
+                warn!("Could not remove blob {}: {}", storage_key, e);
+            }
+        }
+        created.map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn list(&self, todo_id: i32) -> Result<Vec<Attachment>, CommonError> {
+        self.repository
+            .list(todo_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn get(&self, todo_id: i32, attachment_id: i32) -> Result<Attachment, CommonError> {
+        self.repository
+            .get(todo_id, attachment_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn download(&self, attachment: &Attachment, range: ByteRange) -> Result<BlobStream, CommonError> {
+        self.blob_store.get(&attachment.storage_key, range).await
+    }
+
+    async fn delete(&self, todo_id: i32, attachment_id: i32) -> Result<(), CommonError> {
+        self.check_owner(todo_id).await?;
+        let attachment = self.repository
+            .delete(todo_id, attachment_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        self.blob_store.delete(&attachment.storage_key).await
+    }
+}
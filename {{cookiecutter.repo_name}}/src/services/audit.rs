@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::error::{CommonError, NOT_FOUND_ERROR_CODE};
+use crate::domain::models::audit::AuditEvent;
+use crate::domain::repositories::audit::{AuditQueryParams, AuditRepository};
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::services::audit::AuditService;
+
+#[derive(Clone)]
+pub struct AuditServiceImpl {
+    pub repository: Arc<dyn AuditRepository>,
+}
+
+impl AuditServiceImpl {
+    pub fn new(repository: Arc<dyn AuditRepository>) -> Self {
+        AuditServiceImpl {
+            repository,
+        }
+    }
+}
+
+#[async_trait]
+impl AuditService for AuditServiceImpl {
+    // Purged todos keep their history, so this looks at the events rather than the todo itself.
+    // Every todo has at least its creation event, no events means there never was such a todo.
+    async fn history(&self, todo_id: i32, params: QueryParamsImpl) -> Result<ResultPaging<AuditEvent>, CommonError> {
+        let history = self.repository
+            .list(AuditQueryParams {
+                limit: params.limit,
+                offset: params.offset,
+                todo_id: Some(todo_id),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if history.total == 0 {
+            return Err(CommonError {
+                message: format!("Todo {} not found", todo_id),
+                code: NOT_FOUND_ERROR_CODE,
+            });
+        }
+        Ok(history)
+    }
+
+    async fn list(&self, params: AuditQueryParams) -> Result<ResultPaging<AuditEvent>, CommonError> {
+        self.repository
+            .list(params)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
@@ -0,0 +1,334 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use log::{info, warn};
+
+use crate::domain::constants::{MAX_PASSWORD_LENGTH, MIN_PASSWORD_LENGTH};
+use crate::domain::error::{CommonError, CONFLICT_ERROR_CODE, NOT_FOUND_ERROR_CODE, UNAUTHORIZED_ERROR_CODE, VALIDATION_ERROR_CODE};
+use crate::domain::models::auth::{AuthTokens, ExternalIdentity, Login, RegisterUser, TokenClaims, TokenKind};
+use crate::domain::models::authorization::{Permission, Principal, Role};
+use crate::domain::models::tenant::Tenant;
+use crate::domain::models::user::{CreateUser, User};
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::repositories::user::UserRepository;
+use crate::domain::services::auth::AuthService;
+use crate::domain::services::identity_provider::IdentityProvider;
+use crate::domain::services::password_hasher::PasswordHasher;
+use crate::domain::services::token_codec::TokenCodec;
+
+#[derive(Clone)]
+pub struct TokenLifetimes {
+    pub access: Duration,
+    pub refresh: Duration,
+}
+
+#[derive(Clone)]
+pub struct AccessPolicy {
+    // Lower-cased emails of the admins of `admin_tenant`. Registering with one of them makes no
+    // admin, as nobody checked the email: their accounts are seeded with `admin_password`, or
+    // created for external identities whose provider verified the email.
+    pub admin_emails: Vec<String>,
+    pub admin_password: Option<String>,
+    pub admin_tenant: String,
+    // What requests without credentials may do, `None` requires signing in
+    pub anonymous_role: Option<Role>,
+}
+
+#[derive(Clone)]
+pub struct AuthServiceImpl {
+    pub repository: Arc<dyn UserRepository>,
+    pub password_hasher: Arc<dyn PasswordHasher>,
+    pub token_codec: Arc<dyn TokenCodec>,
+    pub lifetimes: TokenLifetimes,
+    pub access: AccessPolicy,
+    // Accepts the access tokens of an external provider alongside our own
+    pub identity_provider: Option<Arc<dyn IdentityProvider>>,
+}
+
+// The same error for every failed sign in, so it does not tell which emails are registered
+fn invalid_credentials() -> CommonError {
+    CommonError {
+        message: "Invalid email or password".to_string(),
+        code: UNAUTHORIZED_ERROR_CODE,
+    }
+}
+
+fn invalid_token() -> CommonError {
+    CommonError {
+        message: "Invalid or expired token".to_string(),
+        code: UNAUTHORIZED_ERROR_CODE,
+    }
+}
+
+impl AuthServiceImpl {
+    pub fn new(
+        repository: Arc<dyn UserRepository>,
+        password_hasher: Arc<dyn PasswordHasher>,
+        token_codec: Arc<dyn TokenCodec>,
+        lifetimes: TokenLifetimes,
+        access: AccessPolicy,
+        identity_provider: Option<Arc<dyn IdentityProvider>>,
+    ) -> Self {
+        AuthServiceImpl {
+            repository,
+            password_hasher,
+            token_codec,
+            lifetimes,
+            access,
+            identity_provider,
+        }
+    }
+
+    // The role of a user with a verified email. Emails are only unique within a tenant, so anyone
+    // could claim an admin email in another one.
+    fn initial_role(&self, email: &str) -> Role {
+        let admin = Tenant::current_id() == self.access.admin_tenant
+            && self.access.admin_emails.iter().any(|admin_email| admin_email == email);
+        if admin { Role::Admin } else { Role::Member }
+    }
+
+    fn normalize_email(email: &str) -> String {
+        email.trim().to_lowercase()
+    }
+
+    fn validate_email(email: &str) -> Result<(), CommonError> {
+        let valid = match email.split_once('@') {
+            Some((local, domain)) => !local.is_empty() && !domain.is_empty() && !domain.contains('@'),
+            None => false,
+        };
+        if !valid || email.chars().any(char::is_whitespace) {
+            return Err(CommonError {
+                message: format!("Invalid email {}", email),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_password(password: &str) -> Result<(), CommonError> {
+        let length = password.chars().count();
+        if !(MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&length) {
+            return Err(CommonError {
+                message: format!("Passwords must be between {} and {} characters long", MIN_PASSWORD_LENGTH, MAX_PASSWORD_LENGTH),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+
+    fn issue_tokens(&self, user: &User) -> Result<AuthTokens, CommonError> {
+        let now = Utc::now();
+        let claims = |kind: TokenKind, lifetime: Duration| TokenClaims {
+            user_id: user.id,
+            tenant: user.tenant_id.clone(),
+            kind,
+            issued_at: now,
+            expires_at: now + lifetime,
+        };
+        let access = claims(TokenKind::Access, self.lifetimes.access);
+        Ok(AuthTokens {
+            access_token: self.token_codec.encode(&access)?,
+            refresh_token: self.token_codec.encode(&claims(TokenKind::Refresh, self.lifetimes.refresh))?,
+            expires_at: access.expires_at,
+        })
+    }
+
+    // The user a token of the given kind was issued to, as long as they still exist. Tokens
+    // name the tenant of their user, which need not be the one the request was made to.
+    async fn token_user(&self, token: &str, kind: TokenKind) -> Result<User, CommonError> {
+        let claims = self.token_codec.decode(token).map_err(|_| invalid_token())?;
+        if claims.kind != kind {
+            return Err(invalid_token());
+        }
+        Tenant::new(claims.tenant)
+            .scope(self.repository.get(claims.user_id))
+            .await
+            .map_err(|e| match e.code {
+                NOT_FOUND_ERROR_CODE => invalid_token(),
+                _ => e.into(),
+            })
+    }
+
+    // The user behind an external identity, created on first sight. Existing accounts are only
+    // linked by email once the provider has verified it. Roles the provider asserts, if it is
+    // trusted with them, replace the stored one.
+    async fn external_user(&self, identity: ExternalIdentity) -> Result<User, CommonError> {
+        let email = Self::normalize_email(&identity.email);
+        let linked = self.repository
+            .find_external(&identity.subject)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        let user = match linked {
+            Some(user) => user,
+            None => match self.repository.find_credentials(&email).await.map_err(|e| -> CommonError { e.into() })? {
+                Some(_) if !identity.email_verified => return Err(CommonError {
+                    message: format!("The provider has not verified the email {}", email),
+                    code: UNAUTHORIZED_ERROR_CODE,
+                }),
+                Some(credentials) => self.repository
+                    .link_external(credentials.user.id, &identity.subject)
+                    .await
+                    .map_err(|e| -> CommonError { e.into() })?,
+                None => self.repository
+                    .create(&CreateUser {
+                        email: email.clone(),
+                        name: identity.name.clone(),
+                        password_hash: None,
+                        role: match identity.role {
+                            Some(role) => role,
+                            None if identity.email_verified => self.initial_role(&email),
+                            None => Role::Member,
+                        },
+                        external_id: Some(identity.subject.clone()),
+                    })
+                    .await
+                    .map_err(|e| -> CommonError { e.into() })?,
+            },
+        };
+        match identity.role {
+            Some(role) if role != user.role => self.repository
+                .set_role(user.id, role)
+                .await
+                .map_err(|e| -> CommonError { e.into() }),
+            _ => Ok(user),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthService for AuthServiceImpl {
+    async fn register(&self, user: RegisterUser) -> Result<User, CommonError> {
+        let email = Self::normalize_email(&user.email);
+        Self::validate_email(&email)?;
+        Self::validate_password(&user.password)?;
+        let password_hash = self.password_hasher.hash(user.password).await?;
+        self.repository
+            .create(&CreateUser {
+                email: email.clone(),
+                name: user.name.map(|name| name.trim().to_string()).filter(|name| !name.is_empty()),
+                password_hash: Some(password_hash),
+                role: Role::Member,
+                external_id: None,
+            })
+            .await
+            .map_err(|e| match e.code {
+                CONFLICT_ERROR_CODE => CommonError {
+                    message: format!("A user with email {} already exists", email),
+                    code: CONFLICT_ERROR_CODE,
+                },
+                _ => e.into(),
+            })
+    }
+
+    async fn login(&self, login: Login) -> Result<AuthTokens, CommonError> {
+        let credentials = self.repository
+            .find_credentials(&Self::normalize_email(&login.email))
+            .await
+            .map_err(|e| -> CommonError { e.into() })?
+            .ok_or_else(invalid_credentials)?;
+        let password_hash = credentials.password_hash.ok_or_else(invalid_credentials)?;
+        if !self.password_hasher.verify(login.password, password_hash).await? {
+            return Err(invalid_credentials());
+        }
+        self.issue_tokens(&credentials.user)
+    }
+
+    async fn refresh(&self, refresh_token: &str) -> Result<AuthTokens, CommonError> {
+        let user = self.token_user(refresh_token, TokenKind::Refresh).await?;
+        self.issue_tokens(&user)
+    }
+
+    async fn authenticate(&self, access_token: &str) -> Result<User, CommonError> {
+        match (self.token_user(access_token, TokenKind::Access).await, &self.identity_provider) {
+            (Err(e), Some(identity_provider)) if e.code == UNAUTHORIZED_ERROR_CODE => {
+                let identity = identity_provider.verify(access_token).await.map_err(|_| invalid_token())?;
+                // Providers serving several tenants say which one the user belongs to, the tenant
+                // the request was made to has no say
+                let tenant = Tenant::parse(&identity.tenant).map_err(|_| invalid_token())?;
+                tenant.scope(self.external_user(identity)).await
+            }
+            (result, _) => result,
+        }
+    }
+
+    fn anonymous(&self) -> Principal {
+        Principal::anonymous(self.access.anonymous_role)
+    }
+
+    async fn seed_admins(&self) -> Result<(), CommonError> {
+        let Some(password) = &self.access.admin_password else {
+            return Ok(());
+        };
+        Self::validate_password(password)?;
+        for email in &self.access.admin_emails {
+            let tenant = Tenant::new(self.access.admin_tenant.clone());
+            let credentials = tenant.clone()
+                .scope(self.repository.find_credentials(email))
+                .await
+                .map_err(|e| -> CommonError { e.into() })?;
+            match credentials {
+                Some(credentials) if credentials.user.role != Role::Admin => {
+                    warn!("Not seeding admin {}, who already signed up as a {}", email, credentials.user.role);
+                }
+                Some(_) => {}
+                None => {
+                    let password_hash = self.password_hasher.hash(password.clone()).await?;
+                    tenant
+                        .scope(self.repository.create(&CreateUser {
+                            email: email.clone(),
+                            name: None,
+                            password_hash: Some(password_hash),
+                            role: Role::Admin,
+                            external_id: None,
+                        }))
+                        .await
+                        .map_err(|e| -> CommonError { e.into() })?;
+                    info!("Seeded admin {}", email);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn list_users(&self, params: QueryParamsImpl) -> Result<ResultPaging<User>, CommonError> {
+        Principal::current().authorize(Permission::ManageUsers)?;
+        self.repository
+            .list(params)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn set_role(&self, user_id: i32, role: Role) -> Result<User, CommonError> {
+        let principal = Principal::current();
+        principal.authorize(Permission::ManageUsers)?;
+        // Keeps admins from locking themselves out
+        if principal.user_id == Some(user_id) && role != Role::Admin {
+            return Err(CommonError {
+                message: "Admins cannot change their own role".to_string(),
+                code: CONFLICT_ERROR_CODE,
+            });
+        }
+        self.repository
+            .set_role(user_id, role)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn set_groups(&self, user_id: i32, groups: Vec<String>) -> Result<User, CommonError> {
+        Principal::current().authorize(Permission::ManageUsers)?;
+        let mut groups = groups.iter().map(|group| group.trim().to_string()).collect::<Vec<_>>();
+        if groups.iter().any(|group| group.is_empty()) {
+            return Err(CommonError {
+                message: "Group names must not be empty".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        groups.sort();
+        groups.dedup();
+        self.repository
+            .set_groups(user_id, &groups)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
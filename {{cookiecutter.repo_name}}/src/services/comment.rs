@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::constants::MAX_COMMENT_LENGTH;
+use crate::domain::error::{CommonError, VALIDATION_ERROR_CODE};
+use crate::domain::models::authorization::Principal;
+use crate::domain::models::comment::{Comment, CreateComment, UpdateComment};
+use crate::domain::repositories::comment::CommentRepository;
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::services::comment::CommentService;
+
+#[derive(Clone)]
+pub struct CommentServiceImpl {
+    pub repository: Arc<dyn CommentRepository>,
+}
+
+impl CommentServiceImpl {
+    pub fn new(repository: Arc<dyn CommentRepository>) -> Self {
+        CommentServiceImpl {
+            repository,
+        }
+    }
+
+    fn validate_body(body: &str) -> Result<(), CommonError> {
+        if body.trim().is_empty() || body.chars().count() > MAX_COMMENT_LENGTH {
+            return Err(CommonError {
+                message: format!("Comment must be between 1 and {} characters", MAX_COMMENT_LENGTH),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+
+    // Comments are changed by their author or whoever may change any todo. As with todos nobody
+    // owns, anyone who may write can change anonymous ones.
+    async fn check_author(&self, todo_id: i32, comment_id: i32) -> Result<(), CommonError> {
+        let comment = self.repository
+            .get(todo_id, comment_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        Principal::current().authorize_modify(comment.author_id)
+    }
+}
+
+#[async_trait]
+impl CommentService for CommentServiceImpl {
+    async fn create(&self, todo_id: i32, comment: CreateComment) -> Result<Comment, CommonError> {
+        Self::validate_body(&comment.body)?;
+        self.repository
+            .create(todo_id, &comment)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn list(&self, todo_id: i32, params: QueryParamsImpl) -> Result<ResultPaging<Comment>, CommonError> {
+        self.repository
+            .list(todo_id, params)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn get(&self, todo_id: i32, comment_id: i32) -> Result<Comment, CommonError> {
+        self.repository
+            .get(todo_id, comment_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn update(&self, todo_id: i32, comment_id: i32, comment: UpdateComment) -> Result<Comment, CommonError> {
+        Self::validate_body(&comment.body)?;
+        self.check_author(todo_id, comment_id).await?;
+        self.repository
+            .update(todo_id, comment_id, &comment)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn delete(&self, todo_id: i32, comment_id: i32) -> Result<(), CommonError> {
+        self.check_author(todo_id, comment_id).await?;
+        self.repository
+            .delete(todo_id, comment_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::domain::error::{CommonError, CONFLICT_ERROR_CODE, UNPROCESSABLE_ERROR_CODE};
+use crate::domain::models::idempotency::StoredResponse;
+use crate::domain::repositories::idempotency::IdempotencyRepository;
+use crate::domain::services::idempotency::IdempotencyService;
+
+#[derive(Clone)]
+pub struct IdempotencyServiceImpl {
+    pub repository: Arc<dyn IdempotencyRepository>,
+    pub ttl: Duration,
+}
+
+impl IdempotencyServiceImpl {
+    pub fn new(repository: Arc<dyn IdempotencyRepository>, ttl: Duration) -> Self {
+        IdempotencyServiceImpl {
+            repository,
+            ttl,
+        }
+    }
+}
+
+#[async_trait]
+impl IdempotencyService for IdempotencyServiceImpl {
+    async fn begin(&self, principal: &str, key: &str, request_fingerprint: &str) -> Result<Option<StoredResponse>, CommonError> {
+        let existing = self.repository
+            .claim(principal, key, request_fingerprint, Utc::now() + self.ttl)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        let Some(existing) = existing else {
+            return Ok(None);
+        };
+        if existing.request_fingerprint != request_fingerprint {
+            return Err(CommonError {
+                message: "Idempotency key was already used for a different request".to_string(),
+                code: UNPROCESSABLE_ERROR_CODE,
+            });
+        }
+        match existing.response {
+            Some(response) => Ok(Some(response)),
+            None => Err(CommonError {
+                message: "A request with this idempotency key is still being processed".to_string(),
+                code: CONFLICT_ERROR_CODE,
+            }),
+        }
+    }
+
+    async fn complete(&self, principal: &str, key: &str, response: StoredResponse) -> Result<(), CommonError> {
+        self.repository
+            .store_response(principal, key, &response)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn abandon(&self, principal: &str, key: &str) -> Result<(), CommonError> {
+        self.repository
+            .release(principal, key)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn purge_expired(&self) -> Result<usize, CommonError> {
+        self.repository
+            .purge_expired(Utc::now())
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
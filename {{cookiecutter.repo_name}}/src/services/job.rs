@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::domain::constants::{JOB_BACKOFF_BASE_SECS, JOB_BACKOFF_MAX_SECS};
+use crate::domain::error::CommonError;
+use crate::domain::models::job::{CreateJob, Job};
+use crate::domain::repositories::job::JobRepository;
+use crate::domain::services::job::JobService;
+
+#[derive(Clone)]
+pub struct JobServiceImpl {
+    pub repository: Arc<dyn JobRepository>,
+    // Running jobs locked for longer than this are considered abandoned by a crashed worker
+    pub lock_timeout: Duration,
+}
+
+impl JobServiceImpl {
+    pub fn new(repository: Arc<dyn JobRepository>, lock_timeout: Duration) -> Self {
+        JobServiceImpl {
+            repository,
+            lock_timeout,
+        }
+    }
+
+    pub fn backoff(attempts: i32) -> Duration {
+        let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+        Duration::seconds((JOB_BACKOFF_BASE_SECS * 2_i64.pow(exponent)).min(JOB_BACKOFF_MAX_SECS))
+    }
+}
+
+#[async_trait]
+impl JobService for JobServiceImpl {
+    async fn enqueue(&self, job: CreateJob) -> Result<Option<Job>, CommonError> {
+        self.repository
+            .enqueue(&job)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn claim_next(&self, kinds: &[String]) -> Result<Option<Job>, CommonError> {
+        self.repository
+            .claim_next(kinds, Utc::now() - self.lock_timeout)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn complete(&self, job: &Job) -> Result<(), CommonError> {
+        self.repository
+            .complete(job.id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn fail(&self, job: &Job, error: &CommonError) -> Result<(), CommonError> {
+        let result = if job.attempts >= job.max_attempts {
+            self.repository.fail(job.id, &error.message).await
+        } else {
+            self.repository.retry(job.id, &error.message, Utc::now() + Self::backoff(job.attempts)).await
+        };
+        result.map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn purge_finished(&self, older_than: Duration) -> Result<usize, CommonError> {
+        self.repository
+            .purge_finished(Utc::now() - older_than)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
@@ -1 +1,11 @@
 pub mod todo;
+pub mod idempotency;
+pub mod job;
+pub mod tag;
+pub mod todo_list;
+pub mod comment;
+pub mod attachment;
+pub mod audit;
+pub mod auth;
+pub mod api_key;
+pub mod tenant;
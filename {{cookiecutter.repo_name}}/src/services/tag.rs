@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::error::{CommonError, VALIDATION_ERROR_CODE};
+use crate::domain::models::tag::{CreateTag, Tag, UpdateTag};
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::repositories::tag::TagRepository;
+use crate::domain::services::tag::TagService;
+
+#[derive(Clone)]
+pub struct TagServiceImpl {
+    pub repository: Arc<dyn TagRepository>,
+}
+
+impl TagServiceImpl {
+    pub fn new(repository: Arc<dyn TagRepository>) -> Self {
+        TagServiceImpl {
+            repository,
+        }
+    }
+
+    fn validate_name(name: &str) -> Result<(), CommonError> {
+        if name.trim().is_empty() {
+            return Err(CommonError {
+                message: "Tag name must not be empty".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+
+    // Colors are `#rrggbb` hex codes
+    fn validate_color(color: &str) -> Result<(), CommonError> {
+        let valid = color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+        if !valid {
+            return Err(CommonError {
+                message: format!("Invalid tag color {}", color),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TagService for TagServiceImpl {
+    async fn create(&self, tag: CreateTag) -> Result<Tag, CommonError> {
+        Self::validate_name(&tag.name)?;
+        if let Some(color) = &tag.color {
+            Self::validate_color(color)?;
+        }
+        let tag = CreateTag { name: tag.name.trim().to_string(), ..tag };
+        self.repository
+            .create(&tag)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn list(&self, params: QueryParamsImpl) -> Result<ResultPaging<Tag>, CommonError> {
+        self.repository
+            .list(params)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn get(&self, tag_id: i32) -> Result<Tag, CommonError> {
+        self.repository
+            .get(tag_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn update(&self, tag_id: i32, tag: UpdateTag) -> Result<Tag, CommonError> {
+        if tag.is_empty() {
+            return Err(CommonError {
+                message: "Nothing to update".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        if let Some(name) = &tag.name {
+            Self::validate_name(name)?;
+        }
+        if let Some(Some(color)) = &tag.color {
+            Self::validate_color(color)?;
+        }
+        let tag = UpdateTag { name: tag.name.map(|name| name.trim().to_string()), ..tag };
+        self.repository
+            .update(tag_id, &tag)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn delete(&self, tag_id: i32) -> Result<(), CommonError> {
+        self.repository
+            .delete(tag_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn list_for_todo(&self, todo_id: i32) -> Result<Vec<Tag>, CommonError> {
+        self.repository
+            .list_for_todo(todo_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn attach(&self, todo_id: i32, tag_ids: Vec<i32>) -> Result<Vec<Tag>, CommonError> {
+        if tag_ids.is_empty() {
+            return Err(CommonError {
+                message: "No tags to attach".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        self.repository
+            .attach(todo_id, &tag_ids)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn detach(&self, todo_id: i32, tag_id: i32) -> Result<Vec<Tag>, CommonError> {
+        self.repository
+            .detach(todo_id, tag_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::error::{CommonError, FORBIDDEN_ERROR_CODE, NOT_FOUND_ERROR_CODE, VALIDATION_ERROR_CODE};
+use crate::domain::models::authorization::{Permission, Principal};
+use crate::domain::models::tenant::{CreateTenant, RegisteredTenant, Tenant, UpdateTenant};
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::repositories::tenant::TenantRepository;
+use crate::domain::services::tenant::TenantService;
+
+#[derive(Clone)]
+pub struct TenantServiceImpl {
+    pub repository: Arc<dyn TenantRepository>,
+    pub admin_tenant: String,
+}
+
+impl TenantServiceImpl {
+    pub fn new(repository: Arc<dyn TenantRepository>, admin_tenant: String) -> Self {
+        TenantServiceImpl {
+            repository,
+            admin_tenant,
+        }
+    }
+
+    // The registry spans all tenants, so it is up to the admins of the admin tenant
+    fn authorize(&self) -> Result<(), CommonError> {
+        Principal::current().authorize(Permission::ManageTenants)?;
+        if Tenant::current_id() != self.admin_tenant {
+            return Err(CommonError {
+                message: "Only admins of the admin tenant can manage tenants".to_string(),
+                code: FORBIDDEN_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TenantService for TenantServiceImpl {
+    fn admin_tenant(&self) -> &str {
+        &self.admin_tenant
+    }
+
+    async fn find(&self, tenant_id: &str) -> Result<RegisteredTenant, CommonError> {
+        self.repository
+            .get(tenant_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+            .map_err(|e| match e.code {
+                NOT_FOUND_ERROR_CODE => CommonError {
+                    message: format!("Unknown tenant {}", tenant_id),
+                    code: NOT_FOUND_ERROR_CODE,
+                },
+                _ => e,
+            })
+    }
+
+    async fn create(&self, tenant: CreateTenant) -> Result<RegisteredTenant, CommonError> {
+        self.authorize()?;
+        let tenant = CreateTenant { id: Tenant::parse(&tenant.id)?.id, ..tenant };
+        self.repository
+            .create(&tenant)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn list(&self, params: QueryParamsImpl) -> Result<ResultPaging<RegisteredTenant>, CommonError> {
+        self.authorize()?;
+        self.repository
+            .list(params)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn update(&self, tenant_id: &str, tenant: UpdateTenant) -> Result<RegisteredTenant, CommonError> {
+        self.authorize()?;
+        if tenant.is_empty() {
+            return Err(CommonError {
+                message: "Nothing to update".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        self.repository
+            .update(tenant_id, &tenant)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
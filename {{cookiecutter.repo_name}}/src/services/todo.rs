@@ -1,22 +1,314 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
-use crate::domain::error::CommonError;
-use crate::domain::models::todo::{CreateTodo, Todo};
+use crate::domain::error::{CommonError, CONFLICT_ERROR_CODE, NOT_FOUND_ERROR_CODE, UNPROCESSABLE_ERROR_CODE, VALIDATION_ERROR_CODE};
+use crate::domain::constants::{DEFAULT_OCCURRENCE_PREVIEW_LIMIT, MAX_BULK_OPERATIONS, MAX_OCCURRENCE_PREVIEW_LIMIT, MAX_SEARCH_QUERY_LENGTH};
+use crate::domain::models::authorization::{Permission, Principal};
+use crate::domain::models::recurrence::Recurrence;
+use crate::domain::models::todo::{CreateTodo, MoveTodo, Todo, TodoOperation, TodoPlacement, TodoSearchHit, TodoStatus, TodoWorkflow, UpdateTodo};
+use crate::domain::models::todo_item::{CreateTodoItem, TodoItem, UpdateTodoItem};
+use crate::domain::repositories::comment::CommentRepository;
 use crate::domain::repositories::repository::ResultPaging;
+use crate::domain::repositories::tag::TagRepository;
 use crate::domain::repositories::todo::{TodoQueryParams, TodoRepository};
+use crate::domain::repositories::todo_item::TodoItemRepository;
+use crate::domain::repositories::todo_list::TodoListRepository;
+use crate::domain::repositories::todo_search::{TodoSearchParams, TodoSearchRepository};
 use crate::domain::services::todo::TodoService;
 
+// Only admins may change todos owned by someone else. Todos of lists someone owns are
+// changed by those who may write to the list instead, whoever owns the todo.
+pub(crate) async fn check_todo_owner(
+    repository: &dyn TodoRepository,
+    list_repository: &dyn TodoListRepository,
+    todo_id: i32,
+) -> Result<(), CommonError> {
+    let principal = Principal::current();
+    if principal.can(Permission::ModifyAnyTodo) {
+        return Ok(());
+    }
+    let ownership = repository
+        .ownership(todo_id)
+        .await
+        .map_err(|e| -> CommonError { e.into() })?;
+    if let Some(list_id) = ownership.list_id {
+        let access = list_repository
+            .access(list_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if access.owner_id.is_some() {
+            return access.authorize_write(&principal);
+        }
+    }
+    principal.authorize_modify(ownership.owner_id)
+}
+
+// Configurable behaviour of `TodoServiceImpl`
+#[derive(Clone)]
+pub struct TodoRules {
+    pub workflow: TodoWorkflow,
+    // Completes a todo once every item of its checklist is done
+    pub auto_complete: bool,
+}
+
 #[derive(Clone)]
 pub struct TodoServiceImpl {
     pub repository: Arc<dyn TodoRepository>,
+    pub tag_repository: Arc<dyn TagRepository>,
+    pub list_repository: Arc<dyn TodoListRepository>,
+    pub item_repository: Arc<dyn TodoItemRepository>,
+    pub search_repository: Arc<dyn TodoSearchRepository>,
+    pub comment_repository: Arc<dyn CommentRepository>,
+    pub rules: TodoRules,
 }
 
 impl TodoServiceImpl {
-    pub fn new(repository: Arc<dyn TodoRepository>) -> Self {
+    pub fn new(
+        repository: Arc<dyn TodoRepository>,
+        tag_repository: Arc<dyn TagRepository>,
+        list_repository: Arc<dyn TodoListRepository>,
+        item_repository: Arc<dyn TodoItemRepository>,
+        search_repository: Arc<dyn TodoSearchRepository>,
+        comment_repository: Arc<dyn CommentRepository>,
+        rules: TodoRules,
+    ) -> Self {
         TodoServiceImpl {
             repository,
+            tag_repository,
+            list_repository,
+            item_repository,
+            search_repository,
+            comment_repository,
+            rules,
+        }
+    }
+
+    // Todos can only be added to (or moved into) lists that exist, are not archived and that
+    // the principal may write to
+    async fn check_list(&self, list_id: Option<i32>) -> Result<(), CommonError> {
+        let list_id = match list_id {
+            Some(list_id) => list_id,
+            None => return Ok(()),
+        };
+        let list = self.list_repository
+            .get(list_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if list.archived {
+            return Err(CommonError {
+                message: format!("List {} is archived", list_id),
+                code: CONFLICT_ERROR_CODE,
+            });
+        }
+        self.list_repository
+            .access(list_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?
+            .authorize_write(&Principal::current())
+    }
+
+    async fn check_owner(&self, todo_id: i32) -> Result<(), CommonError> {
+        check_todo_owner(self.repository.as_ref(), self.list_repository.as_ref(), todo_id).await
+    }
+
+    async fn find(&self, todo_id: i32) -> Result<Todo, CommonError> {
+        self.repository
+            .get(todo_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn with_details(&self, mut todos: Vec<Todo>) -> Result<Vec<Todo>, CommonError> {
+        if todos.is_empty() {
+            return Ok(todos);
+        }
+        let todo_ids = todos.iter().map(|todo| todo.id).collect::<Vec<_>>();
+        let mut tags = self.tag_repository
+            .list_for_todos(&todo_ids)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        let mut progress = self.item_repository
+            .progress(&todo_ids)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        let mut comment_counts = self.comment_repository
+            .count_for_todos(&todo_ids)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        for todo in todos.iter_mut() {
+            todo.tags = tags.remove(&todo.id).unwrap_or_default();
+            todo.comment_count = comment_counts.remove(&todo.id).unwrap_or_default();
+            let progress = progress.remove(&todo.id).unwrap_or_default();
+            todo.done_items = progress.done_items;
+            todo.total_items = progress.total_items;
+        }
+        Ok(todos)
+    }
+
+    async fn with_detail(&self, todo: Todo) -> Result<Todo, CommonError> {
+        let mut todos = self.with_details(vec![todo]).await?;
+        Ok(todos.remove(0))
+    }
+
+    // Completes the todo when its last open item is done, as far as the workflow allows it
+    async fn complete_if_finished(&self, todo_id: i32) -> Result<(), CommonError> {
+        if !self.rules.auto_complete {
+            return Ok(());
+        }
+        let progress = self.item_repository
+            .progress(&[todo_id])
+            .await
+            .map_err(|e| -> CommonError { e.into() })?
+            .remove(&todo_id)
+            .unwrap_or_default();
+        if !progress.is_finished() {
+            return Ok(());
+        }
+        let todo = self.find(todo_id).await?;
+        if todo.status == TodoStatus::Done || !self.rules.workflow.allows(todo.status, TodoStatus::Done) {
+            return Ok(());
+        }
+        self.repository
+            .complete_many(&[todo_id], None)
+            .await
+            .map(|_| ())
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    fn validate_item_title(title: &str) -> Result<(), CommonError> {
+        if title.trim().is_empty() {
+            return Err(CommonError {
+                message: "Item title must not be empty".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_transition(&self, todo_id: i32, from: TodoStatus, to: TodoStatus) -> Result<(), CommonError> {
+        if self.rules.workflow.allows(from, to) {
+            return Ok(());
+        }
+        Err(CommonError {
+            message: format!("Todo {} cannot move from {} to {}", todo_id, from, to),
+            code: CONFLICT_ERROR_CODE,
+        })
+    }
+
+    // Checks a transition against the status the todo will have after the preceding operations of a batch
+    async fn check_batch_transition(
+        &self, statuses: &mut HashMap<i32, TodoStatus>, todo_id: i32, to: TodoStatus,
+    ) -> Result<(), CommonError> {
+        let from = match statuses.get(&todo_id) {
+            Some(status) => *status,
+            None => self.find(todo_id).await?.status,
+        };
+        self.check_transition(todo_id, from, to)?;
+        statuses.insert(todo_id, to);
+        Ok(())
+    }
+
+    fn validate_schedule(due_at: Option<DateTime<Utc>>, remind_at: Option<DateTime<Utc>>) -> Result<(), CommonError> {
+        if let (Some(due_at), Some(remind_at)) = (due_at, remind_at) {
+            if remind_at > due_at {
+                return Err(CommonError {
+                    message: "Reminder must not be after the due date".to_string(),
+                    code: VALIDATION_ERROR_CODE,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_rule(recurrence: &Recurrence) -> Result<(), CommonError> {
+        recurrence.rule().map(|_| ()).map_err(|message| CommonError {
+            message,
+            code: VALIDATION_ERROR_CODE,
+        })
+    }
+
+    // Occurrences are scheduled relative to the due date, so recurring todos need one
+    fn validate_recurrence(recurrence: Option<&Recurrence>, due_at: Option<DateTime<Utc>>) -> Result<(), CommonError> {
+        let recurrence = match recurrence {
+            Some(recurrence) => recurrence,
+            None => return Ok(()),
+        };
+        Self::validate_rule(recurrence)?;
+        if due_at.is_none() {
+            return Err(CommonError {
+                message: "Recurring todos need a due date".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+
+    // Checks the recurrence the todo ends up with when an update touches it or the due date
+    fn validate_updated_recurrence(current: &Todo, todo: &UpdateTodo) -> Result<(), CommonError> {
+        let recurrence = todo.recurrence.clone().unwrap_or_else(|| current.recurrence.clone());
+        Self::validate_recurrence(recurrence.as_ref(), todo.due_at.unwrap_or(current.due_at))
+    }
+
+    fn validate_create(todo: &CreateTodo) -> Result<(), CommonError> {
+        Self::validate_schedule(todo.due_at, todo.remind_at)?;
+        Self::validate_recurrence(todo.recurrence.as_ref(), todo.due_at)
+    }
+
+    fn validate_update(todo: &UpdateTodo) -> Result<(), CommonError> {
+        if todo.is_empty() {
+            return Err(CommonError {
+                message: "Nothing to update".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Self::validate_schedule(todo.due_at.flatten(), todo.remind_at.flatten())?;
+        match todo.recurrence.as_ref().and_then(Option::as_ref) {
+            Some(recurrence) => Self::validate_rule(recurrence),
+            None => Ok(()),
+        }
+    }
+
+    async fn validate_batch_operation(
+        &self, statuses: &mut HashMap<i32, TodoStatus>, operation: &TodoOperation,
+    ) -> Result<(), CommonError> {
+        match operation {
+            TodoOperation::Create(todo) => {
+                Self::validate_create(todo)?;
+                self.check_list(todo.list_id).await
+            }
+            TodoOperation::Update { todo_id, todo, .. } => {
+                Self::validate_update(todo)?;
+                self.check_owner(*todo_id).await?;
+                self.check_list(todo.list_id.flatten()).await?;
+                if todo.recurrence.is_some() || todo.due_at.is_some() {
+                    Self::validate_updated_recurrence(&self.find(*todo_id).await?, todo)?;
+                }
+                match todo.status {
+                    Some(status) => self.check_batch_transition(statuses, *todo_id, status).await,
+                    None => Ok(()),
+                }
+            }
+            TodoOperation::Complete { todo_id, .. } => {
+                self.check_owner(*todo_id).await?;
+                self.check_batch_transition(statuses, *todo_id, TodoStatus::Done).await
+            }
+            TodoOperation::Delete { todo_id, .. } => self.check_owner(*todo_id).await,
+        }
+    }
+
+    async fn apply(&self, operation: TodoOperation) -> Result<Option<Todo>, CommonError> {
+        match operation {
+            TodoOperation::Create(todo) => self.create(todo).await.map(Some),
+            TodoOperation::Update { todo_id, todo, expected_version } => {
+                self.update(todo_id, todo, expected_version).await.map(Some)
+            }
+            TodoOperation::Delete { todo_id, expected_version } => {
+                self.delete(todo_id, expected_version).await.map(|_| None)
+            }
+            TodoOperation::Complete { todo_id, completed_by } => self.complete(todo_id, completed_by).await.map(Some),
         }
     }
 }
@@ -24,6 +316,8 @@ impl TodoServiceImpl {
 #[async_trait]
 impl TodoService for TodoServiceImpl {
     async fn create(&self, todo: CreateTodo) -> Result<Todo, CommonError> {
+        Self::validate_create(&todo)?;
+        self.check_list(todo.list_id).await?;
         let mut cloned = todo.clone();
         self.repository
             .create(&mut cloned)
@@ -32,22 +326,289 @@ impl TodoService for TodoServiceImpl {
     }
 
     async fn list(&self, params: TodoQueryParams) -> Result<ResultPaging<Todo>, CommonError> {
-        self.repository
+        // Deleted todos of everyone are only listed to admins
+        if params.include_deleted.unwrap_or(false) {
+            Principal::current().authorize(Permission::ModifyAnyTodo)?;
+        }
+        let selection = self.repository
             .list(params)
             .await
-            .map_err(|e| -> CommonError { e.into() })
+            .map_err(|e| -> CommonError { e.into() })?;
+        Ok(ResultPaging {
+            total: selection.total,
+            items: self.with_details(selection.items).await?,
+        })
     }
 
     async fn get(&self, todo_id: i32) -> Result<Todo, CommonError> {
+        let todo = self.find(todo_id).await?;
+        self.with_detail(todo).await
+    }
+
+    async fn update(&self, todo_id: i32, todo: UpdateTodo, expected_version: Option<i32>) -> Result<Todo, CommonError> {
+        Self::validate_update(&todo)?;
+        self.check_owner(todo_id).await?;
+        self.check_list(todo.list_id.flatten()).await?;
+        let mut expected_version = expected_version;
+        if todo.status.is_some() || todo.recurrence.is_some() || todo.due_at.is_some() {
+            let current = self.find(todo_id).await?;
+            Self::validate_updated_recurrence(&current, &todo)?;
+            if let Some(status) = todo.status {
+                self.check_transition(todo_id, current.status, status)?;
+                // Guard against the status changing between the check and the update
+                expected_version = expected_version.or(Some(current.version));
+            }
+        }
+        let todo = self.repository
+            .update(todo_id, &todo, expected_version)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        self.with_detail(todo).await
+    }
+
+    async fn delete(&self, todo_id: i32, expected_version: Option<i32>) -> Result<(), CommonError> {
+        self.check_owner(todo_id).await?;
         self.repository
-            .get(todo_id)
+            .delete(todo_id, expected_version)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn complete(&self, todo_id: i32, completed_by: Option<String>) -> Result<Todo, CommonError> {
+        self.check_owner(todo_id).await?;
+        let current = self.find(todo_id).await?;
+        self.check_transition(todo_id, current.status, TodoStatus::Done)?;
+        let todo = self.repository
+            .complete_many(&[todo_id], completed_by)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?
+            .pop()
+            .ok_or_else(|| CommonError {
+                message: format!("Todo {} not found", todo_id),
+                code: NOT_FOUND_ERROR_CODE,
+            })?;
+        self.with_detail(todo).await
+    }
+
+    async fn reopen(&self, todo_id: i32) -> Result<Todo, CommonError> {
+        self.check_owner(todo_id).await?;
+        let current = self.find(todo_id).await?;
+        if current.status != TodoStatus::Done {
+            return self.with_detail(current).await;
+        }
+        self.check_transition(todo_id, current.status, TodoStatus::Todo)?;
+        let todo = self.repository
+            .reopen(todo_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        self.with_detail(todo).await
+    }
+
+    async fn bulk(&self, operations: Vec<TodoOperation>, atomic: bool) -> Result<Vec<Result<Option<Todo>, CommonError>>, CommonError> {
+        if operations.len() > MAX_BULK_OPERATIONS {
+            return Err(CommonError {
+                message: format!("At most {} operations are allowed per request", MAX_BULK_OPERATIONS),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        if !atomic {
+            let mut results = Vec::with_capacity(operations.len());
+            for operation in operations {
+                results.push(self.apply(operation).await);
+            }
+            return Ok(results);
+        }
+        let mut statuses = HashMap::new();
+        for (index, operation) in operations.iter().enumerate() {
+            self.validate_batch_operation(&mut statuses, operation).await.map_err(|e| CommonError {
+                message: format!("Operation {} failed: {}", index, e.message),
+                code: e.code,
+            })?;
+        }
+        let todos = self.repository
+            .execute_batch(&operations)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        // Keep the positions of operations without a resulting todo (deletes)
+        let positions = todos.iter().map(Option::is_some).collect::<Vec<_>>();
+        let mut detailed = self.with_details(todos.into_iter().flatten().collect()).await?.into_iter();
+        Ok(positions.into_iter().map(|has_todo| Ok(if has_todo { detailed.next() } else { None })).collect())
+    }
+
+    async fn restore(&self, todo_id: i32) -> Result<Todo, CommonError> {
+        self.check_owner(todo_id).await?;
+        let todo = self.repository
+            .restore(todo_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        self.with_detail(todo).await
+    }
+
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> Result<usize, CommonError> {
+        self.repository
+            .purge_deleted(deleted_before)
             .await
             .map_err(|e| -> CommonError { e.into() })
     }
 
-    async fn delete(&self, todo_id: i32) -> Result<(), CommonError> {
+    async fn due_reminders(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Todo>, CommonError> {
         self.repository
-            .delete(todo_id)
+            .list_due_reminders(now, limit)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn mark_reminded(&self, todos: &[Todo]) -> Result<(), CommonError> {
+        let reminders = todos.iter()
+            .filter_map(|todo| todo.remind_at.map(|remind_at| (todo.id, remind_at)))
+            .collect::<Vec<_>>();
+        if reminders.is_empty() {
+            return Ok(());
+        }
+        self.repository
+            .mark_reminded(&reminders)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn search(&self, params: TodoSearchParams) -> Result<ResultPaging<TodoSearchHit>, CommonError> {
+        let q = params.q.trim().to_string();
+        if q.is_empty() || q.chars().count() > MAX_SEARCH_QUERY_LENGTH {
+            return Err(CommonError {
+                message: format!("Search query must be between 1 and {} characters", MAX_SEARCH_QUERY_LENGTH),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        let selection = self.search_repository
+            .search(TodoSearchParams { q, ..params })
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        let (todos, matches): (Vec<_>, Vec<_>) = selection.items.into_iter()
+            .map(|hit| (hit.todo, (hit.rank, hit.snippet)))
+            .unzip();
+        Ok(ResultPaging {
+            total: selection.total,
+            items: self.with_details(todos).await?.into_iter().zip(matches)
+                .map(|(todo, (rank, snippet))| TodoSearchHit { todo, rank, snippet })
+                .collect(),
+        })
+    }
+
+    async fn move_todo(&self, todo_id: i32, target: MoveTodo) -> Result<Todo, CommonError> {
+        let placement = match (target.before, target.after) {
+            (Some(before), None) => TodoPlacement::Before(before),
+            (None, Some(after)) => TodoPlacement::After(after),
+            _ => return Err(CommonError {
+                message: "Exactly one of before and after must be given".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            }),
+        };
+        if placement.anchor_id() == todo_id {
+            return Err(CommonError {
+                message: "A todo cannot be moved next to itself".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        self.check_owner(todo_id).await?;
+        let todo = self.repository
+            .move_todo(todo_id, placement)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        self.with_detail(todo).await
+    }
+
+    async fn occurrences(&self, todo_id: i32, limit: Option<usize>) -> Result<Vec<DateTime<Utc>>, CommonError> {
+        let limit = limit.unwrap_or(DEFAULT_OCCURRENCE_PREVIEW_LIMIT);
+        if limit == 0 || limit > MAX_OCCURRENCE_PREVIEW_LIMIT {
+            return Err(CommonError {
+                message: format!("Limit must be between 1 and {}", MAX_OCCURRENCE_PREVIEW_LIMIT),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        let todo = self.find(todo_id).await?;
+        match (todo.recurrence, todo.due_at) {
+            (Some(recurrence), Some(due_at)) => recurrence.preview(due_at, limit).map_err(|message| CommonError {
+                message,
+                code: VALIDATION_ERROR_CODE,
+            }),
+            _ => Err(CommonError {
+                message: format!("Todo {} does not repeat", todo_id),
+                code: UNPROCESSABLE_ERROR_CODE,
+            }),
+        }
+    }
+
+    async fn list_items(&self, todo_id: i32) -> Result<Vec<TodoItem>, CommonError> {
+        self.item_repository
+            .list(todo_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn add_item(&self, todo_id: i32, item: CreateTodoItem) -> Result<TodoItem, CommonError> {
+        Self::validate_item_title(&item.title)?;
+        if matches!(item.position, Some(position) if position < 0) {
+            return Err(CommonError {
+                message: "Item position must not be negative".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        self.check_owner(todo_id).await?;
+        let item = CreateTodoItem { title: item.title.trim().to_string(), ..item };
+        self.item_repository
+            .create(todo_id, &item)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn update_item(&self, todo_id: i32, item_id: i32, item: UpdateTodoItem) -> Result<TodoItem, CommonError> {
+        if item.is_empty() {
+            return Err(CommonError {
+                message: "Nothing to update".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        if let Some(title) = &item.title {
+            Self::validate_item_title(title)?;
+        }
+        self.check_owner(todo_id).await?;
+        let item = UpdateTodoItem { title: item.title.map(|title| title.trim().to_string()), ..item };
+        let item = self.item_repository
+            .update(todo_id, item_id, &item)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if item.done {
+            self.complete_if_finished(todo_id).await?;
+        }
+        Ok(item)
+    }
+
+    async fn toggle_item(&self, todo_id: i32, item_id: i32) -> Result<TodoItem, CommonError> {
+        self.check_owner(todo_id).await?;
+        let item = self.item_repository
+            .toggle(todo_id, item_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        if item.done {
+            self.complete_if_finished(todo_id).await?;
+        }
+        Ok(item)
+    }
+
+    async fn delete_item(&self, todo_id: i32, item_id: i32) -> Result<(), CommonError> {
+        self.check_owner(todo_id).await?;
+        self.item_repository
+            .delete(todo_id, item_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })?;
+        // Removing the last open item finishes the checklist as well
+        self.complete_if_finished(todo_id).await
+    }
+
+    async fn reorder_items(&self, todo_id: i32, item_ids: Vec<i32>) -> Result<Vec<TodoItem>, CommonError> {
+        self.check_owner(todo_id).await?;
+        self.item_repository
+            .reorder(todo_id, &item_ids)
             .await
             .map_err(|e| -> CommonError { e.into() })
     }
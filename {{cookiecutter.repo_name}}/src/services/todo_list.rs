@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::error::{CommonError, VALIDATION_ERROR_CODE};
+use crate::domain::models::authorization::Principal;
+use crate::domain::models::share::{AccessibleTodoList, CreateTodoListShare, ListAccess, TodoListShare};
+use crate::domain::models::todo_list::{CreateTodoList, TodoList, UpdateTodoList};
+use crate::domain::repositories::repository::{QueryParamsImpl, ResultPaging};
+use crate::domain::repositories::todo_list::{TodoListQueryParams, TodoListRepository};
+use crate::domain::services::todo_list::TodoListService;
+
+#[derive(Clone)]
+pub struct TodoListServiceImpl {
+    pub repository: Arc<dyn TodoListRepository>,
+}
+
+impl TodoListServiceImpl {
+    pub fn new(repository: Arc<dyn TodoListRepository>) -> Self {
+        TodoListServiceImpl {
+            repository,
+        }
+    }
+
+    fn validate_name(name: &str) -> Result<(), CommonError> {
+        if name.trim().is_empty() {
+            return Err(CommonError {
+                message: "List name must not be empty".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        Ok(())
+    }
+
+    async fn access(&self, list_id: i32) -> Result<ListAccess, CommonError> {
+        self.repository
+            .access(list_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    // A share names either a user or a group
+    fn validate_share(share: CreateTodoListShare) -> Result<CreateTodoListShare, CommonError> {
+        let group = share.group.map(|group| group.trim().to_string());
+        match (share.user_id, &group) {
+            (Some(_), None) => {}
+            (None, Some(group)) if !group.is_empty() => {}
+            _ => return Err(CommonError {
+                message: "Share the list with either a user or a group".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            }),
+        }
+        Ok(CreateTodoListShare { group, ..share })
+    }
+}
+
+#[async_trait]
+impl TodoListService for TodoListServiceImpl {
+    async fn create(&self, list: CreateTodoList) -> Result<TodoList, CommonError> {
+        Self::validate_name(&list.name)?;
+        self.repository
+            .create(&list)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn list(&self, params: TodoListQueryParams) -> Result<ResultPaging<TodoList>, CommonError> {
+        self.repository
+            .list(params)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn get(&self, list_id: i32) -> Result<TodoList, CommonError> {
+        self.repository
+            .get(list_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn update(&self, list_id: i32, list: UpdateTodoList) -> Result<TodoList, CommonError> {
+        if list.is_empty() {
+            return Err(CommonError {
+                message: "Nothing to update".to_string(),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        if let Some(name) = &list.name {
+            Self::validate_name(name)?;
+        }
+        self.access(list_id).await?.authorize_write(&Principal::current())?;
+        self.repository
+            .update(list_id, &list)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn delete(&self, list_id: i32, cascade: bool) -> Result<(), CommonError> {
+        self.access(list_id).await?.authorize_manage(&Principal::current())?;
+        self.repository
+            .delete(list_id, cascade)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn archive(&self, list_id: i32) -> Result<TodoList, CommonError> {
+        self.access(list_id).await?.authorize_write(&Principal::current())?;
+        self.repository
+            .set_archived(list_id, true)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn unarchive(&self, list_id: i32) -> Result<TodoList, CommonError> {
+        self.access(list_id).await?.authorize_write(&Principal::current())?;
+        self.repository
+            .set_archived(list_id, false)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn list_accessible(&self, params: QueryParamsImpl) -> Result<ResultPaging<AccessibleTodoList>, CommonError> {
+        self.repository
+            .list_accessible(params)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    // Everyone who can see a list can see who else it is shared with
+    async fn list_shares(&self, list_id: i32) -> Result<Vec<TodoListShare>, CommonError> {
+        self.access(list_id).await?;
+        self.repository
+            .list_shares(list_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn share(&self, list_id: i32, share: CreateTodoListShare) -> Result<TodoListShare, CommonError> {
+        let share = Self::validate_share(share)?;
+        let access = self.access(list_id).await?;
+        access.authorize_manage(&Principal::current())?;
+        // Lists nobody owns are visible to everyone already
+        if access.owner_id.is_none() {
+            return Err(CommonError {
+                message: format!("List {} has no owner and is visible to everyone", list_id),
+                code: VALIDATION_ERROR_CODE,
+            });
+        }
+        self.repository
+            .share(list_id, &share)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+
+    async fn unshare(&self, list_id: i32, share_id: i32) -> Result<(), CommonError> {
+        self.access(list_id).await?.authorize_manage(&Principal::current())?;
+        self.repository
+            .unshare(list_id, share_id)
+            .await
+            .map_err(|e| -> CommonError { e.into() })
+    }
+}
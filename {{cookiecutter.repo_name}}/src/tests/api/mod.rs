@@ -1 +1,15 @@
-pub mod test_todo_controllers;
\ No newline at end of file
+pub mod test_todo_controllers;
+pub mod test_tag_controllers;
+pub mod test_todo_list_controllers;
+pub mod test_todo_item_controllers;
+pub mod test_comment_controllers;
+pub mod test_attachment_controllers;
+pub mod test_audit_controllers;
+pub mod test_todo_recurrence_controllers;
+pub mod test_auth_controllers;
+pub mod test_authorization_controllers;
+pub mod test_service_context_controller;
+pub mod test_api_key_controllers;
+pub mod test_oidc_controllers;
+pub mod test_tenant_controllers;
+pub mod test_sharing_controllers;
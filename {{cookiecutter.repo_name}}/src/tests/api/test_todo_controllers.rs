@@ -3,15 +3,19 @@ mod test_todo_controllers{
     use std::env;
     use std::sync::Arc;
     use actix_web::{test};
+    use actix_web::http::{header, StatusCode};
+    use actix_web::http::header::AUTHORIZATION;
+    use chrono::SecondsFormat;
     use testcontainers::clients;
     use serde_json;
     use testcontainers::images::postgres;
-    use actix_clean_architecture::domain::constants::POSTGRESQL_DB_URI;
+    use actix_clean_architecture::api::dto::auth::AuthTokensDTO;
+    use actix_clean_architecture::domain::constants::{ADMIN_EMAILS, ADMIN_PASSWORD, ANONYMOUS_ROLE, POSTGRESQL_DB_URI};
     use actix_clean_architecture::infrastructure::databases::postgresql::db_pool;
     use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
     use serde_json::json;
     use actix_clean_architecture::{container::Container, create_app::create_app};
-    use actix_clean_architecture::domain::models::todo::Todo;
+    use actix_clean_architecture::domain::models::todo::{Todo, TodoPriority, TodoStatus};
     use actix_clean_architecture::domain::repositories::repository::ResultPaging;
 
     pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
@@ -21,7 +25,7 @@ mod test_todo_controllers{
         env::set_var("RUST_BACKTRACE", "1");
         env::set_var("RUST_LOG", "debug");
         env::set_var("RUST_BACKTRACE", "1");
-        env_logger::init();
+        let _ = env_logger::try_init();
 
         let docker = clients::Cli::default();
         let postgres_node = docker.run(postgres::Postgres::default());
@@ -30,13 +34,17 @@ mod test_todo_controllers{
         );
 
         env::set_var(POSTGRESQL_DB_URI, connection_string);
+        env::set_var(ANONYMOUS_ROLE, "member");
+        env::set_var(ADMIN_EMAILS, "admin@example.com");
+        env::set_var(ADMIN_PASSWORD, "correct horse");
 
         {
         let pool = Arc::new(db_pool());
-        pool.get().unwrap().run_pending_migrations(MIGRATIONS).unwrap();
+        pool.get_across_tenants().unwrap().run_pending_migrations(MIGRATIONS).unwrap();
         }
 
         let container = Arc::new(Container::new());
+        container.auth_service.seed_admins().await.unwrap();
 
         let app = test::init_service(create_app(container)).await;
         let request_body = json!({
@@ -69,6 +77,32 @@ mod test_todo_controllers{
         let todos: ResultPaging<Todo> = test::read_body_json(resp).await;
         assert_eq!(todos.items.len(), 2);
 
+        // Timestamp filter test, `created_after` excludes its bound and `updated_since` includes it
+        let req = test::TestRequest::get()
+            .uri(&format!("/todos?created_after={}", todo.created_at.to_rfc3339_opts(SecondsFormat::Micros, true))).to_request();
+        let todos: ResultPaging<Todo> = test::read_body_json(test::call_service(&app, req).await).await;
+        assert_eq!(todos.items.len(), 1);
+        assert_ne!(todos.items[0].id, todo.id);
+        let req = test::TestRequest::get()
+            .uri(&format!("/todos?updated_since={}", todo.updated_at.to_rfc3339_opts(SecondsFormat::Micros, true))).to_request();
+        let todos: ResultPaging<Todo> = test::read_body_json(test::call_service(&app, req).await).await;
+        assert_eq!(todos.items.len(), 2);
+        let req = test::TestRequest::get().uri("/todos?created_after=2999-01-01T00:00:00Z&updated_since=2020-01-01T00:00:00Z").to_request();
+        let todos: ResultPaging<Todo> = test::read_body_json(test::call_service(&app, req).await).await;
+        assert!(todos.items.is_empty());
+        for filter in ["created_after=yesterday", "updated_since=2026-13-01T00:00:00Z", "created_after=2026-10-19"] {
+            let resp = test::TestRequest::get().uri(&format!("/todos?{}", filter)).send_request(&app).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        }
+
+        // Sorting test
+        let req = test::TestRequest::get().uri("/todos?sort_by=created_at&sort_order=desc").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        let todos: ResultPaging<Todo> = test::read_body_json(resp).await;
+        assert!(todos.items[0].created_at >= todos.items[1].created_at);
+        assert_ne!(todos.items[0].id, todo.id);
+
         // Delete test
         let resp = test::TestRequest::delete().uri(&format!("/todos/{}", todo.id)).send_request(&app).await;
         assert!(resp.status().is_success());
@@ -79,5 +113,257 @@ mod test_todo_controllers{
         assert!(resp.status().is_success());
         let todos: ResultPaging<Todo> = test::read_body_json(resp).await;
         assert_eq!(todos.items.len(), 1);
+
+        // Deleted todos are hidden from get but listed to admins with include_deleted
+        let resp = test::TestRequest::get().uri(&format!("/todos/{}", todo.id)).send_request(&app).await;
+        assert!(resp.status().is_client_error());
+        let resp = test::TestRequest::get().uri("/todos?include_deleted=true").send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        let resp = test::TestRequest::post().uri("/auth/login")
+            .set_json(json!({ "email": "admin@example.com", "password": "correct horse" })).send_request(&app).await;
+        let tokens: AuthTokensDTO = test::read_body_json(resp).await;
+        let admin = format!("Bearer {}", tokens.access_token);
+        let resp = test::TestRequest::get().uri("/todos?include_deleted=true")
+            .insert_header((AUTHORIZATION, admin.as_str())).send_request(&app).await;
+        let todos: ResultPaging<Todo> = test::read_body_json(resp).await;
+        assert_eq!(todos.items.len(), 2);
+        assert!(todos.items.iter().any(|t| t.id == todo.id && t.deleted_at.is_some()));
+
+        // Restore test
+        let resp = test::TestRequest::post().uri(&format!("/todos/{}/restore", todo.id)).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let restored_todo: Todo = test::read_body_json(resp).await;
+        assert!(restored_todo.deleted_at.is_none());
+        let resp = test::TestRequest::post().uri(&format!("/todos/{}/restore", todo.id)).send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let req = test::TestRequest::get().uri("/todos").to_request();
+        let resp = test::call_service(&app, req).await;
+        let todos: ResultPaging<Todo> = test::read_body_json(resp).await;
+        assert_eq!(todos.items.len(), 2);
+
+        // Conditional get test
+        let resp = test::TestRequest::get().uri(&format!("/todos/{}", todo.id)).send_request(&app).await;
+        let etag = resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_string();
+        let resp = test::TestRequest::get().uri(&format!("/todos/{}", todo.id))
+            .insert_header((header::IF_NONE_MATCH, etag.clone())).send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+        // Optimistic concurrency test
+        let update_body = json!({ "title": "updated todo" });
+        let resp = test::TestRequest::patch().uri(&format!("/todos/{}", todo.id))
+            .insert_header((header::IF_MATCH, "\"0\"")).set_json(&update_body).send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+        let resp = test::TestRequest::patch().uri(&format!("/todos/{}", todo.id))
+            .insert_header((header::IF_MATCH, etag.clone())).set_json(&update_body).send_request(&app).await;
+        assert!(resp.status().is_success());
+        assert_ne!(resp.headers().get(header::ETAG).unwrap().to_str().unwrap(), etag);
+        let updated_todo: Todo = test::read_body_json(resp).await;
+        assert_eq!(updated_todo.title, "updated todo");
+        assert_eq!(updated_todo.description, todo.description);
+        let resp = test::TestRequest::delete().uri(&format!("/todos/{}", todo.id))
+            .insert_header((header::IF_MATCH, etag)).send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+
+        // Bulk test
+        let bulk_body = json!({
+            "operations": [
+                { "op": "create", "title": "bulk 1", "description": "Bulk description" },
+                { "op": "create", "title": "bulk 2", "description": "Bulk description" },
+                { "op": "complete", "id": todo.id }
+            ]
+        });
+        let resp = test::TestRequest::post().uri("/todos/bulk").set_json(&bulk_body).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let bulk: serde_json::Value = test::read_body_json(resp).await;
+        let results = bulk["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|result| result["success"] == true));
+        assert_eq!(results[1]["todo"]["title"], "bulk 2");
+
+        // Atomic bulk operations are rolled back together
+        let bulk_body = json!({
+            "operations": [
+                { "op": "create", "title": "bulk 3", "description": "Bulk description" },
+                { "op": "complete", "id": 0 }
+            ]
+        });
+        let resp = test::TestRequest::post().uri("/todos/bulk").set_json(&bulk_body).send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let req = test::TestRequest::get().uri("/todos").to_request();
+        let todos: ResultPaging<Todo> = test::read_body_json(test::call_service(&app, req).await).await;
+        assert_eq!(todos.items.len(), 4);
+
+        // Non atomic bulk operations report partial success
+        let bulk_body = json!({
+            "atomic": false,
+            "operations": [
+                { "op": "create", "title": "bulk 3", "description": "Bulk description" },
+                { "op": "complete", "id": 0 }
+            ]
+        });
+        let resp = test::TestRequest::post().uri("/todos/bulk").set_json(&bulk_body).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let bulk: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(bulk["results"][0]["success"], true);
+        assert_eq!(bulk["results"][1]["success"], false);
+
+        // Idempotency key test
+        let resp = test::TestRequest::post().uri("/todos").insert_header(("Idempotency-Key", "create-1"))
+            .set_json(&request_body).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let first_todo: Todo = test::read_body_json(resp).await;
+        let resp = test::TestRequest::post().uri("/todos").insert_header(("Idempotency-Key", "create-1"))
+            .set_json(&request_body).send_request(&app).await;
+        assert!(resp.status().is_success());
+        assert_eq!(resp.headers().get("Idempotent-Replayed").unwrap(), "true");
+        let replayed_todo: Todo = test::read_body_json(resp).await;
+        assert_eq!(first_todo.id, replayed_todo.id);
+        let resp = test::TestRequest::post().uri("/todos").insert_header(("Idempotency-Key", "create-1"))
+            .set_json(&update_body).send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        // Keys are scoped to the user, another user reusing one gets their own response
+        let resp = test::TestRequest::post().uri("/auth/register")
+            .set_json(json!({ "email": "alice@example.com", "password": "correct horse" })).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let resp = test::TestRequest::post().uri("/auth/login")
+            .set_json(json!({ "email": "alice@example.com", "password": "correct horse" })).send_request(&app).await;
+        let tokens: AuthTokensDTO = test::read_body_json(resp).await;
+        let alice = format!("Bearer {}", tokens.access_token);
+        let resp = test::TestRequest::post().uri("/todos").insert_header(("Idempotency-Key", "create-1"))
+            .insert_header((AUTHORIZATION, alice.as_str())).set_json(&request_body).send_request(&app).await;
+        assert!(resp.status().is_success());
+        assert!(resp.headers().get("Idempotent-Replayed").is_none());
+        let alice_todo: Todo = test::read_body_json(resp).await;
+        assert_ne!(alice_todo.id, first_todo.id);
+        let resp = test::TestRequest::post().uri("/todos").insert_header(("Idempotency-Key", "create-1"))
+            .insert_header((AUTHORIZATION, alice.as_str())).set_json(&request_body).send_request(&app).await;
+        assert_eq!(resp.headers().get("Idempotent-Replayed").unwrap(), "true");
+        let replayed_todo: Todo = test::read_body_json(resp).await;
+        assert_eq!(replayed_todo.id, alice_todo.id);
+        // Replays carry the ETag of the original response
+        let mut etags = Vec::new();
+        for _ in 0..2 {
+            let resp = test::TestRequest::patch().uri(&format!("/todos/{}", alice_todo.id))
+                .insert_header(("Idempotency-Key", "update-1")).insert_header((AUTHORIZATION, alice.as_str()))
+                .set_json(json!({ "title": "renamed" })).send_request(&app).await;
+            assert!(resp.status().is_success());
+            etags.push(resp.headers().get(header::ETAG).unwrap().clone());
+        }
+        assert_eq!(etags[0], etags[1]);
+        // Signing in is never replayed, so tokens are not stored
+        for _ in 0..2 {
+            let resp = test::TestRequest::post().uri("/auth/login").insert_header(("Idempotency-Key", "login-1"))
+                .set_json(json!({ "email": "alice@example.com", "password": "correct horse" })).send_request(&app).await;
+            assert!(resp.status().is_success());
+            assert!(resp.headers().get("Idempotent-Replayed").is_none());
+        }
+
+        // Complete and reopen test
+        let resp = test::TestRequest::post().uri(&format!("/todos/{}/complete", first_todo.id))
+            .set_json(json!({ "completed_by": "tester" })).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let completed_todo: Todo = test::read_body_json(resp).await;
+        assert!(completed_todo.completed);
+        assert!(completed_todo.completed_at.is_some());
+        // Only the signed in user completing the todo is recorded, not whoever the client names
+        assert_eq!(completed_todo.completed_by, None);
+        let resp = test::TestRequest::post().uri(&format!("/todos/{}/reopen", first_todo.id)).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let reopened_todo: Todo = test::read_body_json(resp).await;
+        assert!(!reopened_todo.completed);
+        assert!(reopened_todo.completed_at.is_none());
+
+        // Due date test
+        let resp = test::TestRequest::post().uri("/todos").set_json(json!({
+            "title": "overdue todo",
+            "description": "Test description",
+            "due_at": "2020-01-01T12:00:00+02:00",
+            "remind_at": "2020-01-01T08:00:00Z"
+        })).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let overdue_todo: Todo = test::read_body_json(resp).await;
+        assert_eq!(overdue_todo.due_at.unwrap().to_rfc3339(), "2020-01-01T10:00:00+00:00");
+        let resp = test::TestRequest::post().uri("/todos").set_json(json!({
+            "title": "future todo",
+            "description": "Test description",
+            "due_at": "2999-01-01T00:00:00Z"
+        })).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let req = test::TestRequest::get().uri("/todos/overdue").to_request();
+        let todos: ResultPaging<Todo> = test::read_body_json(test::call_service(&app, req).await).await;
+        assert_eq!(todos.items.len(), 1);
+        assert_eq!(todos.items[0].id, overdue_todo.id);
+        let req = test::TestRequest::get().uri("/todos?due_after=2021-01-01T00:00:00Z").to_request();
+        let todos: ResultPaging<Todo> = test::read_body_json(test::call_service(&app, req).await).await;
+        assert_eq!(todos.items.len(), 1);
+        assert_eq!(todos.items[0].title, "future todo");
+        let resp = test::TestRequest::post().uri("/todos").set_json(json!({
+            "title": "invalid todo",
+            "description": "Test description",
+            "due_at": "2020-01-01T00:00:00Z",
+            "remind_at": "2020-01-02T00:00:00Z"
+        })).send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        // Status workflow and priority test
+        let resp = test::TestRequest::post().uri("/todos").set_json(json!({
+            "title": "backlog todo",
+            "description": "Test description",
+            "status": "backlog",
+            "priority": "urgent"
+        })).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let backlog_todo: Todo = test::read_body_json(resp).await;
+        assert_eq!(backlog_todo.status, TodoStatus::Backlog);
+        assert_eq!(backlog_todo.priority, TodoPriority::Urgent);
+        let resp = test::TestRequest::patch().uri(&format!("/todos/{}", backlog_todo.id))
+            .set_json(json!({ "status": "done" })).send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let resp = test::TestRequest::post().uri(&format!("/todos/{}/complete", backlog_todo.id)).send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let resp = test::TestRequest::patch().uri(&format!("/todos/{}", backlog_todo.id))
+            .set_json(json!({ "status": "in_progress" })).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let resp = test::TestRequest::patch().uri(&format!("/todos/{}", backlog_todo.id))
+            .set_json(json!({ "status": "done" })).send_request(&app).await;
+        assert!(resp.status().is_success());
+        let done_todo: Todo = test::read_body_json(resp).await;
+        assert!(done_todo.completed);
+        assert!(done_todo.completed_at.is_some());
+        let req = test::TestRequest::get().uri("/todos?priority=urgent&status=done").to_request();
+        let todos: ResultPaging<Todo> = test::read_body_json(test::call_service(&app, req).await).await;
+        assert_eq!(todos.items.len(), 1);
+        assert_eq!(todos.items[0].id, backlog_todo.id);
+        let req = test::TestRequest::get().uri("/todos?sort_by=priority&sort_order=desc").to_request();
+        let todos: ResultPaging<Todo> = test::read_body_json(test::call_service(&app, req).await).await;
+        assert_eq!(todos.items[0].id, backlog_todo.id);
+        let resp = test::TestRequest::post().uri(&format!("/todos/{}/reopen", backlog_todo.id)).send_request(&app).await;
+        let reopened_todo: Todo = test::read_body_json(resp).await;
+        assert_eq!(reopened_todo.status, TodoStatus::Todo);
+        assert!(!reopened_todo.completed);
+
+        // Full-text search test
+        let resp = test::TestRequest::post().uri("/todos").set_json(json!({
+            "title": "Renew passport",
+            "description": "Book an appointment at the consulate"
+        })).send_request(&app).await;
+        let passport_todo: Todo = test::read_body_json(resp).await;
+        let resp = test::TestRequest::post().uri("/todos").set_json(json!({
+            "title": "Travel checklist",
+            "description": "Bring the passport and the tickets"
+        })).send_request(&app).await;
+        let travel_todo: Todo = test::read_body_json(resp).await;
+        let req = test::TestRequest::get().uri("/todos/search?q=passports").to_request();
+        let hits: serde_json::Value = test::read_body_json(test::call_service(&app, req).await).await;
+        let hits = hits["items"].as_array().unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0]["todo"]["id"], passport_todo.id);
+        assert_eq!(hits[1]["todo"]["id"], travel_todo.id);
+        assert!(hits[1]["snippet"].as_str().unwrap().contains("<mark>passport</mark>"));
+        let req = test::TestRequest::get().uri("/todos/search?q=consulate%20-passport").to_request();
+        let hits: serde_json::Value = test::read_body_json(test::call_service(&app, req).await).await;
+        assert!(hits["items"].as_array().unwrap().is_empty());
+        let resp = test::TestRequest::get().uri("/todos/search?q=%20").send_request(&app).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
 }
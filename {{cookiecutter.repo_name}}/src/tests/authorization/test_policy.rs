@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod test_policy {
+    use actix_clean_architecture::domain::error::{FORBIDDEN_ERROR_CODE, UNAUTHORIZED_ERROR_CODE};
+    use actix_clean_architecture::domain::models::authorization::{Permission, Principal, Role};
+    use actix_clean_architecture::domain::models::share::{ListAccess, SharePermission};
+
+    #[test]
+    fn test_roles() {
+        assert!(Role::Admin.allows(Permission::ManageServiceContext));
+        assert!(Role::Admin.allows(Permission::ModifyAnyTodo));
+        assert!(Role::Member.allows(Permission::WriteTodos));
+        assert!(!Role::Member.allows(Permission::ModifyAnyTodo));
+        assert!(!Role::Member.allows(Permission::ReadAuditLog));
+        assert!(Role::Viewer.allows(Permission::ReadTodos));
+        assert!(!Role::Viewer.allows(Permission::WriteTodos));
+        assert_eq!("viewer".parse::<Role>(), Ok(Role::Viewer));
+        assert!("owner".parse::<Role>().is_err());
+    }
+
+    #[test]
+    fn test_ownership() {
+        let member = Principal::user(1, Role::Member);
+        assert!(member.can_modify(Some(1)));
+        assert!(member.can_modify(None));
+        assert!(!member.can_modify(Some(2)));
+        assert_eq!(member.authorize_modify(Some(2)).unwrap_err().code, FORBIDDEN_ERROR_CODE);
+        assert!(Principal::user(3, Role::Admin).can_modify(Some(2)));
+        assert!(!Principal::user(1, Role::Viewer).can_modify(Some(1)));
+        assert!(Principal::anonymous(Some(Role::Member)).can_modify(None));
+        assert!(!Principal::anonymous(Some(Role::Member)).can_modify(Some(1)));
+    }
+
+    #[test]
+    fn test_list_access() {
+        let member = Principal::user(1, Role::Member);
+        let own = ListAccess { owner_id: Some(1), shared: None };
+        assert!(own.can_manage(&member) && own.can_write(&member));
+        let read = ListAccess { owner_id: Some(2), shared: Some(SharePermission::Read) };
+        assert!(!read.can_write(&member));
+        assert_eq!(read.authorize_write(&member).unwrap_err().code, FORBIDDEN_ERROR_CODE);
+        // Writing to a list does not make it yours to share or delete
+        let write = ListAccess { owner_id: Some(2), shared: Some(SharePermission::Write) };
+        assert!(write.can_write(&member));
+        assert!(!write.can_manage(&member));
+        assert!(!write.can_write(&Principal::user(1, Role::Viewer)));
+        assert!(write.can_manage(&Principal::user(3, Role::Admin)));
+        let open = ListAccess { owner_id: None, shared: None };
+        assert!(open.can_write(&member));
+        assert!(SharePermission::Write > SharePermission::Read);
+        assert_eq!("write".parse::<SharePermission>(), Ok(SharePermission::Write));
+    }
+
+    #[test]
+    fn test_anonymous() {
+        let error = Principal::anonymous(None).authorize(Permission::ReadTodos).unwrap_err();
+        assert_eq!(error.code, UNAUTHORIZED_ERROR_CODE);
+        let error = Principal::anonymous(Some(Role::Viewer)).authorize(Permission::WriteTodos).unwrap_err();
+        assert_eq!(error.code, FORBIDDEN_ERROR_CODE);
+    }
+
+    #[test]
+    fn test_scopes() {
+        let api_key = Principal::user(1, Role::Member).scoped(vec![Permission::ReadTodos]);
+        assert!(api_key.can(Permission::ReadTodos));
+        assert!(!api_key.can(Permission::WriteTodos));
+        assert!(!api_key.can_modify(Some(1)));
+        // Scopes never grant more than the role
+        let api_key = Principal::user(1, Role::Viewer).scoped(vec![Permission::WriteTodos]);
+        assert!(!api_key.can(Permission::WriteTodos));
+        assert_eq!("manage_users".parse::<Permission>(), Ok(Permission::ManageUsers));
+    }
+
+    #[actix_web::test]
+    async fn test_current() {
+        // Outside of any scope nothing is allowed, jobs run as the system explicitly
+        assert_eq!(Principal::current(), Principal::anonymous(None));
+        assert!(!Principal::current().can(Permission::ReadTodos));
+        let system = Principal::system().scope(async { Principal::current() }).await;
+        assert!(system.can(Permission::ModifyAnyTodo));
+        let viewer = Principal::user(1, Role::Viewer);
+        let current = viewer.clone().scope(async { Principal::current() }).await;
+        assert_eq!(current, viewer);
+    }
+}
@@ -1 +1,4 @@
-pub mod api;
\ No newline at end of file
+pub mod api;
+pub mod authorization;
+pub mod jobs;
+pub mod storage;
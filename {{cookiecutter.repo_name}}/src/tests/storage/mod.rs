@@ -0,0 +1 @@
+pub mod test_s3_blob_store;
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod test_s3_blob_store {
+    use bytes::Bytes;
+    use futures_util::StreamExt;
+    use testcontainers::clients;
+    use testcontainers::core::WaitFor;
+    use testcontainers::RunnableImage;
+    use testcontainers::images::generic::GenericImage;
+    use actix_clean_architecture::domain::services::blob_store::{BlobStore, BlobStream};
+    use actix_clean_architecture::infrastructure::services::s3_blob_store::{S3BlobStore, S3Config};
+
+    async fn collect(mut stream: BlobStream) -> Vec<u8> {
+        let mut content = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            content.extend_from_slice(&chunk.unwrap());
+        }
+        content
+    }
+
+    // Runs the S3 blob store against MinIO as a local stand-in for S3
+    #[actix_web::test]
+    async fn test() {
+        let _ = env_logger::try_init();
+
+        let docker = clients::Cli::default();
+        let minio = GenericImage::new("minio/minio", "latest")
+            .with_env_var("MINIO_ROOT_USER", "minioadmin")
+            .with_env_var("MINIO_ROOT_PASSWORD", "minioadmin")
+            .with_exposed_port(9000)
+            .with_wait_for(WaitFor::message_on_stdout("API:"));
+        let minio_node = docker.run(RunnableImage::from((minio, vec!["server".to_string(), "/data".to_string()])));
+
+        let store = S3BlobStore::new(S3Config {
+            endpoint: format!("http://127.0.0.1:{}", minio_node.get_host_port_ipv4(9000)),
+            bucket: "attachments".to_string(),
+            region: "us-east-1".to_string(),
+            access_key_id: "minioadmin".to_string(),
+            secret_access_key: "minioadmin".to_string(),
+        });
+        store.create_bucket().await.unwrap();
+        // Creating an existing bucket is not an error
+        store.create_bucket().await.unwrap();
+
+        let key = "todos/1/test";
+        store.put(key, "text/plain", Bytes::from_static(b"0123456789")).await.unwrap();
+        assert_eq!(collect(store.get(key, None).await.unwrap()).await, b"0123456789");
+        assert_eq!(collect(store.get(key, Some((2, 4))).await.unwrap()).await, b"234");
+
+        store.delete(key).await.unwrap();
+        assert!(store.get(key, None).await.is_err());
+    }
+}